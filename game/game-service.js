@@ -126,16 +126,17 @@ app.get('/api/game/bot-modes', (req, res) => {
  * @route {GET} /play
  * @param {string} req.query.position - Estado del tablero en JSON YEN (obligatorio)
  * @param {string} [req.query.bot_id] - Identificador del bot. Por defecto: hard_bot
+ * @param {number} [req.query.time_ms] - Tiempo restante del bot en milisegundos
  * @returns {Object} {"coords":{"x":N,"y":N,"z":N}} o {"action":"resign"}
  * @throws {400} Si falta position
  * @throws {500} Si hay un error en el bot
  */
 app.get('/play', async (req, res) => {
-  const { position, bot_id } = req.query;
+  const { position, bot_id, time_ms } = req.query;
   if (!position) return res.status(400).json({ error: 'position requerido' });
   try {
     const response = await axios.get(`${GAMEY_BOT_URL}/play`, {
-      params: { position, bot_id: bot_id || 'hard_bot' },
+      params: { position, bot_id: bot_id || 'hard_bot', time_ms },
     });
     res.json(response.data);
   } catch (error) {
@@ -238,9 +239,10 @@ app.post('/api/game/start', async (req, res) => {
       startingPlayer = 'j1',
     } = req.body;
 
-    const ALLOWED_BOARD_SIZES = boardVariant === 'tetra3d'
-      ? [3, 4, 5, 6]
-      : [8, 11, 15, 19];
+    const ALLOWED_BOARD_SIZES = {
+      tetra3d: [3, 4, 5, 6],
+      hex: [7, 9, 11, 13],
+    }[boardVariant] || [8, 11, 15, 19];
     const defaultBoardSize = boardVariant === 'tetra3d' ? 4 : 11;
     const boardSize = ALLOWED_BOARD_SIZES.includes(Number(rawBoardSize))
       ? Number(rawBoardSize)
@@ -249,7 +251,9 @@ app.post('/api/game/start', async (req, res) => {
     const gameId = `game_${randomUUID()}`;
     
     if (boardVariant === 'tetra3d') {
-      await axios.post(`${GAMEY_BOT_URL}/v1/tetra/start`, { size: boardSize }, { timeout: 5000 });
+      await axios.post(`${GAMEY_BOT_URL}/v1/tetra/start`, { size: boardSize, game_id: gameId }, { timeout: 5000 });
+    } else if (boardVariant === 'hex') {
+      await axios.post(`${GAMEY_BOT_URL}/v1/hex/start`, { size: boardSize, game_id: gameId }, { timeout: 5000 });
     } else {
       await axios.post(`${GAMEY_BOT_URL}/v1/game/start`, { board_size: boardSize, game_id: gameId }, { timeout: 5000 });
     }
@@ -266,7 +270,7 @@ app.post('/api/game/start', async (req, res) => {
       boardVariant,
       boardSize,
       startingPlayer: normalizedStartingPlayer,
-      board: boardVariant === 'tetra3d' ? initializeTetraBoard(boardSize) : initializeBoard(boardSize),
+      board: initializeVariantBoard(boardVariant, boardSize),
       connectedFaces: boardVariant === 'tetra3d' ? { j1: [], j2: [] } : undefined,
       connectionEdges: boardVariant === 'tetra3d' ? { j1: [], j2: [] } : undefined,
       hasBranch: boardVariant === 'tetra3d' ? { j1: false, j2: false } : undefined,
@@ -328,6 +332,7 @@ app.post('/api/game/:gameId/validateMove', async (req, res) => {
       const rustResponse = await axios.post(`${GAMEY_BOT_URL}/v1/tetra/move`, {
         ...tetraMove,
         player: toGamey[game.currentPlayer],
+        game_id: gameId,
       });
 
       game.moves.push({ position: move, userId });
@@ -359,6 +364,38 @@ app.post('/api/game/:gameId/validateMove', async (req, res) => {
     }
   }
 
+  if (game.boardVariant === 'hex') {
+    try {
+      const { toGamey, toLogical } = getPlayerMapping(game);
+      const rustResponse = await axios.post(`${GAMEY_BOT_URL}/v1/hex/move`, {
+        cell: move,
+        player: toGamey[game.currentPlayer],
+        game_id: gameId,
+      });
+
+      game.moves.push({ position: move, userId });
+      game.board = updateHexBoardFromRust(rustResponse.data.board, toLogical);
+      if (rustResponse.data.turn !== null && rustResponse.data.turn !== undefined) {
+        game.currentPlayer = toLogical[rustResponse.data.turn];
+      }
+
+      if (rustResponse.data.status === 'finished') {
+        game.winner = toLogical[rustResponse.data.winner];
+        await finishGameAndSave(game);
+      }
+
+      return res.json({
+        valid: true,
+        winner: rustResponse.data.status === 'finished' ? toLogical[rustResponse.data.winner] : null,
+        status: rustResponse.data.status,
+      });
+    } catch (error) {
+      return res.status(error.response?.status || 400).json({
+        error: error.response?.data?.message || 'Invalid hex move',
+      });
+    }
+  }
+
   const [x, y, z] = move.replaceAll('(', '').replaceAll(')', '').split(',').map(v => Number(v.trim()));
 
   // Usamos el turno actual del juego para determinar el player
@@ -402,6 +439,7 @@ app.post('/api/game/:gameId/validateMove', async (req, res) => {
  * @param {string} req.params.gameId - El ID del juego
  * @param {Object} req.body
  * @param {string} req.body.role - El rol (debe ser j2 para el bot)
+ * @param {number} [req.body.timeMs] - Tiempo restante en el reloj del bot, en milisegundos
  * @returns {Object} Estado actualizado del juego después del movimiento del bot
  * @throws {404} Si el juego no se encuentra
  * @throws {400} Si no es el turno del bot
@@ -421,7 +459,8 @@ app.post('/api/game/:gameId/vsBot/move', async (req, res) => {
     if (game.boardVariant === 'tetra3d') {
       const { toLogical } = getPlayerMapping(game);
       const rustResponse = await axios.post(
-        `${GAMEY_BOT_URL}/v1/tetra/bot/${game.botMode}`
+        `${GAMEY_BOT_URL}/v1/tetra/bot/${game.botMode}`,
+        { game_id: gameId }
       );
 
       game.board = updateTetraBoardFromRust(rustResponse.data.board, toLogical);
@@ -461,6 +500,38 @@ app.post('/api/game/:gameId/vsBot/move', async (req, res) => {
       });
     }
 
+    if (game.boardVariant === 'hex') {
+      const { toLogical } = getPlayerMapping(game);
+      const rustResponse = await axios.post(
+        `${GAMEY_BOT_URL}/v1/hex/bot/${game.botMode}`,
+        { game_id: gameId }
+      );
+
+      game.board = updateHexBoardFromRust(rustResponse.data.board, toLogical);
+      if (rustResponse.data.turn !== null && rustResponse.data.turn !== undefined) {
+        game.currentPlayer = toLogical[rustResponse.data.turn];
+      }
+
+      if (rustResponse.data.status === 'finished') {
+        game.status = 'finished';
+        game.winner = toLogical[rustResponse.data.winner];
+        await finishGameAndSave(game);
+      }
+
+      if (rustResponse.data.lastMove) {
+        game.moves.push({ position: rustResponse.data.lastMove, player: 'j2', userId: 'bot' });
+      }
+
+      return res.json({
+        gameId,
+        board: game.board,
+        moves: game.moves,
+        turn: game.currentPlayer,
+        winner: game.winner || null,
+        status: game.status,
+      });
+    }
+
    // NOSONAR: uso de Math.random solo para delay UX, no afecta seguridad
 const array = new Uint32Array(1);
 webcrypto.getRandomValues(array);
@@ -472,13 +543,24 @@ await sleep(delay);
     const botRoute = BOT_ROUTES[game.botMode] || BOT_ROUTES['random_bot'];
     const rustResponse = await axios.post(
       `${GAMEY_BOT_URL}${botRoute}`,
-      { ...convertToYEN(game), game_id: gameId }
+      { ...convertToYEN(game), game_id: gameId, time_ms: req.body?.timeMs }
     );
 
     if (!rustResponse.data?.board) {
       return res.status(500).json({ error: 'Rust no devolvió tablero' });
     }
 
+    // Con la regla de intercambio la ficha de apertura cambia de dueño (y puede
+    // moverse), así que se copia el tablero completo devuelto por Rust
+    if (rustResponse.data.action === 'swap') {
+      game.board.forEach(c => {
+        const [x, y, z] = c.position.replaceAll('(', '').replaceAll(')', '').split(',').map(Number);
+        const m = rustResponse.data.board.find(r => r.x === x && r.y === y && r.z === z);
+        c.player = m ? toLogical[m.player] : null;
+      });
+      game.moves.push({ position: 'swap', player: 'j2', userId: 'bot' });
+    }
+
     // Actualizar solo la celda correspondiente
     rustResponse.data.board.forEach(m => {
   const cell = game.board.find(c => {
@@ -774,6 +856,34 @@ function initializeTetraBoard(boardSize) {
   return board;
 }
 
+/**
+ * Inicializar el tablero de Hex con celdas vacías en notación de Hex (a1, b1...)
+ * @param {number} boardSize - El lado del rombo
+ * @returns {Array} Array de celdas del tablero con posición y jugador
+ */
+function initializeHexBoard(boardSize) {
+  const board = [];
+  for (let row = 0; row < boardSize; row++) {
+    for (let col = 0; col < boardSize; col++) {
+      board.push({ position: `${String.fromCodePoint(97 + col)}${row + 1}`, player: null });
+    }
+  }
+  return board;
+}
+
+function initializeVariantBoard(boardVariant, boardSize) {
+  if (boardVariant === 'tetra3d') return initializeTetraBoard(boardSize);
+  if (boardVariant === 'hex') return initializeHexBoard(boardSize);
+  return initializeBoard(boardSize);
+}
+
+function updateHexBoardFromRust(rustBoard, toLogical) {
+  return rustBoard.map(cell => ({
+    position: cell.cell,
+    player: cell.player === null || cell.player === undefined ? null : toLogical[cell.player],
+  }));
+}
+
 function parseTetraPosition(move) {
   const parts = move.replaceAll('(', '').replaceAll(')', '').split(',').map(v => Number(v.trim()));
   if (parts.length !== 4 || parts.some(Number.isNaN)) {
@@ -931,6 +1041,8 @@ module.exports._test = {
   games,
   initializeBoard,
   initializeTetraBoard,
+  initializeHexBoard,
+  updateHexBoardFromRust,
   parseTetraPosition,
   updateTetraBoardFromRust,
   mapConnectedFaces,
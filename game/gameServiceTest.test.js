@@ -15,6 +15,8 @@ const {
   games,
   initializeBoard,
   initializeTetraBoard,
+  initializeHexBoard,
+  updateHexBoardFromRust,
   parseTetraPosition,
   updateTetraBoardFromRust,
   mapConnectedFaces,
@@ -29,6 +31,32 @@ const {
 
 const rustClassicStartOk = { data: {} };
 const rustTetraStartOk = { data: {} };
+const rustHexStartOk = { data: {} };
+
+const rustHexMoveOk = {
+  data: {
+    board: [
+      { row: 0, col: 0, cell: 'a1', player: 0 },
+      { row: 0, col: 1, cell: 'b1', player: null },
+    ],
+    turn: 1,
+    status: 'active',
+    winner: null,
+  },
+};
+
+const rustHexFinishedBotOk = {
+  data: {
+    board: [
+      { row: 0, col: 0, cell: 'a1', player: 0 },
+      { row: 0, col: 1, cell: 'b1', player: 1 },
+    ],
+    turn: null,
+    status: 'finished',
+    winner: 0,
+    lastMove: 'a1',
+  },
+};
 
 const rustClassicMoveOk = {
   data: {
@@ -132,6 +160,18 @@ async function startTetraGame(overrides = {}) {
   });
 }
 
+async function startHexGame(overrides = {}) {
+  axios.post.mockResolvedValueOnce(rustHexStartOk);
+  return request(app).post('/api/game/start').send({
+    userId: 'jugador1',
+    gameMode: 'vsBot',
+    botMode: 'random_bot',
+    boardVariant: 'hex',
+    boardSize: 7,
+    ...overrides,
+  });
+}
+
 beforeEach(() => {
   jest.clearAllMocks();
   games.clear();
@@ -383,11 +423,32 @@ describe('POST /api/game/start', () => {
     expect(res.body.hasBranch).toEqual({ j1: false, j2: false });
     expect(axios.post).toHaveBeenCalledWith(
       expect.stringContaining('/v1/tetra/start'),
-      { size: 4 },
+      { size: 4, game_id: res.body.gameId },
       { timeout: 5000 }
     );
   });
 
+  test('crea una partida hex', async () => {
+    const res = await startHexGame();
+
+    expect(res.status).toBe(200);
+    expect(res.body.boardVariant).toBe('hex');
+    expect(res.body.board).toHaveLength(49);
+    expect(res.body.board[1]).toEqual({ position: 'b1', player: null });
+    expect(axios.post).toHaveBeenCalledWith(
+      expect.stringContaining('/v1/hex/start'),
+      { size: 7, game_id: res.body.gameId },
+      { timeout: 5000 }
+    );
+  });
+
+  test('normaliza tamano hex invalido a 11', async () => {
+    const res = await startHexGame({ boardSize: 30 });
+
+    expect(res.status).toBe(200);
+    expect(res.body.board).toHaveLength(121);
+  });
+
   test('normaliza tamano tetra invalido a 4', async () => {
     const res = await startTetraGame({ boardSize: 20 });
 
@@ -464,6 +525,10 @@ describe('POST /api/game/:gameId/validateMove', () => {
       j2: [],
     });
     expect(res.body.hasBranch).toEqual({ j1: true, j2: false });
+    expect(axios.post).toHaveBeenLastCalledWith(
+      expect.stringContaining('/v1/tetra/move'),
+      expect.objectContaining({ game_id: start.body.gameId })
+    );
   });
 
   test('mantiene el turno tetra si rust devuelve turn null', async () => {
@@ -522,6 +587,37 @@ describe('POST /api/game/:gameId/validateMove', () => {
     expect(res.body.error).toMatch(/invalid tetra move/i);
   });
 
+  test('valida un movimiento hex activo', async () => {
+    const start = await startHexGame();
+    axios.post.mockResolvedValueOnce(rustHexMoveOk);
+
+    const res = await request(app)
+      .post(`/api/game/${start.body.gameId}/validateMove`)
+      .send({ userId: 'u1', move: 'a1' });
+
+    expect(res.status).toBe(200);
+    expect(res.body.status).toBe('active');
+    expect(axios.post).toHaveBeenLastCalledWith(
+      expect.stringContaining('/v1/hex/move'),
+      { cell: 'a1', player: 0, game_id: start.body.gameId }
+    );
+    const game = games.get(start.body.gameId);
+    expect(game.currentPlayer).toBe('j2');
+    expect(game.board[0]).toEqual({ position: 'a1', player: 'j1' });
+  });
+
+  test('devuelve error hex si rust rechaza el movimiento', async () => {
+    const start = await startHexGame();
+    axios.post.mockRejectedValueOnce(new Error('hex fail'));
+
+    const res = await request(app)
+      .post(`/api/game/${start.body.gameId}/validateMove`)
+      .send({ userId: 'u1', move: 'z9' });
+
+    expect(res.status).toBe(400);
+    expect(res.body.error).toMatch(/invalid hex move/i);
+  });
+
   test('alterna el turno clasico tambien cuando empieza j2', async () => {
     const start = await startClassicGame({ startingPlayer: 'j2' });
     axios.post.mockResolvedValueOnce(rustClassicMoveOk);
@@ -586,6 +682,47 @@ describe('POST /api/game/:gameId/vsBot/move', () => {
     expect(res.body.turn).toBe('j2');
   });
 
+  test('aplica el intercambio elegido por el bot en clasico', async () => {
+    const start = await startClassicGame();
+    axios.post.mockResolvedValueOnce(rustClassicMoveOk);
+
+    await request(app)
+      .post(`/api/game/${start.body.gameId}/validateMove`)
+      .send({ userId: 'u1', move: '(10,0,0)' });
+
+    axios.post.mockResolvedValueOnce({
+      data: {
+        board: [{ x: 10, y: 0, z: 0, player: 1 }],
+        action: 'swap',
+        turn: 0,
+        status: 'active',
+        winner: null,
+      },
+    });
+
+    const res = await request(app).post(`/api/game/${start.body.gameId}/vsBot/move`).send({});
+
+    expect(res.status).toBe(200);
+    expect(res.body.board.find(c => c.position === '(10,0,0)').player).toBe('j2');
+    expect(res.body.moves.at(-1)).toEqual({ position: 'swap', player: 'j2', userId: 'bot' });
+    expect(res.body.turn).toBe('j1');
+  });
+
+  test('reenvía a rust el tiempo restante del bot', async () => {
+    const start = await startClassicGame({ startingPlayer: 'j2' });
+    axios.post.mockResolvedValueOnce(rustClassicMoveOk);
+
+    const res = await request(app)
+      .post(`/api/game/${start.body.gameId}/vsBot/move`)
+      .send({ timeMs: 1500 });
+
+    expect(res.status).toBe(200);
+    expect(axios.post).toHaveBeenLastCalledWith(
+      expect.stringContaining('/v1/ybot/choose/'),
+      expect.objectContaining({ game_id: start.body.gameId, time_ms: 1500 })
+    );
+  });
+
   test('devuelve error si rust no devuelve tablero en clasico', async () => {
     const start = await startClassicGame({ startingPlayer: 'j2' });
     axios.post.mockResolvedValueOnce({ data: { turn: 1, status: 'active' } });
@@ -633,6 +770,27 @@ describe('POST /api/game/:gameId/vsBot/move', () => {
     expect(res.body.connectionEdges.j2).toEqual([{ from: '(0,0,0,3)', to: '(0,1,0,2)' }]);
     expect(res.body.hasBranch.j2).toBe(true);
     expect(GameModel.findOneAndUpdate).toHaveBeenCalled();
+    expect(axios.post).toHaveBeenCalledWith(
+      expect.stringContaining('/v1/tetra/bot/'),
+      { game_id: start.body.gameId }
+    );
+  });
+
+  test('procesa un movimiento hex del bot y finaliza', async () => {
+    const start = await startHexGame({ startingPlayer: 'j2', botMode: 'hard_bot' });
+    axios.post.mockResolvedValueOnce(rustHexFinishedBotOk);
+
+    const res = await request(app).post(`/api/game/${start.body.gameId}/vsBot/move`).send({});
+
+    expect(res.status).toBe(200);
+    expect(res.body.status).toBe('finished');
+    expect(res.body.winner).toBe('j2');
+    expect(res.body.moves).toEqual([{ position: 'a1', player: 'j2', userId: 'bot' }]);
+    expect(GameModel.findOneAndUpdate).toHaveBeenCalled();
+    expect(axios.post).toHaveBeenCalledWith(
+      expect.stringContaining('/v1/hex/bot/hard_bot'),
+      { game_id: start.body.gameId }
+    );
   });
 
   test('procesa un movimiento tetra activo sin lastMove y con turn undefined', async () => {
@@ -753,6 +911,29 @@ describe('Auxiliares internas', () => {
     expect(board).toContainEqual({ position: '(0,0,0,3)', player: null });
   });
 
+  test('initializeHexBoard crea el rombo en notacion de Hex', () => {
+    const board = initializeHexBoard(3);
+
+    expect(board).toHaveLength(9);
+    expect(board[0]).toEqual({ position: 'a1', player: null });
+    expect(board[5]).toEqual({ position: 'c2', player: null });
+  });
+
+  test('updateHexBoardFromRust traduce jugadores y nulos', () => {
+    const board = updateHexBoardFromRust(
+      [
+        { row: 0, col: 0, cell: 'a1', player: 1 },
+        { row: 0, col: 1, cell: 'b1', player: null },
+      ],
+      { 0: 'j1', 1: 'j2' }
+    );
+
+    expect(board).toEqual([
+      { position: 'a1', player: 'j2' },
+      { position: 'b1', player: null },
+    ]);
+  });
+
   test('parseTetraPosition parsea posiciones validas e invalidas', () => {
     expect(parseTetraPosition('(1,2,3,4)')).toEqual({ a: 1, b: 2, c: 3, d: 4 });
     expect(() => parseTetraPosition('(1,2,3)')).toThrow(/invalid tetra position/i);
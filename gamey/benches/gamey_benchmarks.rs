@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use gamey::{Coordinates, GameY, Movement, PlayerId, RenderOptions};
+use gamey::{Coordinates, GameY, Movement, PlayerId, RenderOptions, TetraGame};
 
 /// Benchmarks for coordinate conversion functions
 fn bench_coordinates(c: &mut Criterion) {
@@ -123,6 +123,62 @@ fn bench_add_move(c: &mut Criterion) {
     group.finish();
 }
 
+/// Plays a deterministic game on `game` until it finishes.
+fn play_out(game: &mut GameY) {
+    let size = game.board_size();
+    let mut step = 0;
+    while let Some(player) = game.next_player() {
+        let available = game.available_cells();
+        if available.is_empty() {
+            break;
+        }
+        let idx = available[(step * 7) % available.len()];
+        let coords = Coordinates::from_index(idx, size);
+        let _ = game.add_move(Movement::Placement { player, coords });
+        step += 1;
+    }
+}
+
+/// Benchmarks for the operations search bots repeat the most: cloning a
+/// position and playing a game to the end from it
+fn bench_search_workload(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_workload");
+
+    for board_size in [11, 15, 19].iter() {
+        // A position in the middle of a game
+        let mut game = GameY::new(*board_size);
+        let total_cells = (board_size * (board_size + 1)) / 2;
+        for step in 0..(total_cells / 3) {
+            let available = game.available_cells();
+            let idx = available[(step as usize * 7) % available.len()];
+            let coords = Coordinates::from_index(idx, *board_size);
+            let player = PlayerId::new(step % 2);
+            let _ = game.add_move(Movement::Placement { player, coords });
+        }
+
+        group.bench_with_input(BenchmarkId::new("clone", board_size), &game, |b, game| {
+            b.iter(|| black_box(game.clone()))
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("playout", board_size),
+            board_size,
+            |b, &size| {
+                b.iter_batched(
+                    || GameY::new(size),
+                    |mut game| {
+                        play_out(&mut game);
+                        black_box(game)
+                    },
+                    criterion::BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmarks for board rendering
 fn bench_render(c: &mut Criterion) {
     let mut group = c.benchmark_group("render");
@@ -198,13 +254,56 @@ fn bench_touches_side(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmarks for the tetrahedral variant
+fn bench_tetra(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tetra");
+
+    for board_size in [4, 6, 8].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("fill_board", board_size),
+            board_size,
+            |b, &size| {
+                b.iter(|| {
+                    let mut game = TetraGame::new(size).unwrap();
+                    for coords in game.available_moves() {
+                        let Some(player) = game.next_player() else {
+                            break;
+                        };
+                        game.place(coords, player).unwrap();
+                    }
+                    black_box(game)
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("best_component", board_size),
+            board_size,
+            |b, &size| {
+                let mut game = TetraGame::new(size).unwrap();
+                for coords in game.available_moves().into_iter().step_by(3) {
+                    let Some(player) = game.next_player() else {
+                        break;
+                    };
+                    game.place(coords, player).unwrap();
+                }
+                b.iter(|| black_box(game.best_component_for_player(PlayerId::new(0))))
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_coordinates,
     bench_game_creation,
     bench_add_move,
+    bench_search_workload,
     bench_render,
     bench_touches_side,
+    bench_tetra,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,218 @@
+//! Move analysis for bots.
+//!
+//! Bots that score every cell can share those scores through
+//! [`YBot::analyze`](crate::YBot::analyze) instead of keeping only the best
+//! one. Each candidate comes as a [`MoveAnalysis`] with the bot's score and a
+//! [`MoveReason`] telling, in one word, why the move is good. Hints and
+//! post-game reviews are built on top of it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Coordinates, GameY};
+
+/// The main reason a move is suggested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveReason {
+    /// The move wins the game.
+    Win,
+    /// The move stops an opponent, either from winning on their next move or
+    /// along their shortest path.
+    Block,
+    /// The move extends or joins the player's own chains towards the sides.
+    Connect,
+    /// The move builds a bridge: a gap between stones that the opponent
+    /// cannot cut in one move.
+    Bridge,
+    /// The move takes a central cell.
+    Centrality,
+}
+
+/// A candidate move with the score a bot gives it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveAnalysis {
+    /// The cell to play.
+    pub coords: Coordinates,
+    /// The bot's score for the move. Scores are only comparable between moves
+    /// analysed by the same bot in the same position.
+    pub score: f64,
+    /// The main reason for the move.
+    pub reason: MoveReason,
+}
+
+/// The score of a cell, kept as the weighted terms it is made of.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ScoreTerms {
+    terms: Vec<(MoveReason, f64)>,
+}
+
+impl ScoreTerms {
+    /// Creates an empty score.
+    pub(crate) fn new() -> Self {
+        ScoreTerms::default()
+    }
+
+    /// Adds a weighted term standing for `reason` and returns the score.
+    pub(crate) fn with(mut self, reason: MoveReason, value: f64) -> Self {
+        self.terms.push((reason, value));
+        self
+    }
+
+    /// Returns the sum of the terms, added in the order they were given.
+    pub(crate) fn total(&self) -> f64 {
+        self.terms
+            .iter()
+            .fold(0.0, |total, (_, value)| total + value)
+    }
+
+    /// Returns the reason whose terms add up to the most, the first given on
+    /// ties, or [`MoveReason::Centrality`] for an empty score.
+    pub(crate) fn reason(&self) -> MoveReason {
+        let mut sums: Vec<(MoveReason, f64)> = Vec::new();
+        for &(reason, value) in &self.terms {
+            match sums.iter_mut().find(|(r, _)| *r == reason) {
+                Some((_, sum)) => *sum += value,
+                None => sums.push((reason, value)),
+            }
+        }
+        sums.into_iter()
+            .reduce(|best, next| if next.1 > best.1 { next } else { best })
+            .map_or(MoveReason::Centrality, |(reason, _)| reason)
+    }
+}
+
+/// Scores every empty cell of `board` for the player to move with `score`
+/// and returns the best `count` of them, best first.
+///
+/// A cell that wins the game is tagged [`MoveReason::Win`] and one where an
+/// opponent would win is tagged [`MoveReason::Block`], whatever the terms of
+/// its score say. Equal scores keep the cell order.
+pub(crate) fn analyze_cells<F>(board: &GameY, count: usize, score: F) -> Vec<MoveAnalysis>
+where
+    F: Fn(&Coordinates) -> ScoreTerms,
+{
+    let Some(player) = board.next_player() else {
+        return Vec::new();
+    };
+    let opponents = board.opponents(player);
+    let size = board.board_size();
+
+    let mut moves: Vec<MoveAnalysis> = board
+        .available_cells()
+        .iter()
+        .map(|&idx| {
+            let coords = Coordinates::from_index(idx, size);
+            let terms = score(&coords);
+            let reason = if board.connects_all_sides(player, &coords) {
+                MoveReason::Win
+            } else if opponents
+                .iter()
+                .any(|&opponent| board.connects_all_sides(opponent, &coords))
+            {
+                MoveReason::Block
+            } else {
+                terms.reason()
+            };
+            MoveAnalysis {
+                coords,
+                score: terms.total(),
+                reason,
+            }
+        })
+        .collect();
+
+    moves.sort_by(|a, b| b.score.total_cmp(&a.score));
+    moves.truncate(count);
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Movement, PlayerId};
+
+    fn place(game: &mut GameY, player: u32, x: u32, y: u32, z: u32) {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords: Coordinates::new(x, y, z),
+        })
+        .unwrap();
+    }
+
+    fn reason_at(moves: &[MoveAnalysis], coords: Coordinates) -> MoveReason {
+        moves
+            .iter()
+            .find(|analysis| analysis.coords == coords)
+            .unwrap()
+            .reason
+    }
+
+    #[test]
+    fn test_total_and_reason() {
+        let terms = ScoreTerms::new()
+            .with(MoveReason::Connect, 2.0)
+            .with(MoveReason::Bridge, 3.0)
+            .with(MoveReason::Connect, 1.5);
+        assert_eq!(terms.total(), 6.5);
+        assert_eq!(terms.reason(), MoveReason::Connect);
+        assert_eq!(ScoreTerms::new().total(), 0.0);
+        assert_eq!(ScoreTerms::new().reason(), MoveReason::Centrality);
+    }
+
+    #[test]
+    fn test_reason_ties_keep_the_first() {
+        let terms = ScoreTerms::new()
+            .with(MoveReason::Block, 1.0)
+            .with(MoveReason::Centrality, 1.0);
+        assert_eq!(terms.reason(), MoveReason::Block);
+    }
+
+    #[test]
+    fn test_reason_serializes_in_snake_case() {
+        let json = serde_json::to_string(&MoveReason::Centrality).unwrap();
+        assert_eq!(json, "\"centrality\"");
+        let reason: MoveReason = serde_json::from_str("\"win\"").unwrap();
+        assert_eq!(reason, MoveReason::Win);
+    }
+
+    #[test]
+    fn test_analyze_cells_sorts_and_truncates() {
+        let game = GameY::new(3);
+        let moves = analyze_cells(&game, 2, |coords| {
+            ScoreTerms::new().with(MoveReason::Centrality, f64::from(coords.x()))
+        });
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].coords, Coordinates::new(2, 0, 0));
+        assert_eq!(moves[0].score, 2.0);
+        assert!(moves[0].score >= moves[1].score);
+    }
+
+    #[test]
+    fn test_analyze_cells_tags_wins_and_blocks() {
+        // Player 0 can win at (0, 1, 1)
+        let mut game = GameY::new(3);
+        for (player, x, y, z) in [(0, 0, 2, 0), (1, 2, 0, 0), (0, 0, 0, 2)] {
+            place(&mut game, player, x, y, z);
+        }
+        // Player 1 must block it
+        let moves = analyze_cells(&game, usize::MAX, |_| ScoreTerms::new());
+        assert_eq!(
+            reason_at(&moves, Coordinates::new(0, 1, 1)),
+            MoveReason::Block
+        );
+        // Player 0 takes it
+        place(&mut game, 1, 1, 1, 0);
+        let moves = analyze_cells(&game, usize::MAX, |_| ScoreTerms::new());
+        assert_eq!(
+            reason_at(&moves, Coordinates::new(0, 1, 1)),
+            MoveReason::Win
+        );
+    }
+
+    #[test]
+    fn test_analyze_cells_empty_when_game_over() {
+        let mut game = GameY::new(1);
+        place(&mut game, 0, 0, 0, 0);
+        assert!(analyze_cells(&game, 5, |_| ScoreTerms::new()).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! Search budgets for bots.
+//!
+//! A [`SearchBudget`] tells a bot how much it may think about one move: a
+//! wall-clock time, a number of search nodes, a search depth, or any mix of
+//! them, plus an optional flag that another thread can raise to stop the
+//! search early. It is passed to [`YBot::choose_move_with_budget`](crate::YBot::choose_move_with_budget).
+//! Bots that do not search ignore it.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Limits on the thinking a bot may do for one move.
+///
+/// Every limit is optional, and the default budget is unlimited. A bot stops
+/// at whichever limit it reaches first, and never goes beyond its own
+/// configured limits. However small the budget, a bot that searches still
+/// returns a move as long as the game is not over.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::time::Duration;
+/// use gamey::SearchBudget;
+///
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// let budget = SearchBudget::new()
+///     .with_time(Duration::from_millis(500))
+///     .with_cancel_flag(cancel.clone());
+///
+/// assert!(!budget.is_cancelled());
+/// cancel.store(true, Ordering::Relaxed);
+/// assert!(budget.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchBudget {
+    time: Option<Duration>,
+    nodes: Option<u64>,
+    depth: Option<u32>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl SearchBudget {
+    /// Share of the remaining clock kept in reserve by [`SearchBudget::from_clock`],
+    /// for the network and the server.
+    pub const CLOCK_RESERVE: u32 = 10;
+
+    /// Creates an unlimited budget.
+    pub fn new() -> Self {
+        SearchBudget::default()
+    }
+
+    /// Creates a budget for a player with `remaining` time on their clock.
+    ///
+    /// One part in [`SearchBudget::CLOCK_RESERVE`] of the remaining time is
+    /// kept in reserve, so that the move reaches the clock before it runs out.
+    pub fn from_clock(remaining: Duration) -> Self {
+        SearchBudget::new().with_time(remaining - remaining / Self::CLOCK_RESERVE)
+    }
+
+    /// Sets the maximum wall-clock time and returns the updated budget.
+    pub fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Sets the maximum number of search nodes and returns the updated budget.
+    ///
+    /// What a node is depends on the bot: a playout for
+    /// [`MctsBot`](crate::MctsBot), a searched position for
+    /// [`SearchBot`](crate::SearchBot).
+    pub fn with_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    /// Sets the maximum search depth, in moves, and returns the updated budget.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Sets a flag that stops the search once it is raised and returns the
+    /// updated budget.
+    pub fn with_cancel_flag(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Returns the maximum wall-clock time, if any.
+    pub fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// Returns the maximum number of search nodes, if any.
+    pub fn nodes(&self) -> Option<u64> {
+        self.nodes
+    }
+
+    /// Returns the maximum search depth, if any.
+    pub fn depth(&self) -> Option<u32> {
+        self.depth
+    }
+
+    /// Returns true if the cancellation flag has been raised.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+}
+
+/// Returns the tighter of two optional limits, where None means no limit.
+pub(crate) fn tighter<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_unlimited() {
+        let budget = SearchBudget::new();
+        assert_eq!(budget.time(), None);
+        assert_eq!(budget.nodes(), None);
+        assert_eq!(budget.depth(), None);
+        assert!(!budget.is_cancelled());
+    }
+
+    #[test]
+    fn test_builders() {
+        let budget = SearchBudget::new()
+            .with_time(Duration::from_millis(250))
+            .with_nodes(1000)
+            .with_depth(3);
+        assert_eq!(budget.time(), Some(Duration::from_millis(250)));
+        assert_eq!(budget.nodes(), Some(1000));
+        assert_eq!(budget.depth(), Some(3));
+    }
+
+    #[test]
+    fn test_from_clock_keeps_a_reserve() {
+        let budget = SearchBudget::from_clock(Duration::from_secs(2));
+        assert_eq!(budget.time(), Some(Duration::from_millis(1800)));
+        assert_eq!(
+            SearchBudget::from_clock(Duration::ZERO).time(),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_cancel_flag() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let budget = SearchBudget::new().with_cancel_flag(cancel.clone());
+        assert!(!budget.is_cancelled());
+        cancel.store(true, Ordering::Relaxed);
+        assert!(budget.is_cancelled());
+        assert!(budget.clone().is_cancelled());
+    }
+
+    #[test]
+    fn test_tighter() {
+        assert_eq!(tighter(Some(3), Some(5)), Some(3));
+        assert_eq!(tighter(None, Some(5)), Some(5));
+        assert_eq!(tighter(Some(3), None), Some(3));
+        assert_eq!(tighter::<u32>(None, None), None);
+    }
+}
@@ -1,7 +1,8 @@
 //! ## Algorithm
 //!
-//! The bot pre-computes six BFS win-distance maps (one per side per player) and
-//! uses them together with structural positional bonuses to score every empty cell.
+//! The bot pre-computes three BFS win-distance maps (one per side) for itself and
+//! for every opponent still in the game, and uses them together with structural
+//! positional bonuses to score every empty cell.
 //! The cell with the highest score is selected as the move.
 //!
 //! ### Scoring layers (in order of descending weight)
@@ -9,9 +10,10 @@
 //! 1. **Immediate win detection** – If placing here wins the game right now,
 //!    return an infinite score immediately. Never miss a winning move.
 //!
-//! 2. **Immediate block** – If the opponent would win on their next turn at this
-//!    cell, assign a near-infinite blocking score. Never let the opponent win
-//!    in one move.
+//! 2. **Immediate block** – If an opponent would win on their next turn at this
+//!    cell, assign a near-infinite blocking score. Never let an opponent win
+//!    in one move. With more than two players, opponents are checked in turn
+//!    order, so the one who moves first is blocked first.
 //!
 //! 3. **Own win-path score** – Using a 0-1 BFS from each side, we compute how
 //!    many empty cells must be filled to reach every cell from side A, B, and C
@@ -19,10 +21,10 @@
 //!    (minimax), ensuring the bot reduces its weakest side rather than
 //!    over-extending along one edge.
 //!
-//! 4. **Opponent win-path blocking score** – The same metric for the opponent.
-//!    Placing on a cell that is on the opponent's shortest path disrupts their
+//! 4. **Opponent win-path blocking score** – The same metric for each opponent.
+//!    Placing on a cell that is on an opponent's shortest path disrupts their
 //!    plan. Scaled by an urgency multiplier that increases as the opponent
-//!    approaches a win.
+//!    approaches a win; the most threatened opponent path sets the score.
 //!
 //! 5. **Junction bonus** – A cell that, after placement, connects friendly
 //!    chains touching *different* sides scores much higher, because it directly
@@ -39,10 +41,31 @@
 //!
 //! 8. **Centrality** – `min(x, y, z)` normalised to [0, 1]. Central cells
 //!    have more neighbours and more strategic flexibility.
+//!
+//! ### Misère
+//!
+//! When the game is played misère, connecting the three sides loses. The bot
+//! then skips the immediate win and block checks and scores cells with the
+//! own-path terms negated: a move that completes its own Y is played only when
+//! nothing else is left, cells that would complete an opponent's Y are left
+//! empty, and cells far from the bot's paths and off the sides are preferred.
+//!
+//! ### Swap and resignation
+//!
+//! Through [`YBot::choose_movement`] the bot may also answer with an action.
+//! When it can use the swap rule it takes over an opening stone whose
+//! centrality is at least [`HardBot::SWAP_CENTRALITY`], since central openings
+//! are the strongest. In a two-player game it resigns once the loss is certain:
+//! the opponent has two or more winning cells and the bot has none, so only one
+//! of them can be blocked.
 
 use std::collections::{HashSet, VecDeque};
 
-use crate::{Coordinates, GameY, PlayerId, YBot};
+use crate::analysis::{ScoreTerms, analyze_cells};
+use crate::{
+    Coordinates, GameAction, GameY, MoveAnalysis, MoveReason, Movement, PlayerId, SearchBudget,
+    YBot,
+};
 
 
 
@@ -52,9 +75,29 @@ const SIDE_C: u8 = 0b100; // z == 0
 
 pub struct HardBot;
 
+/// Win-distance maps of one player from each of the three sides.
+struct SideDistances {
+    a: Vec<u32>,
+    b: Vec<u32>,
+    c: Vec<u32>,
+}
+
+impl SideDistances {
+    fn compute(board: &GameY, player: PlayerId) -> Self {
+        Self {
+            a: HardBot::win_distance(board, player, SIDE_A),
+            b: HardBot::win_distance(board, player, SIDE_B),
+            c: HardBot::win_distance(board, player, SIDE_C),
+        }
+    }
+}
 
 
 impl HardBot {
+    /// Smallest centrality of the opening stone, from zero on the sides to one
+    /// in the middle of the board, for which the bot uses the swap rule.
+    pub const SWAP_CENTRALITY: f64 = 0.5;
+
     fn merge_component_sides(
         board: &GameY,
         player: PlayerId,
@@ -86,7 +129,7 @@ impl HardBot {
         dist: &mut [u32],
         queue: &mut VecDeque<usize>,
     ) {
-        if Self::side_mask(coords) & side == 0 {
+        if Self::side_mask(board, coords) & side == 0 {
             return;
         }
 
@@ -106,7 +149,7 @@ impl HardBot {
     fn traversal_cost(board: &GameY, coords: &Coordinates, player: PlayerId) -> Option<u32> {
         match board.player_at(coords) {
             Some(p) if p == player => Some(0),
-            None => Some(1),
+            None if !board.is_blocked(coords) => Some(1),
             _ => None,
         }
     }
@@ -137,7 +180,7 @@ impl HardBot {
         player: PlayerId,
     ) -> u8 {
         let size = board.board_size();
-        let mut mask = Self::side_mask(candidate);
+        let mut mask = Self::side_mask(board, candidate);
         let mut seen: HashSet<usize> = HashSet::new();
 
         for nb in Self::neighbors(candidate) {
@@ -154,7 +197,7 @@ impl HardBot {
         player: PlayerId,
         base_mask: u8,
     ) -> bool {
-        let mut combined = base_mask | Self::side_mask(follow_up);
+        let mut combined = base_mask | Self::side_mask(board, follow_up);
 
         for nb in Self::neighbors(follow_up) {
             if nb == *candidate || board.player_at(&nb) != Some(player) {
@@ -187,16 +230,22 @@ impl HardBot {
         nb
     }
 
-    /// Returns a bitmask of which sides `coords` touches.
+    /// Returns a bitmask of which sides `coords` touches on `board`, whose
+    /// blocked cells may move the sides. Blocked cells touch no side.
     #[inline]
-    fn side_mask(coords: &Coordinates) -> u8 {
+    fn side_mask(board: &GameY, coords: &Coordinates) -> u8 {
         let mut m = 0u8;
-        if coords.touches_side_a() { m |= SIDE_A; }
-        if coords.touches_side_b() { m |= SIDE_B; }
-        if coords.touches_side_c() { m |= SIDE_C; }
+        if board.touches_side_a(coords) { m |= SIDE_A; }
+        if board.touches_side_b(coords) { m |= SIDE_B; }
+        if board.touches_side_c(coords) { m |= SIDE_C; }
         m
     }
 
+    /// Returns true if `coords` is a free cell that can still be played.
+    fn is_empty(board: &GameY, coords: &Coordinates) -> bool {
+        board.player_at(coords).is_none() && !board.is_blocked(coords)
+    }
+
     /// Computes the minimum number of *additional empty cells* that `player`
     /// must fill to create a connected path starting from cells on `side`.
     ///
@@ -246,7 +295,7 @@ impl HardBot {
         queue.push_back(*start);
 
         while let Some(cur) = queue.pop_front() {
-            sides |= Self::side_mask(&cur);
+            sides |= Self::side_mask(board, &cur);
             for nb in Self::neighbors(&cur) {
                 let nidx = nb.to_index(size) as usize;
                 if !visited.contains(&nidx) && board.player_at(&nb) == Some(player) {
@@ -261,7 +310,7 @@ impl HardBot {
     /// Returns `true` if placing `player`'s piece at `candidate` would
     /// immediately win the game (connect all three sides).
     fn is_winning_move(candidate: &Coordinates, board: &GameY, player: PlayerId) -> bool {
-        let mut sides = Self::side_mask(candidate);
+        let mut sides = Self::side_mask(board, candidate);
 
         for nb in Self::neighbors(candidate) {
             if board.player_at(&nb) == Some(player) {
@@ -278,7 +327,7 @@ impl HardBot {
     /// Returns how many distinct sides would be touched by the friendly
     /// component that includes `candidate` after a hypothetical placement.
     fn sides_after_placement(candidate: &Coordinates, board: &GameY, player: PlayerId) -> u8 {
-        let mut sides = Self::side_mask(candidate);
+        let mut sides = Self::side_mask(board, candidate);
         let mut seen_roots: HashSet<usize> = HashSet::new();
         let size = board.board_size();
 
@@ -296,7 +345,7 @@ impl HardBot {
     ) -> usize {
         let p1_empty: HashSet<Coordinates> = Self::neighbors(p1)
             .into_iter()
-            .filter(|c| c != candidate && board.player_at(c).is_none())
+            .filter(|c| c != candidate && Self::is_empty(board, c))
             .collect();
 
         Self::neighbors(p2)
@@ -364,7 +413,7 @@ impl HardBot {
 
         let mut skip_count = 0usize;
         for mid in &direct_nbs {
-            if board.player_at(mid).is_some() { continue; }
+            if !Self::is_empty(board, mid) { continue; }
             for far in Self::neighbors(mid) {
                 if far == *candidate { continue; }
                 if direct_nbs.contains(&far) { continue; }
@@ -391,7 +440,7 @@ impl HardBot {
         }
 
         for nb in Self::neighbors(candidate) {
-            if board.player_at(&nb).is_none()
+            if Self::is_empty(board, &nb)
                 && Self::completes_three_sides_with_follow_up(
                     candidate,
                     &nb,
@@ -410,13 +459,16 @@ impl HardBot {
         candidate: &Coordinates,
         board: &GameY,
         my_id: PlayerId,
-        opp_id: PlayerId,
-    ) -> Option<f64> {
+        opponents: &[PlayerId],
+    ) -> Option<ScoreTerms> {
         if Self::is_winning_move(candidate, board, my_id) {
-            return Some(1_000_000.0);
+            return Some(ScoreTerms::new().with(MoveReason::Win, 1_000_000.0));
         }
-        if Self::is_winning_move(candidate, board, opp_id) {
-            return Some(900_000.0);
+        if opponents
+            .iter()
+            .any(|&opp_id| Self::is_winning_move(candidate, board, opp_id))
+        {
+            return Some(ScoreTerms::new().with(MoveReason::Block, 900_000.0));
         }
 
         None
@@ -500,6 +552,127 @@ impl HardBot {
         }
     }
 
+    /// Scores `candidate` for a misère game, where connecting the three sides
+    /// loses. Higher score → better move.
+    fn misere_score_cell(
+        candidate: &Coordinates,
+        board: &GameY,
+        my_id: PlayerId,
+        opponents: &[PlayerId],
+        mine: &SideDistances,
+    ) -> f64 {
+        if Self::is_winning_move(candidate, board, my_id) {
+            return -1_000_000.0;
+        }
+        let relieves_opponent = opponents
+            .iter()
+            .any(|&opp_id| Self::is_winning_move(candidate, board, opp_id));
+        let relief_penalty = if relieves_opponent { 200.0 } else { 0.0 };
+
+        let size = board.board_size();
+        let idx = candidate.to_index(size) as usize;
+        let (my_path_score, all_paths_bonus) =
+            Self::own_path_scores(size, idx, &mine.a, &mine.b, &mine.c);
+        let junction_score = Self::junction_score(candidate, board, my_id);
+        let near_win_bonus = Self::near_win_bonus(candidate, board, my_id);
+        let chain_len = Self::largest_adjacent_chain(candidate, board, my_id) as f64;
+        let chain_score = (chain_len + 1.0).ln() * 1.5;
+        let side_touch = Self::side_mask(board, candidate).count_ones() as f64;
+
+        -(2.0 * my_path_score
+            + 1.0 * all_paths_bonus
+            + 1.0 * junction_score
+            + 1.0 * near_win_bonus
+            + 0.8 * chain_score
+            + 5.0 * side_touch
+            + relief_penalty)
+    }
+
+    /// Returns the number of empty cells `player` still has to fill to
+    /// connect the three sides, estimated from the side distances.
+    ///
+    /// This is the cheapest cell where the paths from the three sides can
+    /// meet, with the cell itself counted once. A player cut off from some
+    /// side gets a distance larger than the number of cells on the board.
+    pub(crate) fn connection_distance(board: &GameY, player: PlayerId) -> u32 {
+        let size = board.board_size();
+        let sides = SideDistances::compute(board, player);
+        (0..sides.a.len())
+            .filter_map(|idx| {
+                let c = Coordinates::from_index(idx as u32, size);
+                let cost = Self::traversal_cost(board, &c, player)?;
+                let distances = [sides.a[idx], sides.b[idx], sides.c[idx]];
+                if distances.contains(&u32::MAX) {
+                    return None;
+                }
+                Some(distances.iter().sum::<u32>() - 2 * cost)
+            })
+            .min()
+            .unwrap_or(board.total_cells() + 1)
+    }
+
+    /// Returns the empty cells sorted from best to worst by the score the
+    /// bot gives them in a normal game, ties kept in cell order.
+    pub(crate) fn ranked_moves(board: &GameY) -> Vec<Coordinates> {
+        let Some(my_id) = board.next_player() else {
+            return Vec::new();
+        };
+        let opponents = board.opponents(my_id);
+        let size = board.board_size();
+        let mine = SideDistances::compute(board, my_id);
+        let theirs: Vec<SideDistances> = opponents
+            .iter()
+            .map(|&opp_id| SideDistances::compute(board, opp_id))
+            .collect();
+
+        let mut scored: Vec<(Coordinates, f64)> = board
+            .available_cells()
+            .iter()
+            .map(|&idx| {
+                let c = Coordinates::from_index(idx, size);
+                let s = Self::score_cell(&c, board, my_id, &opponents, &mine, &theirs).total();
+                (c, s)
+            })
+            .collect();
+        scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        scored.into_iter().map(|(c, _)| c).collect()
+    }
+
+    /// Returns true if `player` should use the swap rule: it is available to
+    /// them and the opening stone is central enough. Misère games never swap.
+    fn should_swap(board: &GameY, player: PlayerId) -> bool {
+        !board.rules().misere
+            && board.swap_stone(player).is_some_and(|stone| {
+                Self::centrality_score(&stone, board.board_size()) >= Self::SWAP_CENTRALITY
+            })
+    }
+
+    /// Returns true if `player` has certainly lost a two-player game: they
+    /// cannot win with this move and the opponent can win on two or more
+    /// cells, of which only one can be blocked.
+    fn is_hopeless(board: &GameY, player: PlayerId) -> bool {
+        if board.rules().misere || board.num_players() != 2 {
+            return false;
+        }
+        let Some(&opponent) = board.opponents(player).first() else {
+            return false;
+        };
+        let size = board.board_size();
+        let cells: Vec<Coordinates> = board
+            .available_cells()
+            .iter()
+            .map(|&idx| Coordinates::from_index(idx, size))
+            .collect();
+        if cells.iter().any(|c| Self::is_winning_move(c, board, player)) {
+            return false;
+        }
+        cells
+            .iter()
+            .filter(|c| Self::is_winning_move(c, board, opponent))
+            .nth(1)
+            .is_some()
+    }
+
     fn centrality_score(candidate: &Coordinates, size: u32) -> f64 {
         let max_centrality = ((size - 1) as f64) / 3.0;
         if max_centrality <= 0.0 {
@@ -509,27 +682,28 @@ impl HardBot {
         candidate.x().min(candidate.y()).min(candidate.z()) as f64 / max_centrality
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// Scores `candidate` in a normal game, term by term. Higher total →
+    /// better move.
     fn score_cell(
         candidate: &Coordinates,
         board: &GameY,
         my_id: PlayerId,
-        opp_id: PlayerId,
-        da_me:  &[u32],
-        db_me:  &[u32],
-        dc_me:  &[u32],
-        da_opp: &[u32],
-        db_opp: &[u32],
-        dc_opp: &[u32],
-    ) -> f64 {
+        opponents: &[PlayerId],
+        mine: &SideDistances,
+        theirs: &[SideDistances],
+    ) -> ScoreTerms {
         let size = board.board_size();
         let idx = candidate.to_index(size) as usize;
-        if let Some(score) = Self::immediate_score(candidate, board, my_id, opp_id) {
-            return score;
+        if let Some(terms) = Self::immediate_score(candidate, board, my_id, opponents) {
+            return terms;
         }
 
-        let (my_path_score, all_paths_bonus) = Self::own_path_scores(size, idx, da_me, db_me, dc_me);
-        let blocking_score = Self::blocking_score(size, idx, da_opp, db_opp, dc_opp);
+        let (my_path_score, all_paths_bonus) =
+            Self::own_path_scores(size, idx, &mine.a, &mine.b, &mine.c);
+        let blocking_score = theirs
+            .iter()
+            .map(|opp| Self::blocking_score(size, idx, &opp.a, &opp.b, &opp.c))
+            .fold(0.0, f64::max);
         let junction_score = Self::junction_score(candidate, board, my_id);
         let bridge = Self::bridge_bonus(candidate, board, my_id);
         let skip = Self::skip_bridge_bonus(candidate, board, my_id);
@@ -538,15 +712,16 @@ impl HardBot {
         let chain_score = (chain_len + 1.0).ln() * 1.5;
         let centrality = Self::centrality_score(candidate, size);
 
-        4.0 * my_path_score
-            + 2.0 * all_paths_bonus
-            + 4.0 * blocking_score
-            + 1.0 * junction_score
-            + 1.2 * skip
-            + 1.0 * near_win_bonus
-            + 1.0 * bridge
-            + 0.8 * chain_score
-            + 1.5 * centrality
+        ScoreTerms::new()
+            .with(MoveReason::Connect, 4.0 * my_path_score)
+            .with(MoveReason::Connect, 2.0 * all_paths_bonus)
+            .with(MoveReason::Block, 4.0 * blocking_score)
+            .with(MoveReason::Connect, 1.0 * junction_score)
+            .with(MoveReason::Bridge, 1.2 * skip)
+            .with(MoveReason::Connect, 1.0 * near_win_bonus)
+            .with(MoveReason::Bridge, 1.0 * bridge)
+            .with(MoveReason::Connect, 0.8 * chain_score)
+            .with(MoveReason::Centrality, 1.5 * centrality)
     }
 }
 
@@ -558,8 +733,8 @@ impl YBot for HardBot {
     }
 
     fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
-        let my_id  = board.next_player()?;
-        let opp_id = PlayerId::new(1 - my_id.id());
+        let my_id = board.next_player()?;
+        let opponents = board.opponents(my_id);
 
         let available = board.available_cells();
         if available.is_empty() {
@@ -568,6 +743,18 @@ impl YBot for HardBot {
 
         let size = board.board_size();
 
+        if board.rules().misere {
+            let mine = SideDistances::compute(board, my_id);
+            return available
+                .iter()
+                .map(|&idx| {
+                    let c = Coordinates::from_index(idx, size);
+                    let s = Self::misere_score_cell(&c, board, my_id, &opponents, &mine);
+                    (c, s)
+                })
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(c, _)| c);
+        }
 
         for &idx in available.iter() {
             let c = Coordinates::from_index(idx, size);
@@ -577,34 +764,70 @@ impl YBot for HardBot {
         }
 
 
-        for &idx in available.iter() {
-            let c = Coordinates::from_index(idx, size);
-            if Self::is_winning_move(&c, board, opp_id) {
-                return Some(c);
+        // Block the opponents in the order they move: the first one to win
+        // is the most urgent.
+        for &opp_id in &opponents {
+            for &idx in available.iter() {
+                let c = Coordinates::from_index(idx, size);
+                if Self::is_winning_move(&c, board, opp_id) {
+                    return Some(c);
+                }
             }
         }
 
-        let da_me  = Self::win_distance(board, my_id,  SIDE_A);
-        let db_me  = Self::win_distance(board, my_id,  SIDE_B);
-        let dc_me  = Self::win_distance(board, my_id,  SIDE_C);
-        let da_opp = Self::win_distance(board, opp_id, SIDE_A);
-        let db_opp = Self::win_distance(board, opp_id, SIDE_B);
-        let dc_opp = Self::win_distance(board, opp_id, SIDE_C);
+        let mine = SideDistances::compute(board, my_id);
+        let theirs: Vec<SideDistances> = opponents
+            .iter()
+            .map(|&opp_id| SideDistances::compute(board, opp_id))
+            .collect();
 
         available
             .iter()
             .map(|&idx| {
                 let c = Coordinates::from_index(idx, size);
-                let s = Self::score_cell(
-                    &c, board, my_id, opp_id,
-                    &da_me, &db_me, &dc_me,
-                    &da_opp, &db_opp, &dc_opp,
-                );
+                let s = Self::score_cell(&c, board, my_id, &opponents, &mine, &theirs).total();
                 (c, s)
             })
             .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .map(|(c, _)| c)
     }
+
+    fn choose_movement(&self, board: &GameY, budget: &SearchBudget) -> Option<Movement> {
+        let player = board.next_player()?;
+        if Self::should_swap(board, player) {
+            return Some(Movement::Action {
+                player,
+                action: GameAction::Swap,
+            });
+        }
+        if Self::is_hopeless(board, player) {
+            return Some(Movement::Action {
+                player,
+                action: GameAction::Resign,
+            });
+        }
+        let coords = self.choose_move_with_budget(board, budget)?;
+        Some(Movement::Placement { player, coords })
+    }
+
+    /// Analyses normal games only: misère scores do not split into reasons.
+    fn analyze(&self, board: &GameY, count: usize) -> Option<Vec<MoveAnalysis>> {
+        if board.rules().misere {
+            return None;
+        }
+        let Some(my_id) = board.next_player() else {
+            return Some(Vec::new());
+        };
+        let opponents = board.opponents(my_id);
+        let mine = SideDistances::compute(board, my_id);
+        let theirs: Vec<SideDistances> = opponents
+            .iter()
+            .map(|&opp_id| SideDistances::compute(board, opp_id))
+            .collect();
+        Some(analyze_cells(board, count, |c| {
+            Self::score_cell(c, board, my_id, &opponents, &mine, &theirs)
+        }))
+    }
 }
 
 
@@ -612,7 +835,7 @@ impl YBot for HardBot {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Movement, PlayerId};
+    use crate::{GameRules, Movement, PlayerId};
 
     fn bot() -> HardBot { HardBot }
 
@@ -731,6 +954,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blocks_any_opponent_in_three_player_game() {
+        let rules = GameRules {
+            num_players: 3,
+            ..GameRules::default()
+        };
+        let mut game = GameY::with_rules(3, rules).unwrap();
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (0, Coordinates::new(0, 0, 2)),
+            (1, Coordinates::new(2, 0, 0)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            }).unwrap();
+        }
+        assert_eq!(game.next_player(), Some(PlayerId::new(2)));
+
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_eq!(
+            chosen,
+            Coordinates::new(0, 1, 1),
+            "HardBot must block player 0 even though it is not the next to move"
+        );
+    }
+
+    #[test]
+    fn test_takes_win_through_blocked_corner() {
+        let mut game = GameY::new(3)
+            .with_blocked_cells([Coordinates::new(0, 2, 0)])
+            .unwrap();
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 1, 1),
+        }).unwrap();
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(1),
+            coords: Coordinates::new(2, 0, 0),
+        }).unwrap();
+
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_eq!(
+            chosen,
+            Coordinates::new(1, 0, 1),
+            "HardBot must use the sides extended by the blocked corner"
+        );
+    }
+
+    fn misere_game() -> GameY {
+        let rules = GameRules {
+            misere: true,
+            ..GameRules::default()
+        };
+        GameY::with_rules(3, rules).unwrap()
+    }
+
+    #[test]
+    fn test_misere_avoids_completing_own_y() {
+        let mut game = misere_game();
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 0, 2)),
+            (1, Coordinates::new(1, 1, 0)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            }).unwrap();
+        }
+
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_eq!(
+            chosen,
+            Coordinates::new(1, 0, 1),
+            "HardBot must not complete its own Y in a misère game"
+        );
+    }
+
+    #[test]
+    fn test_misere_leaves_opponent_losing_cell_empty() {
+        let mut game = misere_game();
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 0, 2)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            }).unwrap();
+        }
+
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_ne!(
+            chosen,
+            Coordinates::new(0, 1, 1),
+            "HardBot must not take the cell that makes the opponent lose"
+        );
+    }
+
     #[test]
     fn test_win_distance_own_piece_on_side_is_zero() {
         let mut game = GameY::new(5);
@@ -766,6 +1091,33 @@ mod tests {
         assert!(da[idx] < u32::MAX, "Interior cell should be reachable from side A");
     }
 
+    #[test]
+    fn test_connection_distance() {
+        let mut game = GameY::new(5);
+        let p0 = PlayerId::new(0);
+        // A corner touches two sides and is four cells away from the third
+        assert_eq!(HardBot::connection_distance(&game, p0), 5);
+        // From the middle of side A, two cells reach each of the other sides
+        game.add_move(Movement::Placement { player: p0, coords: Coordinates::new(0, 2, 2) }).unwrap();
+        assert_eq!(HardBot::connection_distance(&game, p0), 4);
+    }
+
+    #[test]
+    fn test_ranked_moves_start_with_the_win() {
+        let mut game = GameY::new(3);
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 0, 2)),
+            (1, Coordinates::new(1, 1, 0)),
+        ] {
+            game.add_move(Movement::Placement { player: PlayerId::new(player), coords }).unwrap();
+        }
+        let ranked = HardBot::ranked_moves(&game);
+        assert_eq!(ranked.len(), game.available_cells().len());
+        assert_eq!(ranked[0], Coordinates::new(0, 1, 1));
+    }
+
     #[test]
     fn test_bridge_bonus_between_adjacent_friendlies() {
         let mut game = GameY::new(5);
@@ -801,4 +1153,122 @@ mod tests {
         let sides = HardBot::sides_after_placement(&Coordinates::new(1, 1, 2), &game, p0);
         assert!(sides >= 2, "Merging side-A and side-B chains should give ≥ 2 sides");
     }
+
+    #[test]
+    fn test_swaps_central_opening() {
+        let mut game = GameY::new(7);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(2, 2, 2) }).unwrap();
+        let movement = bot().choose_movement(&game, &SearchBudget::new()).unwrap();
+        assert!(matches!(
+            movement,
+            Movement::Action { action: GameAction::Swap, player } if player == PlayerId::new(1)
+        ));
+        game.add_move(movement).unwrap();
+        assert!(game.swapped());
+    }
+
+    #[test]
+    fn test_does_not_swap_side_opening() {
+        let mut game = GameY::new(7);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(5, 1, 0) }).unwrap();
+        let movement = bot().choose_movement(&game, &SearchBudget::new()).unwrap();
+        assert!(matches!(movement, Movement::Placement { .. }));
+    }
+
+    #[test]
+    fn test_does_not_swap_when_disabled() {
+        let rules = GameRules {
+            swap_allowed: false,
+            ..GameRules::default()
+        };
+        let mut game = GameY::with_rules(7, rules).unwrap();
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(2, 2, 2) }).unwrap();
+        let movement = bot().choose_movement(&game, &SearchBudget::new()).unwrap();
+        assert!(matches!(movement, Movement::Placement { .. }));
+    }
+
+    #[test]
+    fn test_resigns_against_double_threat() {
+        // Player 0 can win at both (0, 2, 1) and (0, 1, 2)
+        let mut game = GameY::new(4);
+        for (player, x, y, z) in [(0, 2, 0, 1), (1, 3, 0, 0), (0, 1, 2, 0), (1, 0, 3, 0), (0, 1, 1, 1)] {
+            game.add_move(Movement::Placement { player: PlayerId::new(player), coords: Coordinates::new(x, y, z) }).unwrap();
+        }
+        let movement = bot().choose_movement(&game, &SearchBudget::new()).unwrap();
+        assert!(matches!(
+            movement,
+            Movement::Action { action: GameAction::Resign, player } if player == PlayerId::new(1)
+        ));
+        game.add_move(movement).unwrap();
+        assert_eq!(*game.status(), crate::GameStatus::Finished { winner: PlayerId::new(0) });
+    }
+
+    #[test]
+    fn test_does_not_resign_against_single_threat() {
+        let mut game = GameY::new(3);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(0, 2, 0) }).unwrap();
+        game.add_move(Movement::Placement { player: PlayerId::new(1), coords: Coordinates::new(2, 0, 0) }).unwrap();
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(0, 0, 2) }).unwrap();
+        let movement = bot().choose_movement(&game, &SearchBudget::new()).unwrap();
+        assert!(matches!(
+            movement,
+            Movement::Placement { coords, .. } if coords == Coordinates::new(0, 1, 1)
+        ));
+    }
+
+    #[test]
+    fn test_no_movement_when_game_over() {
+        let mut game = GameY::new(1);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(0, 0, 0) }).unwrap();
+        assert!(bot().choose_movement(&game, &SearchBudget::new()).is_none());
+    }
+
+    #[test]
+    fn test_analyze_tags_winning_move() {
+        let mut game = GameY::new(3);
+        for (player, x, y, z) in [(0, 0, 2, 0), (1, 2, 0, 0), (0, 0, 0, 2), (1, 1, 1, 0)] {
+            game.add_move(Movement::Placement { player: PlayerId::new(player), coords: Coordinates::new(x, y, z) }).unwrap();
+        }
+        let moves = bot().analyze(&game, 2).unwrap();
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].coords, Coordinates::new(0, 1, 1));
+        assert_eq!(moves[0].reason, MoveReason::Win);
+        assert!(moves[0].score > moves[1].score);
+    }
+
+    #[test]
+    fn test_analyze_tags_block() {
+        let mut game = GameY::new(3);
+        for (player, x, y, z) in [(0, 0, 2, 0), (1, 2, 0, 0), (0, 0, 0, 2)] {
+            game.add_move(Movement::Placement { player: PlayerId::new(player), coords: Coordinates::new(x, y, z) }).unwrap();
+        }
+        let moves = bot().analyze(&game, 1).unwrap();
+        assert_eq!(moves[0].coords, bot().choose_move(&game).unwrap());
+        assert_eq!(moves[0].reason, MoveReason::Block);
+    }
+
+    #[test]
+    fn test_analyze_ranks_like_choose_move() {
+        let mut game = GameY::new(7);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(2, 2, 2) }).unwrap();
+        game.add_move(Movement::Placement { player: PlayerId::new(1), coords: Coordinates::new(3, 1, 2) }).unwrap();
+        let moves = bot().analyze(&game, 5).unwrap();
+        assert_eq!(moves.len(), 5);
+        assert!(moves.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_eq!(moves[0].score, moves.iter().find(|m| m.coords == chosen).unwrap().score);
+    }
+
+    #[test]
+    fn test_analyze_misere_and_game_over() {
+        let rules = GameRules {
+            misere: true,
+            ..GameRules::default()
+        };
+        assert!(bot().analyze(&GameY::with_rules(3, rules).unwrap(), 3).is_none());
+
+        let mut game = GameY::new(1);
+        game.add_move(Movement::Placement { player: PlayerId::new(0), coords: Coordinates::new(0, 0, 0) }).unwrap();
+        assert_eq!(bot().analyze(&game, 3), Some(Vec::new()));
+    }
 }
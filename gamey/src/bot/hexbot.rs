@@ -0,0 +1,446 @@
+//! Bots for Hex.
+//!
+//! [`HexBot`] is the Hex counterpart of [`YBot`](crate::YBot), and Hex bots
+//! are looked up by name in a [`HexBotRegistry`](crate::HexBotRegistry). The
+//! bots register under the difficulty names the Y and tetrahedral bots use:
+//!
+//! - [`HexRandomBot`] (`random_bot`) plays a random empty cell.
+//! - [`HexDistanceBot`] (`intermediate_bot`) scores every empty cell with the
+//!   shortest connection distance of both players: the number of empty cells
+//!   each one still has to fill to join their edges. It plays the cell that
+//!   best shortens its own path while lengthening the opponent's.
+//! - [`HexPlayoutBot`] (`hard_bot`) keeps the best cells of that evaluation
+//!   and plays each of them out many times, with random moves until the board
+//!   is full. It picks the cell that wins most often.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{HexCell, HexGame, PlayerId};
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+
+/// Number of cells, best first, that the playout bot plays out.
+const PLAYOUT_CANDIDATES: usize = 8;
+
+/// Trait representing a Hex game bot.
+///
+/// Implementors must provide a name and a method to choose a move given the
+/// current game state.
+pub trait HexBot: Send + Sync {
+    /// Returns the name of the bot.
+    fn name(&self) -> &str;
+
+    /// Chooses a move based on the current game state, or None if the game is over.
+    fn choose_move(&self, board: &HexGame) -> Option<HexCell>;
+}
+
+/// A Hex bot that plays a random empty cell.
+pub struct HexRandomBot;
+
+impl HexBot for HexRandomBot {
+    fn name(&self) -> &str {
+        "random_bot"
+    }
+
+    fn choose_move(&self, board: &HexGame) -> Option<HexCell> {
+        board.next_player()?;
+        board.available_cells().choose(&mut rand::rng()).copied()
+    }
+}
+
+/// A Hex bot that plays the cell with the best connection distances for
+/// both players.
+///
+/// It takes a winning cell when there is one, and otherwise ties go to the
+/// most central cell, so it is deterministic.
+pub struct HexDistanceBot;
+
+impl HexBot for HexDistanceBot {
+    fn name(&self) -> &str {
+        "intermediate_bot"
+    }
+
+    fn choose_move(&self, board: &HexGame) -> Option<HexCell> {
+        ranked_moves(board).first().copied()
+    }
+}
+
+/// A Hex bot that plays out the best evaluated cells and keeps the one that
+/// wins most often.
+///
+/// It always takes a winning cell, and otherwise blocks a cell where the
+/// opponent would win. The playouts of a move stop after a number of
+/// playouts or, when a time limit is set, as soon as either budget runs out.
+/// Every candidate cell gets the same number of playouts.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use gamey::{HexBot, HexGame, HexPlayoutBot};
+///
+/// let bot = HexPlayoutBot::new()
+///     .with_playouts(256)
+///     .with_time_limit(Duration::from_millis(500));
+/// let game = HexGame::new(5).unwrap();
+///
+/// assert!(bot.choose_move(&game).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexPlayoutBot {
+    playouts: u32,
+    time_limit: Option<Duration>,
+}
+
+impl HexPlayoutBot {
+    /// Default number of random playouts per move, shared by all candidates.
+    pub const DEFAULT_PLAYOUTS: u32 = 2048;
+
+    /// Creates a bot with the default playout budget and no time limit.
+    pub fn new() -> Self {
+        HexPlayoutBot {
+            playouts: Self::DEFAULT_PLAYOUTS,
+            time_limit: None,
+        }
+    }
+
+    /// Sets the maximum number of playouts per move and returns the updated bot.
+    pub fn with_playouts(mut self, playouts: u32) -> Self {
+        self.playouts = playouts.max(1);
+        self
+    }
+
+    /// Sets the maximum thinking time per move and returns the updated bot.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Returns the maximum number of playouts per move.
+    pub fn playouts(&self) -> u32 {
+        self.playouts
+    }
+
+    /// Returns the maximum thinking time per move, if any.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+}
+
+impl Default for HexPlayoutBot {
+    fn default() -> Self {
+        HexPlayoutBot::new()
+    }
+}
+
+impl HexBot for HexPlayoutBot {
+    fn name(&self) -> &str {
+        "hard_bot"
+    }
+
+    fn choose_move(&self, board: &HexGame) -> Option<HexCell> {
+        let start = Instant::now();
+        let me = board.next_player()?;
+        if let Some(forced) = forced_move(board) {
+            return Some(forced);
+        }
+
+        let candidates = ranked_moves(board)
+            .into_iter()
+            .take(PLAYOUT_CANDIDATES)
+            .map(|cell| {
+                let mut after = board.clone();
+                after.play(me, cell).ok()?;
+                Some((cell, after))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // Playouts go in rounds of one per candidate, so that every candidate
+        // is played out the same number of times
+        let mut rng = rand::rng();
+        let mut wins = vec![0; candidates.len()];
+        let mut played = 0;
+        while played < self.playouts {
+            if played > 0
+                && self
+                    .time_limit
+                    .is_some_and(|limit| start.elapsed() >= limit)
+            {
+                break;
+            }
+            for ((_, after), wins) in candidates.iter().zip(wins.iter_mut()) {
+                if playout(after, &mut rng) == Some(me) {
+                    *wins += 1;
+                }
+            }
+            played += candidates.len().max(1) as u32;
+        }
+
+        // Ties keep the better evaluated cell, which comes first
+        let mut best: Option<(u32, HexCell)> = None;
+        for ((cell, _), wins) in candidates.iter().zip(wins) {
+            if best.is_none_or(|(best_wins, _)| wins > best_wins) {
+                best = Some((wins, *cell));
+            }
+        }
+        best.map(|(_, cell)| cell)
+    }
+}
+
+/// Fills `board` with random moves and returns the winner.
+fn playout<R: Rng + ?Sized>(board: &HexGame, rng: &mut R) -> Option<PlayerId> {
+    let mut game = board.clone();
+    let mut cells = game.available_cells();
+    cells.shuffle(rng);
+    for cell in cells {
+        let Some(player) = game.next_player() else {
+            break;
+        };
+        if game.play(player, cell).is_err() {
+            break;
+        }
+    }
+    game.winner()
+}
+
+/// Returns the cell the player to move must play: one that wins, or failing
+/// that one where the opponent would win.
+fn forced_move(board: &HexGame) -> Option<HexCell> {
+    let me = board.next_player()?;
+    let opponent = PlayerId::new(1 - me.id());
+    let available = board.available_cells();
+    available
+        .iter()
+        .find(|cell| connection_distance(board, me, Some((**cell, me))) == Some(0))
+        .or_else(|| {
+            available.iter().find(|cell| {
+                connection_distance(board, opponent, Some((**cell, opponent))) == Some(0)
+            })
+        })
+        .copied()
+}
+
+/// Returns the empty cells sorted from best to worst for the player to move.
+///
+/// A winning cell comes first. Other cells are scored by the connection
+/// distance of the opponent minus that of the player with a stone assumed on
+/// the cell, and ties go to the most central cell.
+fn ranked_moves(board: &HexGame) -> Vec<HexCell> {
+    let Some(me) = board.next_player() else {
+        return Vec::new();
+    };
+    let opponent = PlayerId::new(1 - me.id());
+    let size = board.board_size();
+    let center = size as i64 - 1;
+
+    let mut scored = board
+        .available_cells()
+        .into_iter()
+        .map(|cell| {
+            let mine = connection_distance(board, me, Some((cell, me)));
+            if mine == Some(0) {
+                return (cell, i64::MAX);
+            }
+            let theirs = connection_distance(board, opponent, Some((cell, me)));
+            let score = distance_score(theirs, size) - distance_score(mine, size);
+            // Prefer central cells when scores are tied
+            let offset =
+                (2 * cell.row() as i64 - center).abs() + (2 * cell.col() as i64 - center).abs();
+            (cell, score * 4 * size as i64 - offset)
+        })
+        .collect::<Vec<_>>();
+
+    scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(cell, _)| cell).collect()
+}
+
+/// Turns a connection distance into a score, counting a cut-off player as
+/// needing more cells than the board has.
+fn distance_score(distance: Option<u32>, size: u32) -> i64 {
+    distance.map_or(2 * (size * size) as i64, i64::from)
+}
+
+/// Returns the number of empty cells `player` still has to fill to connect
+/// their edges, or None if the opponent has cut every path.
+///
+/// `extra` is a stone assumed to be on the board, so candidate moves can be
+/// evaluated without cloning the game. The search is a 0-1 BFS where own
+/// stones cost nothing, empty cells cost one and opponent stones are walls.
+fn connection_distance(
+    board: &HexGame,
+    player: PlayerId,
+    extra: Option<(HexCell, PlayerId)>,
+) -> Option<u32> {
+    let size = board.board_size();
+    let stone_at = |cell: &HexCell| match extra {
+        Some((extra_cell, extra_player)) if extra_cell == *cell => Some(extra_player),
+        _ => board.player_at(cell),
+    };
+    let cost = |cell: &HexCell| match stone_at(cell) {
+        Some(owner) if owner == player => Some(0),
+        Some(_) => None,
+        None => Some(1),
+    };
+
+    let mut dist = vec![u32::MAX; (size * size) as usize];
+    let mut queue = VecDeque::new();
+    for row in 0..size {
+        for col in 0..size {
+            let cell = HexCell::new(row, col);
+            if board.touches_start_edge(player, &cell)
+                && let Some(c) = cost(&cell)
+            {
+                dist[(row * size + col) as usize] = c;
+                if c == 0 {
+                    queue.push_front(cell);
+                } else {
+                    queue.push_back(cell);
+                }
+            }
+        }
+    }
+
+    let mut best = None;
+    while let Some(cell) = queue.pop_front() {
+        let d = dist[(cell.row() * size + cell.col()) as usize];
+        if board.touches_end_edge(player, &cell) {
+            best = Some(best.map_or(d, |b: u32| b.min(d)));
+        }
+        for neighbor in board.neighbors(&cell) {
+            let Some(c) = cost(&neighbor) else { continue };
+            let idx = (neighbor.row() * size + neighbor.col()) as usize;
+            if d + c < dist[idx] {
+                dist[idx] = d + c;
+                if c == 0 {
+                    queue.push_front(neighbor);
+                } else {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(HexRandomBot.name(), "random_bot");
+        assert_eq!(HexDistanceBot.name(), "intermediate_bot");
+        assert_eq!(HexPlayoutBot::new().name(), "hard_bot");
+    }
+
+    #[test]
+    fn test_random_bot_plays_an_empty_cell() {
+        let game = HexGame::from_moves(3, "b2").unwrap();
+        let cell = HexRandomBot.choose_move(&game).unwrap();
+        assert!(game.available_cells().contains(&cell));
+    }
+
+    #[test]
+    fn test_no_move_after_game_over() {
+        let game = HexGame::from_moves(2, "a1 b1 a2").unwrap();
+        assert!(HexRandomBot.choose_move(&game).is_none());
+        assert!(HexDistanceBot.choose_move(&game).is_none());
+        assert!(HexPlayoutBot::new().choose_move(&game).is_none());
+    }
+
+    #[test]
+    fn test_connection_distance() {
+        let game = HexGame::new(4).unwrap();
+        assert_eq!(connection_distance(&game, PlayerId::new(0), None), Some(4));
+        let game = HexGame::from_moves(4, "b1 a2 b2").unwrap();
+        assert_eq!(connection_distance(&game, PlayerId::new(0), None), Some(2));
+        let game = HexGame::from_moves(3, "a1 a2 b1 b2 c3 c2").unwrap();
+        assert_eq!(connection_distance(&game, PlayerId::new(0), None), None);
+    }
+
+    #[test]
+    fn test_distance_bot_takes_the_winning_cell() {
+        let game = HexGame::from_moves(3, "a1 b1 a2 b2").unwrap();
+        assert_eq!(HexDistanceBot.choose_move(&game), Some(HexCell::new(2, 0)));
+        assert_eq!(
+            HexPlayoutBot::new().choose_move(&game),
+            Some(HexCell::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn test_distance_bot_blocks_the_opponent() {
+        // Player 1 needs only d2 to join a2-b2-c2 to the right edge
+        let game = HexGame::from_moves(4, "d1 a2 a4 b2 b4 c2").unwrap();
+        assert_eq!(HexDistanceBot.choose_move(&game), Some(HexCell::new(1, 3)));
+        assert_eq!(
+            HexPlayoutBot::new().choose_move(&game),
+            Some(HexCell::new(1, 3))
+        );
+    }
+
+    #[test]
+    fn test_distance_bot_beats_a_random_opponent() {
+        for seed in 0..5 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = HexGame::new(5).unwrap();
+            while let Some(player) = game.next_player() {
+                let cell = if player.id() == 0 {
+                    HexDistanceBot.choose_move(&game)
+                } else {
+                    game.available_cells().choose(&mut rng).copied()
+                };
+                game.play(player, cell.unwrap()).unwrap();
+            }
+            assert_eq!(
+                *game.status(),
+                crate::GameStatus::Finished {
+                    winner: PlayerId::new(0)
+                },
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_playout_bot_builders() {
+        let bot = HexPlayoutBot::default();
+        assert_eq!(bot.playouts(), HexPlayoutBot::DEFAULT_PLAYOUTS);
+        assert_eq!(bot.time_limit(), None);
+        let bot = HexPlayoutBot::new()
+            .with_playouts(0)
+            .with_time_limit(Duration::from_millis(20));
+        assert_eq!(bot.playouts(), 1);
+        assert_eq!(bot.time_limit(), Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn test_playout_bot_time_limit_stops_the_playouts() {
+        let bot = HexPlayoutBot::new()
+            .with_playouts(u32::MAX)
+            .with_time_limit(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(bot.choose_move(&HexGame::new(11).unwrap()).is_some());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_playout_bot_beats_a_random_opponent() {
+        for seed in 0..3 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = HexGame::new(5).unwrap();
+            while let Some(player) = game.next_player() {
+                let cell = if player.id() == 1 {
+                    HexPlayoutBot::new().choose_move(&game)
+                } else {
+                    game.available_cells().choose(&mut rng).copied()
+                };
+                game.play(player, cell.unwrap()).unwrap();
+            }
+            assert_eq!(game.winner(), Some(PlayerId::new(1)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Registry for managing HexBot implementations.
+//!
+//! The [`HexBotRegistry`] is the [`YBotRegistry`](crate::YBotRegistry) of
+//! Hex: it stores [`HexBot`] implementations by name.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::HexBot;
+
+/// A registry that stores and manages [`HexBot`] implementations.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use gamey::{HexBotRegistry, HexPlayoutBot, HexRandomBot};
+///
+/// let registry = HexBotRegistry::new()
+///     .with_bot(Arc::new(HexRandomBot))
+///     .with_bot(Arc::new(HexPlayoutBot::new()));
+///
+/// assert!(registry.find("hard_bot").is_some());
+/// ```
+pub struct HexBotRegistry {
+    bots: HashMap<String, Arc<dyn HexBot>>,
+}
+
+impl HexBotRegistry {
+    /// Creates a new empty registry.
+    pub fn new() -> Self {
+        HexBotRegistry {
+            bots: HashMap::new(),
+        }
+    }
+
+    /// Adds a bot to the registry and returns the registry for chaining.
+    ///
+    /// The bot is registered under its name (as returned by [`HexBot::name`]).
+    pub fn with_bot(mut self, bot: Arc<dyn HexBot>) -> Self {
+        self.bots.insert(bot.name().to_string(), bot);
+        self
+    }
+
+    /// Finds a bot by name.
+    ///
+    /// Returns `Some(bot)` if a bot with the given name exists, `None` otherwise.
+    pub fn find(&self, name: &str) -> Option<Arc<dyn HexBot>> {
+        self.bots.get(name).cloned()
+    }
+
+    /// Returns a list of all registered bot names.
+    pub fn names(&self) -> Vec<String> {
+        self.bots.keys().cloned().collect()
+    }
+}
+
+impl Default for HexBotRegistry {
+    fn default() -> Self {
+        HexBotRegistry::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HexDistanceBot, HexPlayoutBot, HexRandomBot};
+
+    #[test]
+    fn test_default_registry_is_empty() {
+        assert!(HexBotRegistry::default().names().is_empty());
+    }
+
+    #[test]
+    fn test_with_bot_registers_by_name() {
+        let registry = HexBotRegistry::new()
+            .with_bot(Arc::new(HexRandomBot))
+            .with_bot(Arc::new(HexDistanceBot))
+            .with_bot(Arc::new(HexPlayoutBot::new()));
+
+        assert_eq!(registry.names().len(), 3);
+        assert_eq!(
+            registry.find("intermediate_bot").unwrap().name(),
+            "intermediate_bot"
+        );
+        assert!(registry.find("nonexistent").is_none());
+    }
+}
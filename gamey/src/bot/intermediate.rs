@@ -20,11 +20,28 @@
 //!    the bot's own pieces, used only as a tiebreaker after the above.
 //!
 //! The cell with the highest combined score is chosen as the next move.
+//!
+//! ## Misère
+//!
+//! When the game is played misère, connecting the three sides loses, so the
+//! weights are turned around: the bot never completes its own Y while it has
+//! another move, leaves the cells that would complete an opponent's Y empty,
+//! and prefers central cells that stay away from its own pieces and the sides.
 
-use crate::{Coordinates, GameY, PlayerId, YBot};
+use crate::analysis::{ScoreTerms, analyze_cells};
+use crate::{Coordinates, GameY, MoveAnalysis, MoveReason, PlayerId, YBot};
 
 pub struct IntermediateBot;
 
+/// The scoring features of a candidate cell, before weighting.
+struct CellFeatures {
+    centrality_score: f64,
+    threat_score: f64,
+    skip_bonus: f64,
+    own_adjacent: f64,
+    side_touch: f64,
+}
+
 impl IntermediateBot {
     /// Returns the 6 (or fewer, on edges/corners) neighbours of a cell.
     ///
@@ -65,30 +82,6 @@ impl IntermediateBot {
         result
     }
 
-    /// Returns all empty cells at exactly graph-distance 2 from `coords`
-    /// that share exactly one common empty neighbour with `coords` (the
-    /// "skip" pattern). This is the preferred way to extend a chain: place
-    /// at distance 2, leaving a virtual connection the opponent cannot break
-    /// in one move.
-    fn skip_targets(coords: &Coordinates, board: &GameY) -> Vec<Coordinates> {
-        let mut result = Vec::new();
-        let direct_nbs: Vec<Coordinates> = Self::neighbors(coords)
-            .into_iter()
-            .filter(|nb| board.player_at(nb).is_none())
-            .collect();
-
-        for n1 in &direct_nbs {
-            for n2 in Self::neighbors(n1) {
-                // Must be empty, not the origin itself, and not a direct neighbour.
-                if n2 == *coords { continue; }
-                if board.player_at(&n2).is_some() { continue; }
-                if direct_nbs.contains(&n2) { continue; }
-                result.push(n2);
-            }
-        }
-        result
-    }
-
     /// Computes a heuristic score for placing a piece at `candidate`.
     ///
     /// Higher score → better move.
@@ -96,8 +89,20 @@ impl IntermediateBot {
         candidate: &Coordinates,
         board: &GameY,
         my_id: PlayerId,
-        opp_id: PlayerId,
     ) -> f64 {
+        let features = Self::features(candidate, board, my_id);
+        if board.rules().misere {
+            return Self::misere_score(candidate, board, my_id, &features);
+        }
+        Self::score_terms(&features).total()
+    }
+
+    /// Computes the scoring features of `candidate` for `my_id`.
+    fn features(
+        candidate: &Coordinates,
+        board: &GameY,
+        my_id: PlayerId,
+    ) -> CellFeatures {
         let n = board.board_size();
         let (x, y, z) = (candidate.x(), candidate.y(), candidate.z());
 
@@ -110,27 +115,23 @@ impl IntermediateBot {
             0.0
         };
 
-        //2. Opponent threat proximity
+        //2. Opponent threat proximity (stones of every other player count)
+        let is_opponent = |nb: &Coordinates| board.player_at(nb).is_some_and(|p| p != my_id);
         let opp_adjacent = Self::neighbors(candidate)
             .iter()
-            .filter(|nb| board.player_at(nb) == Some(opp_id))
+            .filter(|nb| is_opponent(nb))
             .count() as f64;
 
         let opp_2hop = Self::neighbors_2(candidate)
             .iter()
-            .filter(|nb| board.player_at(nb) == Some(opp_id))
+            .filter(|nb| is_opponent(nb))
             .count() as f64;
 
         let urgency_multiplier = if opp_adjacent >= 2.0 { 1.5 } else { 1.0 };
         let threat_score = (3.0 * opp_adjacent + 1.5 * opp_2hop) * urgency_multiplier;
 
         //3. Strategic skip bonus
-        // Prefer cells that are "skip" targets from the bot's own pieces: place at
-        let own_pieces_as_skip_sources = board.available_cells().iter()
-            .map(|&idx| Coordinates::from_index(idx, n))
-            .count();
-
-        let _ = own_pieces_as_skip_sources; // discard dummy count
+        // Prefer cells that are "skip" targets from the bot's own pieces.
         let skip_bonus: f64 = {
             let own_nbs: Vec<Coordinates> = Self::neighbors(candidate)
                 .into_iter()
@@ -140,7 +141,7 @@ impl IntermediateBot {
             let own_skip_count = {
                 let candidate_empty_nbs: std::collections::HashSet<_> = Self::neighbors(candidate)
                     .into_iter()
-                    .filter(|nb| board.player_at(nb).is_none())
+                    .filter(|nb| board.player_at(nb).is_none() && !board.is_blocked(nb))
                     .collect();
 
                 Self::neighbors_2(candidate)
@@ -166,16 +167,57 @@ impl IntermediateBot {
             .count() as f64;
 
         // 5. Side-touch bonus
-        let side_touch = (candidate.touches_side_a() as u8
-            + candidate.touches_side_b() as u8
-            + candidate.touches_side_c() as u8) as f64;
-
-        //Weighted combination
-        3.0 * centrality_score   // strong centre preference
-            + 4.0 * threat_score       // blocking opponent is TOP priority
-            + 3.0 * skip_bonus         // extend via skip patterns
-            + 1.0 * own_adjacent       // mild: prefer being near our pieces
-            + 0.5 * side_touch         // mild edge-capture incentive
+        let side_touch = (board.touches_side_a(candidate) as u8
+            + board.touches_side_b(candidate) as u8
+            + board.touches_side_c(candidate) as u8) as f64;
+
+        CellFeatures {
+            centrality_score,
+            threat_score,
+            skip_bonus,
+            own_adjacent,
+            side_touch,
+        }
+    }
+
+    /// Weights the features for a normal game, each term tagged with the
+    /// reason it stands for.
+    fn score_terms(features: &CellFeatures) -> ScoreTerms {
+        ScoreTerms::new()
+            // strong centre preference
+            .with(MoveReason::Centrality, 3.0 * features.centrality_score)
+            // blocking opponent is TOP priority
+            .with(MoveReason::Block, 4.0 * features.threat_score)
+            // extend via skip patterns
+            .with(MoveReason::Bridge, 3.0 * features.skip_bonus)
+            // mild: prefer being near our pieces
+            .with(MoveReason::Connect, 1.0 * features.own_adjacent)
+            // mild edge-capture incentive
+            .with(MoveReason::Connect, 0.5 * features.side_touch)
+    }
+
+    /// Combines the scoring terms for a misère game, where the bot must avoid
+    /// connecting its own pieces to the three sides.
+    fn misere_score(
+        candidate: &Coordinates,
+        board: &GameY,
+        my_id: PlayerId,
+        features: &CellFeatures,
+    ) -> f64 {
+        if board.connects_all_sides(my_id, candidate) {
+            return -1_000_000.0;
+        }
+        let relieves_opponent = board
+            .opponents(my_id)
+            .into_iter()
+            .any(|opp_id| board.connects_all_sides(opp_id, candidate));
+        let relief_penalty = if relieves_opponent { 50.0 } else { 0.0 };
+
+        2.0 * features.centrality_score  // centre cells are far from every side
+            - 3.0 * features.skip_bonus    // do not build chains
+            - 1.0 * features.own_adjacent  // keep our pieces apart
+            - 1.0 * features.side_touch    // sides are what we must not connect
+            - relief_penalty               // leave the opponent's losing cells empty
     }
 }
 
@@ -185,9 +227,8 @@ impl YBot for IntermediateBot {
     }
 
     fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
-        // Determine which player we are and who the opponent is.
+        // Determine which player we are; every other player is an opponent.
         let my_id = board.next_player()?;
-        let opp_id = PlayerId::new(1 - my_id.id());
 
         let available = board.available_cells();
         if available.is_empty() {
@@ -201,13 +242,26 @@ impl YBot for IntermediateBot {
             .iter()
             .map(|&idx| {
                 let coords = Coordinates::from_index(idx, size);
-                let score = Self::score_cell(&coords, board, my_id, opp_id);
+                let score = Self::score_cell(&coords, board, my_id);
                 (coords, score)
             })
             // Use a stable max: ties broken by first occurrence (deterministic).
             .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .map(|(coords, _)| coords)
     }
+
+    /// Analyses normal games only: misère scores do not split into reasons.
+    fn analyze(&self, board: &GameY, count: usize) -> Option<Vec<MoveAnalysis>> {
+        if board.rules().misere {
+            return None;
+        }
+        let Some(my_id) = board.next_player() else {
+            return Some(Vec::new());
+        };
+        Some(analyze_cells(board, count, |coords| {
+            Self::score_terms(&Self::features(coords, board, my_id))
+        }))
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -215,7 +269,7 @@ impl YBot for IntermediateBot {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Movement, PlayerId};
+    use crate::{GameRules, Movement, PlayerId};
 
     fn bot() -> IntermediateBot {
         IntermediateBot
@@ -282,6 +336,60 @@ mod tests {
             chosen
         );
     }
+    #[test]
+    fn test_blocks_any_opponent_in_three_player_game() {
+        let rules = GameRules {
+            num_players: 3,
+            ..GameRules::default()
+        };
+        let mut game = GameY::with_rules(5, rules).unwrap();
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 0, 4),
+        })
+            .unwrap();
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(1),
+            coords: Coordinates::new(2, 1, 1),
+        })
+            .unwrap();
+        assert_eq!(game.next_player(), Some(PlayerId::new(2)));
+
+        let chosen = bot().choose_move(&game).unwrap();
+        let opp_piece = Coordinates::new(2, 1, 1);
+        let is_nearby = IntermediateBot::neighbors(&opp_piece).contains(&chosen)
+            || IntermediateBot::neighbors_2(&opp_piece).contains(&chosen);
+        assert!(
+            is_nearby,
+            "Bot should play near player 1's piece; chose {:?}",
+            chosen
+        );
+    }
+
+    #[test]
+    fn test_misere_avoids_completing_own_y() {
+        let rules = GameRules {
+            misere: true,
+            ..GameRules::default()
+        };
+        let mut game = GameY::with_rules(3, rules).unwrap();
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 0, 2)),
+            (1, Coordinates::new(1, 1, 0)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+                .unwrap();
+        }
+
+        let chosen = bot().choose_move(&game).unwrap();
+        assert_eq!(chosen, Coordinates::new(1, 0, 1));
+    }
+
     #[test]
     fn test_extends_own_chain() {
         let mut game = GameY::new(5);
@@ -310,4 +418,39 @@ mod tests {
             chosen
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_analyze_empty_board_prefers_centre() {
+        let game = GameY::new(7);
+        let moves = bot().analyze(&game, 3).unwrap();
+        assert_eq!(moves.len(), 3);
+        assert_eq!(moves[0].reason, MoveReason::Centrality);
+        assert_eq!(moves[0].coords, Coordinates::new(2, 2, 2));
+        assert!(moves.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn test_analyze_scores_match_choice() {
+        let mut game = GameY::new(5);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 1, 1),
+        })
+            .unwrap();
+        let moves = bot().analyze(&game, usize::MAX).unwrap();
+        assert_eq!(moves.len(), game.available_cells().len());
+        let chosen = bot().choose_move(&game).unwrap();
+        let chosen_score = moves.iter().find(|m| m.coords == chosen).unwrap().score;
+        assert_eq!(chosen_score, moves[0].score);
+    }
+
+    #[test]
+    fn test_analyze_not_offered_in_misere() {
+        let rules = GameRules {
+            misere: true,
+            ..GameRules::default()
+        };
+        let game = GameY::with_rules(3, rules).unwrap();
+        assert!(bot().analyze(&game, 3).is_none());
+    }
+}
@@ -0,0 +1,455 @@
+//! A Monte-Carlo Tree Search bot.
+//!
+//! [`MctsBot`] grows a search tree with UCT (Upper Confidence bounds applied
+//! to Trees). Every iteration walks down the tree, picking the child with the
+//! best balance of win rate and exploration, adds one new move, and then
+//! plays the game out to the end with random moves. The result is credited
+//! to every node on the path, each from the point of view of the player who
+//! made its move. Once the budget is spent the most visited move is played.
+//!
+//! Random playouts are cheap because a full Y board always has a winner. The
+//! bot only reads the game status, so it follows whatever rules the game is
+//! played by, misère and games of more than two players included.
+//!
+//! The bot draws its random numbers from a fresh generator on every move,
+//! unless a seed is given with [`MctsBot::with_seed`], which makes every
+//! search from the same position return the same move.
+
+use std::time::{Duration, Instant};
+
+use crate::budget::tighter;
+use crate::{Coordinates, GameStatus, GameY, Movement, PlayerId, SearchBudget, YBot};
+use rand::SeedableRng;
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+
+/// A bot that chooses its moves with Monte-Carlo Tree Search.
+///
+/// The search stops after a number of iterations or, when a time limit is
+/// set, as soon as either budget runs out. A [`SearchBudget`] can lower both
+/// for one move: its node count caps the iterations, and its depth is ignored.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use gamey::{GameY, MctsBot, YBot};
+///
+/// let bot = MctsBot::new()
+///     .with_iterations(200)
+///     .with_time_limit(Duration::from_millis(500));
+/// let game = GameY::new(5);
+///
+/// assert!(bot.choose_move(&game).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MctsBot {
+    iterations: u32,
+    time_limit: Option<Duration>,
+    exploration: f64,
+    seed: Option<u64>,
+}
+
+// A node of the search tree, stored in an arena and linked by index.
+struct Node {
+    // Cell index of the move leading here, None for the root.
+    cell: Option<u32>,
+    // Player who made that move, None for the root.
+    player: Option<PlayerId>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    // Moves not expanded yet, in random order.
+    untried: Vec<u32>,
+    visits: u32,
+    // Sum of the rewards of `player` over all visits.
+    reward: f64,
+}
+
+impl Node {
+    fn new(
+        cell: Option<u32>,
+        player: Option<PlayerId>,
+        parent: Option<usize>,
+        game: &GameY,
+        rng: &mut StdRng,
+    ) -> Self {
+        let mut untried = if game.check_game_over() {
+            Vec::new()
+        } else {
+            game.available_cells().clone()
+        };
+        untried.shuffle(rng);
+        Node {
+            cell,
+            player,
+            parent,
+            children: Vec::new(),
+            untried,
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+}
+
+impl MctsBot {
+    /// Default number of iterations per move.
+    pub const DEFAULT_ITERATIONS: u32 = 4000;
+
+    /// Default UCT exploration constant, close to `sqrt(2)`.
+    pub const DEFAULT_EXPLORATION: f64 = 1.4;
+
+    /// Creates a bot with the default budget and no time limit.
+    pub fn new() -> Self {
+        MctsBot {
+            iterations: Self::DEFAULT_ITERATIONS,
+            time_limit: None,
+            exploration: Self::DEFAULT_EXPLORATION,
+            seed: None,
+        }
+    }
+
+    /// Sets the maximum number of iterations per move and returns the updated bot.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    /// Sets the maximum thinking time per move and returns the updated bot.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Sets the UCT exploration constant and returns the updated bot.
+    pub fn with_exploration(mut self, exploration: f64) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    /// Seeds the random number generator of every search and returns the
+    /// updated bot.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the maximum number of iterations per move.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the maximum thinking time per move, if any.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    /// Runs the search from `board` within `budget` and returns the tree, root
+    /// first. At least one iteration is always run.
+    fn search(&self, board: &GameY, budget: &SearchBudget) -> Vec<Node> {
+        let start = Instant::now();
+        let time_limit = tighter(self.time_limit, budget.time());
+        let iterations = budget
+            .nodes()
+            .map_or(u64::from(self.iterations), |nodes| {
+                nodes.min(u64::from(self.iterations))
+            })
+            .max(1);
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        let mut tree = vec![Node::new(None, None, None, board, &mut rng)];
+
+        for iteration in 0..iterations {
+            if iteration > 0
+                && (budget.is_cancelled()
+                    || time_limit.is_some_and(|limit| start.elapsed() >= limit))
+            {
+                break;
+            }
+            let mut game = board.clone();
+
+            // Selection
+            let mut node = 0;
+            while tree[node].untried.is_empty() && !tree[node].children.is_empty() {
+                node = self.select_child(&tree, node);
+                let (Some(cell), Some(player)) = (tree[node].cell, tree[node].player) else {
+                    break;
+                };
+                play(&mut game, player, cell);
+            }
+
+            // Expansion
+            if let Some(cell) = tree[node].untried.pop()
+                && let Some(player) = game.next_player()
+            {
+                play(&mut game, player, cell);
+                tree.push(Node::new(
+                    Some(cell),
+                    Some(player),
+                    Some(node),
+                    &game,
+                    &mut rng,
+                ));
+                let child = tree.len() - 1;
+                tree[node].children.push(child);
+                node = child;
+            }
+
+            // Simulation
+            let mut cells = game.available_cells().clone();
+            cells.shuffle(&mut rng);
+            for cell in cells {
+                let Some(player) = game.next_player() else {
+                    break;
+                };
+                play(&mut game, player, cell);
+            }
+
+            // Backpropagation
+            let num_players = game.num_players();
+            let mut current = Some(node);
+            while let Some(idx) = current {
+                tree[idx].visits += 1;
+                if let Some(player) = tree[idx].player {
+                    tree[idx].reward += reward(game.status(), player, num_players);
+                }
+                current = tree[idx].parent;
+            }
+        }
+        tree
+    }
+
+    /// Returns the child of `node` with the highest UCT value.
+    fn select_child(&self, tree: &[Node], node: usize) -> usize {
+        let log_visits = f64::from(tree[node].visits.max(1)).ln();
+        let uct = |child: usize| {
+            let visits = f64::from(tree[child].visits.max(1));
+            tree[child].reward / visits + self.exploration * (log_visits / visits).sqrt()
+        };
+        tree[node]
+            .children
+            .iter()
+            .copied()
+            .max_by(|a, b| uct(*a).total_cmp(&uct(*b)))
+            .unwrap_or(node)
+    }
+}
+
+impl Default for MctsBot {
+    fn default() -> Self {
+        MctsBot::new()
+    }
+}
+
+impl YBot for MctsBot {
+    fn name(&self) -> &str {
+        "mcts_bot"
+    }
+
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        self.choose_move_with_budget(board, &SearchBudget::new())
+    }
+
+    fn choose_move_with_budget(&self, board: &GameY, budget: &SearchBudget) -> Option<Coordinates> {
+        board.next_player()?;
+        let tree = self.search(board, budget);
+        let best = tree[0]
+            .children
+            .iter()
+            .max_by_key(|child| tree[**child].visits)?;
+        let cell = tree[*best].cell?;
+        Some(Coordinates::from_index(cell, board.board_size()))
+    }
+}
+
+/// Places a stone of `player` on the cell with index `cell`.
+fn play(game: &mut GameY, player: PlayerId, cell: u32) {
+    let coords = Coordinates::from_index(cell, game.board_size());
+    // Cells come from the available list, so the move is always legal
+    let _ = game.add_move(Movement::Placement { player, coords });
+}
+
+/// Returns the reward of `player` for a finished playout: one for a win, zero
+/// for a loss and an equal share when nobody won.
+fn reward(status: &GameStatus, player: PlayerId, num_players: u32) -> f64 {
+    match status {
+        GameStatus::Finished { winner } if *winner == player => 1.0,
+        GameStatus::Finished { .. } => 0.0,
+        GameStatus::Ongoing { .. } => 1.0 / f64::from(num_players),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::IndexedRandom;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn place(game: &mut GameY, player: u32, x: u32, y: u32, z: u32) {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords: Coordinates::new(x, y, z),
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_name_and_defaults() {
+        let bot = MctsBot::default();
+        assert_eq!(bot.name(), "mcts_bot");
+        assert_eq!(bot.iterations(), MctsBot::DEFAULT_ITERATIONS);
+        assert_eq!(bot.time_limit(), None);
+    }
+
+    #[test]
+    fn test_builders() {
+        let bot = MctsBot::new()
+            .with_iterations(0)
+            .with_time_limit(Duration::from_millis(20));
+        assert_eq!(bot.iterations(), 1);
+        assert_eq!(bot.time_limit(), Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn test_returns_available_move() {
+        let mut game = GameY::new(4);
+        place(&mut game, 0, 1, 1, 1);
+        let coords = MctsBot::new()
+            .with_iterations(100)
+            .choose_move(&game)
+            .unwrap();
+        let idx = coords.to_index(game.board_size());
+        assert!(game.available_cells().contains(&idx));
+    }
+
+    #[test]
+    fn test_no_move_when_game_over() {
+        let mut game = GameY::new(1);
+        place(&mut game, 0, 0, 0, 0);
+        assert!(game.check_game_over());
+        assert!(MctsBot::new().choose_move(&game).is_none());
+    }
+
+    #[test]
+    fn test_takes_the_winning_cell() {
+        // (0, 0, 2) joins player 0's group on sides B and C to side A
+        let mut game = GameY::new(3);
+        place(&mut game, 0, 2, 0, 0);
+        place(&mut game, 1, 0, 1, 1);
+        place(&mut game, 0, 1, 0, 1);
+        place(&mut game, 1, 0, 2, 0);
+        for seed in 0..3 {
+            let coords = MctsBot::new().with_seed(seed).choose_move(&game).unwrap();
+            assert_eq!(coords, Coordinates::new(0, 0, 2), "missed with seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_blocks_the_opponent() {
+        // Player 1 wins at (0, 2, 1) unless player 0 takes it, and player 0
+        // has no win of their own
+        let mut game = GameY::new(4);
+        for (player, x, y, z) in [
+            (0, 0, 1, 2),
+            (1, 1, 1, 1),
+            (0, 1, 0, 2),
+            (1, 2, 1, 0),
+            (0, 2, 0, 1),
+            (1, 3, 0, 0),
+        ] {
+            place(&mut game, player, x, y, z);
+        }
+        for seed in 0..3 {
+            let coords = MctsBot::new().with_seed(seed).choose_move(&game).unwrap();
+            assert_eq!(coords, Coordinates::new(0, 2, 1), "missed with seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_time_limit_stops_the_search() {
+        let bot = MctsBot::new()
+            .with_iterations(u32::MAX)
+            .with_time_limit(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(bot.choose_move(&GameY::new(7)).is_some());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_budget_caps_the_iterations() {
+        let game = GameY::new(5);
+        let bot = MctsBot::new();
+        let tree = bot.search(&game, &SearchBudget::new().with_nodes(50));
+        assert_eq!(tree[0].visits, 50);
+        let tree = bot.search(&game, &SearchBudget::new().with_nodes(0));
+        assert_eq!(tree[0].visits, 1);
+    }
+
+    #[test]
+    fn test_budget_time_stops_the_search() {
+        let bot = MctsBot::new().with_iterations(u32::MAX);
+        let budget = SearchBudget::new().with_time(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(
+            bot.choose_move_with_budget(&GameY::new(7), &budget)
+                .is_some()
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_cancelled_budget_still_returns_a_move() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let budget = SearchBudget::new().with_cancel_flag(cancel);
+        let game = GameY::new(5);
+        let tree = MctsBot::new().search(&game, &budget);
+        assert_eq!(tree[0].visits, 1);
+        let coords = MctsBot::new()
+            .choose_move_with_budget(&game, &budget)
+            .unwrap();
+        let idx = coords.to_index(game.board_size());
+        assert!(game.available_cells().contains(&idx));
+    }
+
+    #[test]
+    fn test_seeded_searches_repeat() {
+        let mut game = GameY::new(5);
+        place(&mut game, 0, 2, 1, 1);
+        let bot = MctsBot::new().with_iterations(200).with_seed(7);
+        let first = bot.choose_move(&game);
+        assert!(first.is_some());
+        assert_eq!(bot.choose_move(&game), first);
+    }
+
+    #[test]
+    fn test_beats_a_random_opponent() {
+        for seed in 0..3 {
+            let bot = MctsBot::new().with_iterations(500).with_seed(seed);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = GameY::new(5);
+            while let Some(player) = game.next_player() {
+                let coords = if player.id() == 0 {
+                    bot.choose_move(&game).unwrap()
+                } else {
+                    let cell = *game.available_cells().choose(&mut rng).unwrap();
+                    Coordinates::from_index(cell, game.board_size())
+                };
+                place_coords(&mut game, player, coords);
+            }
+            assert_eq!(
+                *game.status(),
+                GameStatus::Finished {
+                    winner: PlayerId::new(0)
+                },
+                "lost with seed {seed}"
+            );
+        }
+    }
+
+    fn place_coords(game: &mut GameY, player: PlayerId, coords: Coordinates) {
+        game.add_move(Movement::Placement { player, coords })
+            .unwrap();
+    }
+}
@@ -4,17 +4,39 @@
 //! that can play the Game of Y. It includes:
 //!
 //! - [`YBot`] - A trait that defines the interface for all bots
+//! - [`SearchBudget`] - Time, node and depth limits on a bot's thinking
+//! - [`MoveAnalysis`] - Ranked candidate moves with scores and reasons
 //! - [`YBotRegistry`] - A registry for managing multiple bot implementations
 //! - [`RandomBot`] - A simple bot that makes random valid moves
+//! - [`MctsBot`] - A Monte-Carlo Tree Search bot with a configurable budget
+//! - [`SearchBot`] - A deterministic alpha-beta search bot
+//! - [`HexBot`] and [`HexBotRegistry`] - The bot interface and registry for Hex
+//! - [`TetraBot`] and [`TetraBotRegistry`] - The same for the tetrahedral variant
 
 pub mod random;
+pub mod analysis;
+pub mod budget;
 pub mod ybot;
 pub mod ybot_registry;
 pub mod intermediate;
 pub mod hard;
+pub mod mcts;
+pub mod search;
+pub mod hexbot;
+pub mod hexbot_registry;
+pub mod tetrabot;
+pub mod tetrabot_registry;
 
 pub use random::*;
+pub use analysis::*;
+pub use budget::*;
 pub use ybot::*;
 pub use ybot_registry::*;
 pub use intermediate::*;
-pub use hard::*;
\ No newline at end of file
+pub use hard::*;
+pub use mcts::*;
+pub use search::*;
+pub use hexbot::*;
+pub use hexbot_registry::*;
+pub use tetrabot::*;
+pub use tetrabot_registry::*;
\ No newline at end of file
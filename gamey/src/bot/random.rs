@@ -137,4 +137,34 @@ mod tests {
             assert!(game.available_cells().contains(&index));
         }
     }
+
+    #[test]
+    fn test_random_bot_movement_is_a_placement() {
+        let bot = RandomBot;
+        let mut game = GameY::new(3);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 0, 0),
+        })
+        .unwrap();
+
+        // The default decision never swaps or resigns
+        let movement = bot
+            .choose_movement(&game, &crate::SearchBudget::new())
+            .unwrap();
+        match movement {
+            Movement::Placement { player, coords } => {
+                assert_eq!(player, PlayerId::new(1));
+                let index = coords.to_index(game.board_size());
+                assert!(game.available_cells().contains(&index));
+            }
+            other => panic!("Expected a placement, got {}", other),
+        }
+    }
+
+    #[test]
+    fn test_random_bot_does_not_analyze() {
+        let bot = RandomBot;
+        assert!(bot.analyze(&GameY::new(3), 3).is_none());
+    }
 }
@@ -0,0 +1,501 @@
+//! An alpha-beta search bot.
+//!
+//! [`SearchBot`] runs an iterative-deepening negamax search with alpha-beta
+//! pruning. Leaves are scored with the connection distances of [`HardBot`]:
+//! how many empty cells the opponent still needs minus how many the player to
+//! move needs. Moves are tried in the order of [`HardBot`]'s cell scores, and
+//! only the best few of them are searched at each node. A transposition table
+//! keyed on [`GameY::position_hash`] remembers the result and best move of
+//! every searched position, so later iterations try the best move first.
+//!
+//! Unlike [`MctsBot`](crate::MctsBot), the search is deterministic: the same
+//! position and budget always give the same move. It plays two-player games;
+//! misère games and games of more than two players are left to [`HardBot`].
+//!
+//! A [`SearchBudget`] can lower the depth and time limit for one move, cap the
+//! number of searched positions, or stop the search from another thread. The
+//! move of the last completed depth is played when the search is cut short.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::budget::tighter;
+use crate::{Coordinates, GameY, HardBot, Movement, PlayerId, SearchBudget, YBot};
+
+/// Score of a won position, lowered by one for every move needed to reach it
+/// so that faster wins are preferred.
+const WIN_SCORE: i32 = 1_000_000;
+
+/// A deterministic bot that searches the game tree with alpha-beta pruning.
+///
+/// # Example
+///
+/// ```
+/// use gamey::{GameY, SearchBot, YBot};
+///
+/// let bot = SearchBot::new().with_depth(2).with_width(4);
+/// let game = GameY::new(4);
+///
+/// assert!(bot.choose_move(&game).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchBot {
+    name: String,
+    depth: u32,
+    width: usize,
+    time_limit: Option<Duration>,
+}
+
+/// Whether a stored score is exact or only a bound on the true score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+/// A transposition table entry.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    depth: u32,
+    score: i32,
+    bound: Bound,
+    best: Option<Coordinates>,
+}
+
+/// State of one search: the transposition table and what is left of the
+/// budget.
+struct Search<'a> {
+    table: HashMap<u64, Entry>,
+    deadline: Option<Instant>,
+    nodes: u64,
+    budget: &'a SearchBudget,
+}
+
+impl Search<'_> {
+    /// Counts one more searched position and returns true if the budget has
+    /// run out.
+    fn exhausted(&mut self) -> bool {
+        self.nodes += 1;
+        self.budget.nodes().is_some_and(|nodes| self.nodes > nodes)
+            || self.budget.is_cancelled()
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+impl SearchBot {
+    /// Default maximum search depth, in moves.
+    pub const DEFAULT_DEPTH: u32 = 4;
+
+    /// Default number of moves searched at each node.
+    pub const DEFAULT_WIDTH: usize = 8;
+
+    /// Creates a bot named `search_bot` with the default depth and width and
+    /// no time limit.
+    pub fn new() -> Self {
+        SearchBot {
+            name: "search_bot".to_string(),
+            depth: Self::DEFAULT_DEPTH,
+            width: Self::DEFAULT_WIDTH,
+            time_limit: None,
+        }
+    }
+
+    /// Sets the name the bot is registered under and returns the updated bot,
+    /// so that bots with different budgets can sit in the same registry.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Sets the maximum search depth and returns the updated bot.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Sets how many of the best ordered moves are searched at each node and
+    /// returns the updated bot.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Sets the maximum thinking time per move and returns the updated bot.
+    ///
+    /// When time runs out the move of the last completed depth is played.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Returns the maximum search depth.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns how many moves are searched at each node.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the maximum thinking time per move, if any.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    /// Searches `board` one depth at a time within `budget` and returns the
+    /// best move with its score for the player to move.
+    fn search(&self, board: &GameY, budget: &SearchBudget) -> Option<(Coordinates, i32)> {
+        board.next_player()?;
+        let mut game = board.clone();
+        let max_depth = budget
+            .depth()
+            .map_or(self.depth, |depth| depth.min(self.depth))
+            .max(1);
+        let mut search = Search {
+            table: HashMap::new(),
+            deadline: tighter(self.time_limit, budget.time()).map(|limit| Instant::now() + limit),
+            nodes: 0,
+            budget,
+        };
+
+        let mut best = None;
+        for depth in 1..=max_depth {
+            let Some(score) = self.negamax(&mut search, &mut game, depth, 0, -WIN_SCORE, WIN_SCORE)
+            else {
+                break;
+            };
+            let entry = search.table.get(&game.position_hash());
+            if let Some(coords) = entry.and_then(|entry| entry.best) {
+                best = Some((coords, score));
+            }
+            // A forced result cannot change at a greater depth
+            if score.abs() >= WIN_SCORE - max_depth as i32 {
+                break;
+            }
+        }
+        best.or_else(|| {
+            HardBot::ranked_moves(board)
+                .first()
+                .map(|coords| (*coords, 0))
+        })
+    }
+
+    /// Returns the score of `game` for the player to move, searched `depth`
+    /// moves deep, or None if time ran out.
+    fn negamax(
+        &self,
+        search: &mut Search,
+        game: &mut GameY,
+        depth: u32,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Option<i32> {
+        // The game can only have been won by the move just played
+        let Some(player) = game.next_player() else {
+            return Some(-(WIN_SCORE - ply));
+        };
+        if depth == 0 {
+            return Some(evaluate(game, player));
+        }
+        if search.exhausted() {
+            return None;
+        }
+
+        let key = game.position_hash();
+        let stored = search.table.get(&key).copied();
+        if let Some(entry) = stored
+            && entry.depth >= depth
+        {
+            match entry.bound {
+                Bound::Exact => return Some(entry.score),
+                Bound::Lower if entry.score >= beta => return Some(entry.score),
+                Bound::Upper if entry.score <= alpha => return Some(entry.score),
+                _ => {}
+            }
+        }
+
+        let mut moves: Vec<Coordinates> = HardBot::ranked_moves(game)
+            .into_iter()
+            .take(self.width)
+            .collect();
+        if let Some(best) = stored.and_then(|entry| entry.best) {
+            moves.retain(|coords| *coords != best);
+            moves.insert(0, best);
+        }
+
+        let original_alpha = alpha;
+        let mut best: Option<(Coordinates, i32)> = None;
+        for coords in moves {
+            if game
+                .add_move(Movement::Placement { player, coords })
+                .is_err()
+            {
+                continue;
+            }
+            let child = self.negamax(search, game, depth - 1, ply + 1, -beta, -alpha);
+            let _ = game.undo_move();
+            let score = -child?;
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((coords, score));
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let (coords, score) = best?;
+        let bound = if score <= original_alpha {
+            Bound::Upper
+        } else if score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        search.table.insert(
+            key,
+            Entry {
+                depth,
+                score,
+                bound,
+                best: Some(coords),
+            },
+        );
+        Some(score)
+    }
+}
+
+impl Default for SearchBot {
+    fn default() -> Self {
+        SearchBot::new()
+    }
+}
+
+impl YBot for SearchBot {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        self.choose_move_with_budget(board, &SearchBudget::new())
+    }
+
+    fn choose_move_with_budget(&self, board: &GameY, budget: &SearchBudget) -> Option<Coordinates> {
+        board.next_player()?;
+        if board.rules().misere || board.num_players() != 2 {
+            return HardBot.choose_move(board);
+        }
+        self.search(board, budget).map(|(coords, _)| coords)
+    }
+}
+
+/// Scores a position that is still being played for `player`, who is to
+/// move: the opponent's connection distance minus their own.
+fn evaluate(game: &GameY, player: PlayerId) -> i32 {
+    let opponent = PlayerId::new(1 - player.id());
+    HardBot::connection_distance(game, opponent) as i32
+        - HardBot::connection_distance(game, player) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameRules, GameStatus};
+    use rand::SeedableRng;
+    use rand::prelude::IndexedRandom;
+    use rand::rngs::StdRng;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    fn place(game: &mut GameY, player: u32, x: u32, y: u32, z: u32) {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords: Coordinates::new(x, y, z),
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_name_and_builders() {
+        let bot = SearchBot::default();
+        assert_eq!(bot.name(), "search_bot");
+        assert_eq!(bot.depth(), SearchBot::DEFAULT_DEPTH);
+        assert_eq!(bot.width(), SearchBot::DEFAULT_WIDTH);
+        assert_eq!(bot.time_limit(), None);
+
+        let bot = SearchBot::new()
+            .with_name("search_bot_deep")
+            .with_depth(0)
+            .with_width(0)
+            .with_time_limit(Duration::from_millis(10));
+        assert_eq!(bot.name(), "search_bot_deep");
+        assert_eq!(bot.depth(), 1);
+        assert_eq!(bot.width(), 1);
+        assert_eq!(bot.time_limit(), Some(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_no_move_when_game_over() {
+        let mut game = GameY::new(1);
+        place(&mut game, 0, 0, 0, 0);
+        assert!(SearchBot::new().choose_move(&game).is_none());
+    }
+
+    #[test]
+    fn test_takes_the_winning_cell() {
+        let mut game = GameY::new(3);
+        place(&mut game, 0, 0, 2, 0);
+        place(&mut game, 1, 2, 0, 0);
+        place(&mut game, 0, 0, 0, 2);
+        place(&mut game, 1, 1, 1, 0);
+        let (coords, score) = SearchBot::new()
+            .search(&game, &SearchBudget::new())
+            .unwrap();
+        assert_eq!(coords, Coordinates::new(0, 1, 1));
+        assert_eq!(score, WIN_SCORE - 1);
+    }
+
+    #[test]
+    fn test_blocks_the_opponent() {
+        // Player 1 wins at (0, 2, 1) unless player 0 takes it
+        let mut game = GameY::new(4);
+        for (player, x, y, z) in [
+            (0, 0, 1, 2),
+            (1, 1, 1, 1),
+            (0, 1, 0, 2),
+            (1, 2, 1, 0),
+            (0, 2, 0, 1),
+            (1, 3, 0, 0),
+        ] {
+            place(&mut game, player, x, y, z);
+        }
+        let bot = SearchBot::new();
+        assert_eq!(bot.choose_move(&game), Some(Coordinates::new(0, 2, 1)));
+    }
+
+    #[test]
+    fn test_sees_a_double_threat() {
+        // After (1, 1, 1) player 0 threatens to win at both (0, 2, 1) and
+        // (0, 1, 2), so a three-move search proves the win
+        let mut game = GameY::new(4);
+        for (player, x, y, z) in [(0, 2, 0, 1), (1, 3, 0, 0), (0, 1, 2, 0), (1, 0, 3, 0)] {
+            place(&mut game, player, x, y, z);
+        }
+        let (_, score) = SearchBot::new()
+            .with_depth(3)
+            .search(&game, &SearchBudget::new())
+            .unwrap();
+        assert_eq!(score, WIN_SCORE - 3);
+    }
+
+    #[test]
+    fn test_budget_depth_lowers_the_depth() {
+        let mut game = GameY::new(4);
+        for (player, x, y, z) in [(0, 2, 0, 1), (1, 3, 0, 0), (0, 1, 2, 0), (1, 0, 3, 0)] {
+            place(&mut game, player, x, y, z);
+        }
+        let bot = SearchBot::new().with_depth(3);
+        let (_, score) = bot
+            .search(&game, &SearchBudget::new().with_depth(2))
+            .unwrap();
+        assert!(score < WIN_SCORE - 3);
+        // The budget cannot raise the depth of the bot
+        let (_, score) = SearchBot::new()
+            .with_depth(2)
+            .search(&game, &SearchBudget::new().with_depth(5))
+            .unwrap();
+        assert!(score < WIN_SCORE - 3);
+    }
+
+    #[test]
+    fn test_budget_nodes_stop_the_search() {
+        let mut game = GameY::new(5);
+        place(&mut game, 0, 2, 1, 1);
+        place(&mut game, 1, 0, 2, 2);
+        // One node only completes the first depth
+        let bot = SearchBot::new();
+        assert_eq!(
+            bot.search(&game, &SearchBudget::new().with_nodes(1)),
+            bot.search(&game, &SearchBudget::new().with_depth(1))
+        );
+    }
+
+    #[test]
+    fn test_cancelled_budget_still_returns_a_move() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let budget = SearchBudget::new().with_cancel_flag(cancel);
+        let game = GameY::new(5);
+        let coords = SearchBot::new()
+            .choose_move_with_budget(&game, &budget)
+            .unwrap();
+        assert_eq!(Some(coords), HardBot::ranked_moves(&game).first().copied());
+    }
+
+    #[test]
+    fn test_is_deterministic() {
+        let mut game = GameY::new(5);
+        place(&mut game, 0, 2, 1, 1);
+        place(&mut game, 1, 0, 2, 2);
+        let bot = SearchBot::new().with_depth(2);
+        assert_eq!(bot.choose_move(&game), bot.choose_move(&game));
+    }
+
+    #[test]
+    fn test_time_limit_still_returns_a_move() {
+        let bot = SearchBot::new()
+            .with_depth(20)
+            .with_time_limit(Duration::from_millis(1));
+        let game = GameY::new(6);
+        let coords = bot.choose_move(&game).unwrap();
+        let idx = coords.to_index(game.board_size());
+        assert!(game.available_cells().contains(&idx));
+    }
+
+    #[test]
+    fn test_misere_is_left_to_hard_bot() {
+        let rules = GameRules {
+            misere: true,
+            ..GameRules::default()
+        };
+        let mut game = GameY::with_rules(3, rules).unwrap();
+        place(&mut game, 0, 0, 2, 0);
+        place(&mut game, 1, 2, 0, 0);
+        place(&mut game, 0, 0, 0, 2);
+        place(&mut game, 1, 1, 1, 0);
+        assert_eq!(
+            SearchBot::new().choose_move(&game),
+            HardBot.choose_move(&game)
+        );
+    }
+
+    #[test]
+    fn test_beats_a_random_opponent() {
+        let bot = SearchBot::new().with_depth(2).with_width(4);
+        for seed in 0..3 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = GameY::new(5);
+            while let Some(player) = game.next_player() {
+                let coords = if player.id() == 0 {
+                    bot.choose_move(&game).unwrap()
+                } else {
+                    let cell = *game.available_cells().choose(&mut rng).unwrap();
+                    Coordinates::from_index(cell, game.board_size())
+                };
+                game.add_move(Movement::Placement { player, coords })
+                    .unwrap();
+            }
+            assert_eq!(
+                *game.status(),
+                GameStatus::Finished {
+                    winner: PlayerId::new(0)
+                },
+                "lost with seed {seed}"
+            );
+        }
+    }
+}
@@ -0,0 +1,541 @@
+//! Bots for the tetrahedral variant.
+//!
+//! [`TetraBot`] is the [`TetraGame`] counterpart of [`YBot`](crate::YBot),
+//! and bots are looked up by name in a
+//! [`TetraBotRegistry`](crate::TetraBotRegistry). The bots have their own
+//! types, but register under the difficulty names the Y bots use, so that
+//! clients pick the same level on both boards:
+//!
+//! - [`TetraRandomBot`] (`random_bot`) plays a random empty cell.
+//! - [`TetraDistanceBot`] (`intermediate_bot`) evaluates every empty cell by
+//!   the *face distance* of both players once a stone is placed there. The
+//!   face distance is the number of empty cells a player still has to fill to
+//!   join all four faces. The bot plays the cell that best shortens its own
+//!   distance while lengthening the opponent's.
+//! - [`TetraPlayoutBot`] (`hard_bot`) keeps the best cells of that evaluation
+//!   and plays each of them out many times, with random moves until the game
+//!   ends. It picks the cell that wins most often.
+//!
+//! The distance and playout bots always take a winning cell, and otherwise
+//! block a cell where the opponent would win.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{PlayerId, TetraCoordinates, TetraGame};
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+
+/// Number of cells, best first, that the playout bot plays out.
+const PLAYOUT_CANDIDATES: usize = 8;
+
+/// Trait representing a bot for the tetrahedral variant.
+///
+/// Implementors must provide a name and a method to choose a move given the
+/// current game state.
+pub trait TetraBot: Send + Sync {
+    /// Returns the name of the bot.
+    fn name(&self) -> &str;
+
+    /// Chooses a move based on the current game state, or None if the game is over.
+    fn choose_move(&self, board: &TetraGame) -> Option<TetraCoordinates>;
+}
+
+/// A bot that plays a random empty cell.
+pub struct TetraRandomBot;
+
+impl TetraBot for TetraRandomBot {
+    fn name(&self) -> &str {
+        "random_bot"
+    }
+
+    fn choose_move(&self, board: &TetraGame) -> Option<TetraCoordinates> {
+        board.next_player()?;
+        board.available_moves().choose(&mut rand::rng()).copied()
+    }
+}
+
+/// A bot that plays the cell with the best face distance evaluation.
+pub struct TetraDistanceBot;
+
+impl TetraBot for TetraDistanceBot {
+    fn name(&self) -> &str {
+        "intermediate_bot"
+    }
+
+    fn choose_move(&self, board: &TetraGame) -> Option<TetraCoordinates> {
+        if let Some(forced) = forced_move(board) {
+            return Some(forced);
+        }
+        ranked_moves(board).first().copied()
+    }
+}
+
+/// A bot that plays out the best evaluated cells and keeps the one that wins
+/// most often.
+///
+/// The playouts of a move stop after a number of playouts or, when a time
+/// limit is set, as soon as either budget runs out. Every candidate cell gets
+/// the same number of playouts.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use gamey::{TetraBot, TetraGame, TetraPlayoutBot};
+///
+/// let bot = TetraPlayoutBot::new()
+///     .with_playouts(256)
+///     .with_time_limit(Duration::from_millis(500));
+/// let game = TetraGame::new(3).unwrap();
+///
+/// assert!(bot.choose_move(&game).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TetraPlayoutBot {
+    playouts: u32,
+    time_limit: Option<Duration>,
+}
+
+impl TetraPlayoutBot {
+    /// Default number of random playouts per move, shared by all candidates.
+    pub const DEFAULT_PLAYOUTS: u32 = 2048;
+
+    /// Creates a bot with the default playout budget and no time limit.
+    pub fn new() -> Self {
+        TetraPlayoutBot {
+            playouts: Self::DEFAULT_PLAYOUTS,
+            time_limit: None,
+        }
+    }
+
+    /// Sets the maximum number of playouts per move and returns the updated bot.
+    pub fn with_playouts(mut self, playouts: u32) -> Self {
+        self.playouts = playouts.max(1);
+        self
+    }
+
+    /// Sets the maximum thinking time per move and returns the updated bot.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Returns the maximum number of playouts per move.
+    pub fn playouts(&self) -> u32 {
+        self.playouts
+    }
+
+    /// Returns the maximum thinking time per move, if any.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+}
+
+impl Default for TetraPlayoutBot {
+    fn default() -> Self {
+        TetraPlayoutBot::new()
+    }
+}
+
+impl TetraBot for TetraPlayoutBot {
+    fn name(&self) -> &str {
+        "hard_bot"
+    }
+
+    fn choose_move(&self, board: &TetraGame) -> Option<TetraCoordinates> {
+        let start = Instant::now();
+        let me = board.next_player()?;
+        if let Some(forced) = forced_move(board) {
+            return Some(forced);
+        }
+
+        let candidates = ranked_moves(board)
+            .into_iter()
+            .take(PLAYOUT_CANDIDATES)
+            .map(|coords| {
+                let mut after = board.clone();
+                after.place(coords, me).ok()?;
+                Some((coords, after))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // Playouts go in rounds of one per candidate, so that every candidate
+        // is played out the same number of times
+        let mut rng = rand::rng();
+        let mut points = vec![0; candidates.len()];
+        let mut played = 0;
+        while played < self.playouts {
+            if played > 0
+                && self
+                    .time_limit
+                    .is_some_and(|limit| start.elapsed() >= limit)
+            {
+                break;
+            }
+            for ((_, after), points) in candidates.iter().zip(points.iter_mut()) {
+                *points += playout(after, me, &mut rng);
+            }
+            played += candidates.len().max(1) as u32;
+        }
+
+        // Ties keep the better evaluated cell, which comes first
+        let mut best: Option<(u32, TetraCoordinates)> = None;
+        for ((coords, _), points) in candidates.iter().zip(points) {
+            if best.is_none_or(|(best_points, _)| points > best_points) {
+                best = Some((points, *coords));
+            }
+        }
+        best.map(|(_, coords)| coords)
+    }
+}
+
+/// Plays `board` out with random moves and returns the points of `player`:
+/// two for a win and one for a game nobody won.
+fn playout<R: Rng + ?Sized>(board: &TetraGame, player: PlayerId, rng: &mut R) -> u32 {
+    let mut game = board.clone();
+    let mut cells = game.available_moves();
+    cells.shuffle(rng);
+    for cell in cells {
+        let Some(next) = game.next_player() else {
+            break;
+        };
+        if game.place(cell, next).is_err() {
+            break;
+        }
+    }
+    match game.winner() {
+        Some(winner) if winner == player => 2,
+        Some(_) => 0,
+        None => 1,
+    }
+}
+
+/// Returns the cell the player to move must play: one that wins, or failing
+/// that one where the opponent would win.
+fn forced_move(board: &TetraGame) -> Option<TetraCoordinates> {
+    let me = board.next_player()?;
+    let opponent = PlayerId::new(1 - me.id());
+    let available = board.available_moves();
+    available
+        .iter()
+        .find(|coords| board.completes_win(coords, me))
+        .or_else(|| {
+            available
+                .iter()
+                .find(|coords| board.completes_win(coords, opponent))
+        })
+        .copied()
+}
+
+/// The cells of a board with their neighbours by index, built once per move
+/// and shared by every face distance computed for it.
+struct CellTable {
+    cells: Vec<TetraCoordinates>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl CellTable {
+    fn new(board: &TetraGame) -> Self {
+        let cells = board.all_cells();
+        let neighbors = cells
+            .iter()
+            .map(|coords| {
+                board
+                    .neighbors(coords)
+                    .iter()
+                    .filter_map(|neighbor| board.index(neighbor))
+                    .collect()
+            })
+            .collect();
+        CellTable { cells, neighbors }
+    }
+}
+
+/// Returns the empty cells sorted from best to worst for the player to move.
+///
+/// A cell is scored by the face distance of the opponent minus that of the
+/// player with a stone assumed on it. Ties go to central cells, those whose
+/// smallest coordinate is largest.
+fn ranked_moves(board: &TetraGame) -> Vec<TetraCoordinates> {
+    let Some(me) = board.next_player() else {
+        return Vec::new();
+    };
+    let opponent = PlayerId::new(1 - me.id());
+    let table = CellTable::new(board);
+    let cut_off = table.cells.len() as i64 + 1;
+
+    let mut scored = table
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|(_, coords)| board.player_at(coords).is_none())
+        .map(|(idx, &coords)| {
+            let score = if board.completes_win(&coords, me) {
+                i64::MAX
+            } else {
+                let extra = Some((idx, me));
+                let mine = face_distance(board, &table, me, extra).map_or(cut_off, i64::from);
+                let theirs =
+                    face_distance(board, &table, opponent, extra).map_or(cut_off, i64::from);
+                theirs - mine
+            };
+            let centrality = coords.values().into_iter().min().unwrap_or(0);
+            (coords, (score, centrality))
+        })
+        .collect::<Vec<_>>();
+
+    scored.sort_by_key(|(_, key)| std::cmp::Reverse(*key));
+    scored.into_iter().map(|(coords, _)| coords).collect()
+}
+
+/// Returns the number of empty cells `player` still has to fill so that one
+/// of their groups touches all four faces, or None if the opponent has cut
+/// them off from some face.
+///
+/// `extra` is a stone assumed on the cell with that index, so candidate moves
+/// can be evaluated without cloning the game. Every face gets a 0-1 BFS
+/// distance map where own stones cost nothing, empty cells cost one and
+/// opponent stones are walls. The estimate is the cheapest cell where the four
+/// paths can meet, counting that cell once.
+fn face_distance(
+    board: &TetraGame,
+    table: &CellTable,
+    player: PlayerId,
+    extra: Option<(usize, PlayerId)>,
+) -> Option<u32> {
+    let cells = &table.cells;
+    let cost: Vec<Option<u32>> = cells
+        .iter()
+        .enumerate()
+        .map(|(idx, coords)| {
+            let owner = match extra {
+                Some((extra_idx, extra_player)) if extra_idx == idx => Some(extra_player),
+                _ => board.player_at(coords),
+            };
+            match owner {
+                Some(owner) if owner == player => Some(0),
+                Some(_) => None,
+                None => Some(1),
+            }
+        })
+        .collect();
+
+    let maps: Vec<Vec<u32>> = (0..4)
+        .map(|face| {
+            let mut dist = vec![u32::MAX; cells.len()];
+            let mut queue = VecDeque::new();
+            for (idx, coords) in cells.iter().enumerate() {
+                if coords.values()[face] != 0 {
+                    continue;
+                }
+                let Some(c) = cost[idx] else { continue };
+                dist[idx] = c;
+                if c == 0 {
+                    queue.push_front(idx);
+                } else {
+                    queue.push_back(idx);
+                }
+            }
+            while let Some(idx) = queue.pop_front() {
+                for &next in &table.neighbors[idx] {
+                    let Some(c) = cost[next] else { continue };
+                    if dist[idx] + c < dist[next] {
+                        dist[next] = dist[idx] + c;
+                        if c == 0 {
+                            queue.push_front(next);
+                        } else {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+            dist
+        })
+        .collect();
+
+    (0..cells.len())
+        .filter_map(|idx| {
+            let c = cost[idx]?;
+            let total = maps
+                .iter()
+                .map(|dist| (dist[idx] != u32::MAX).then_some(dist[idx]))
+                .sum::<Option<u32>>()?;
+            Some(total - 3 * c)
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn p(id: u32) -> PlayerId {
+        PlayerId::new(id)
+    }
+
+    fn t(a: u32, b: u32, c: u32, d: u32) -> TetraCoordinates {
+        TetraCoordinates::new(a, b, c, d)
+    }
+
+    fn play(game: &mut TetraGame, moves: &[TetraCoordinates]) {
+        for coords in moves {
+            let player = game.next_player().unwrap();
+            game.place(*coords, player).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_names_match_y_bots() {
+        assert_eq!(TetraRandomBot.name(), "random_bot");
+        assert_eq!(TetraDistanceBot.name(), "intermediate_bot");
+        assert_eq!(TetraPlayoutBot::new().name(), "hard_bot");
+    }
+
+    #[test]
+    fn test_bots_play_empty_cells() {
+        let mut game = TetraGame::new(3).unwrap();
+        play(&mut game, &[t(1, 0, 1, 0)]);
+        let bots: [&dyn TetraBot; 3] =
+            [&TetraRandomBot, &TetraDistanceBot, &TetraPlayoutBot::new()];
+        for bot in bots {
+            let coords = bot.choose_move(&game).unwrap();
+            assert!(game.available_moves().contains(&coords));
+        }
+    }
+
+    #[test]
+    fn test_no_move_after_game_over() {
+        let mut game = TetraGame::new(2).unwrap();
+        play(&mut game, &[t(1, 0, 0, 0), t(0, 0, 0, 1), t(0, 1, 0, 0)]);
+        assert!(game.check_game_over());
+        let bots: [&dyn TetraBot; 3] =
+            [&TetraRandomBot, &TetraDistanceBot, &TetraPlayoutBot::new()];
+        for bot in bots {
+            assert!(bot.choose_move(&game).is_none());
+        }
+    }
+
+    fn distance(game: &TetraGame, player: PlayerId) -> Option<u32> {
+        face_distance(game, &CellTable::new(game), player, None)
+    }
+
+    #[test]
+    fn test_face_distance() {
+        let game = TetraGame::new(4).unwrap();
+        // The shortest group touching every face runs from a corner to the opposite face
+        assert_eq!(distance(&game, p(0)), Some(4));
+        let mut game = TetraGame::new(3).unwrap();
+        play(&mut game, &[t(2, 0, 0, 0), t(0, 0, 0, 2), t(1, 1, 0, 0)]);
+        assert_eq!(distance(&game, p(0)), Some(1));
+    }
+
+    #[test]
+    fn test_extra_stone_matches_placing_it() {
+        let mut game = TetraGame::new(4).unwrap();
+        play(&mut game, &[t(1, 1, 1, 0), t(0, 1, 1, 1), t(2, 0, 0, 1)]);
+        let table = CellTable::new(&game);
+        for (idx, coords) in table.cells.iter().enumerate() {
+            if game.player_at(coords).is_some() {
+                continue;
+            }
+            let mut after = game.clone();
+            after.place(*coords, p(1)).unwrap();
+            for player in [p(0), p(1)] {
+                assert_eq!(
+                    face_distance(&game, &table, player, Some((idx, p(1)))),
+                    distance(&after, player)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_playout_bot_builders() {
+        let bot = TetraPlayoutBot::default();
+        assert_eq!(bot.playouts(), TetraPlayoutBot::DEFAULT_PLAYOUTS);
+        assert_eq!(bot.time_limit(), None);
+        let bot = TetraPlayoutBot::new()
+            .with_playouts(0)
+            .with_time_limit(Duration::from_millis(20));
+        assert_eq!(bot.playouts(), 1);
+        assert_eq!(bot.time_limit(), Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn test_playout_bot_time_limit_stops_the_playouts() {
+        let bot = TetraPlayoutBot::new()
+            .with_playouts(u32::MAX)
+            .with_time_limit(Duration::from_millis(50));
+        let game = TetraGame::new(5).unwrap();
+        let start = Instant::now();
+        assert!(bot.choose_move(&game).is_some());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_bots_take_the_winning_cell() {
+        let mut game = TetraGame::new(3).unwrap();
+        play(
+            &mut game,
+            &[t(2, 0, 0, 0), t(0, 0, 0, 2), t(1, 1, 0, 0), t(0, 0, 2, 0)],
+        );
+        let winning: Vec<_> = game
+            .available_moves()
+            .into_iter()
+            .filter(|coords| game.completes_win(coords, p(0)))
+            .collect();
+        assert!(!winning.is_empty());
+        assert!(winning.contains(&TetraDistanceBot.choose_move(&game).unwrap()));
+        assert!(winning.contains(&TetraPlayoutBot::new().choose_move(&game).unwrap()));
+    }
+
+    #[test]
+    fn test_bots_block_the_opponent() {
+        let mut game = TetraGame::new(4).unwrap();
+        play(
+            &mut game,
+            &[
+                t(0, 0, 0, 3),
+                t(3, 0, 0, 0),
+                t(0, 2, 1, 0),
+                t(2, 1, 0, 0),
+                t(0, 3, 0, 0),
+                t(1, 2, 0, 0),
+            ],
+        );
+        // Player 1 threatens to win, and player 0 has no win of their own
+        let available = game.available_moves();
+        assert!(
+            !available
+                .iter()
+                .any(|coords| game.completes_win(coords, p(0)))
+        );
+        let threats: Vec<_> = available
+            .into_iter()
+            .filter(|coords| game.completes_win(coords, p(1)))
+            .collect();
+        assert_eq!(threats.len(), 1);
+        assert!(threats.contains(&TetraDistanceBot.choose_move(&game).unwrap()));
+        assert!(threats.contains(&TetraPlayoutBot::new().choose_move(&game).unwrap()));
+    }
+
+    #[test]
+    fn test_playout_bot_beats_a_random_opponent() {
+        for seed in 0..3 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = TetraGame::new(4).unwrap();
+            while let Some(player) = game.next_player() {
+                let coords = if player.id() == 0 {
+                    TetraPlayoutBot::new().choose_move(&game)
+                } else {
+                    game.available_moves().choose(&mut rng).copied()
+                };
+                game.place(coords.unwrap(), player).unwrap();
+            }
+            assert_eq!(game.winner(), Some(p(0)), "lost with seed {seed}");
+        }
+    }
+}
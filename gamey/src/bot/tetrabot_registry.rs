@@ -0,0 +1,87 @@
+//! Registry for managing TetraBot implementations.
+//!
+//! The [`TetraBotRegistry`] is the [`YBotRegistry`](crate::YBotRegistry) of the
+//! tetrahedral variant: it stores [`TetraBot`] implementations by name.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::TetraBot;
+
+/// A registry that stores and manages [`TetraBot`] implementations.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use gamey::{TetraBotRegistry, TetraPlayoutBot, TetraRandomBot};
+///
+/// let registry = TetraBotRegistry::new()
+///     .with_bot(Arc::new(TetraRandomBot))
+///     .with_bot(Arc::new(TetraPlayoutBot::new()));
+///
+/// assert!(registry.find("hard_bot").is_some());
+/// ```
+pub struct TetraBotRegistry {
+    bots: HashMap<String, Arc<dyn TetraBot>>,
+}
+
+impl TetraBotRegistry {
+    /// Creates a new empty registry.
+    pub fn new() -> Self {
+        TetraBotRegistry {
+            bots: HashMap::new(),
+        }
+    }
+
+    /// Adds a bot to the registry and returns the registry for chaining.
+    ///
+    /// The bot is registered under its name (as returned by [`TetraBot::name`]).
+    pub fn with_bot(mut self, bot: Arc<dyn TetraBot>) -> Self {
+        self.bots.insert(bot.name().to_string(), bot);
+        self
+    }
+
+    /// Finds a bot by name.
+    ///
+    /// Returns `Some(bot)` if a bot with the given name exists, `None` otherwise.
+    pub fn find(&self, name: &str) -> Option<Arc<dyn TetraBot>> {
+        self.bots.get(name).cloned()
+    }
+
+    /// Returns a list of all registered bot names.
+    pub fn names(&self) -> Vec<String> {
+        self.bots.keys().cloned().collect()
+    }
+}
+
+impl Default for TetraBotRegistry {
+    fn default() -> Self {
+        TetraBotRegistry::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TetraDistanceBot, TetraPlayoutBot, TetraRandomBot};
+
+    #[test]
+    fn test_default_registry_is_empty() {
+        assert!(TetraBotRegistry::default().names().is_empty());
+    }
+
+    #[test]
+    fn test_with_bot_registers_by_name() {
+        let registry = TetraBotRegistry::new()
+            .with_bot(Arc::new(TetraRandomBot))
+            .with_bot(Arc::new(TetraDistanceBot))
+            .with_bot(Arc::new(TetraPlayoutBot::new()));
+
+        assert_eq!(registry.names().len(), 3);
+        assert_eq!(
+            registry.find("intermediate_bot").unwrap().name(),
+            "intermediate_bot"
+        );
+        assert!(registry.find("nonexistent").is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Coordinates, GameY};
+use crate::{Coordinates, GameY, MoveAnalysis, Movement, SearchBudget};
 
 /// Trait representing a Y game bot (YBot)
 /// A YBot is an AI that can choose moves in the game of Y.
@@ -9,4 +9,35 @@ pub trait YBot: Send + Sync {
 
     /// Chooses a move based on the current game state.
     fn choose_move(&self, board: &GameY) -> Option<Coordinates>;
+
+    /// Chooses a move without going beyond the given search budget.
+    ///
+    /// Bots that search override this to stop when the budget runs out. The
+    /// default implementation ignores the budget and calls [`YBot::choose_move`].
+    fn choose_move_with_budget(&self, board: &GameY, budget: &SearchBudget) -> Option<Coordinates> {
+        let _ = budget;
+        self.choose_move(board)
+    }
+
+    /// Chooses the full decision of the player to move within the given
+    /// search budget: a placement, or an action such as a swap or a
+    /// resignation. Returns None if the game is over.
+    ///
+    /// The default implementation places a stone on the cell chosen by
+    /// [`YBot::choose_move_with_budget`]. Pass [`SearchBudget::new`] for no limit.
+    fn choose_movement(&self, board: &GameY, budget: &SearchBudget) -> Option<Movement> {
+        let player = board.next_player()?;
+        let coords = self.choose_move_with_budget(board, budget)?;
+        Some(Movement::Placement { player, coords })
+    }
+
+    /// Returns up to `count` of the best moves for the player to move, best
+    /// first, each with the bot's score and the main reason for it.
+    ///
+    /// Returns None if the bot cannot analyse the position, which is what the
+    /// default implementation does, and an empty list if the game is over.
+    fn analyze(&self, board: &GameY, count: usize) -> Option<Vec<MoveAnalysis>> {
+        let _ = (board, count);
+        None
+    }
 }
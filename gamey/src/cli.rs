@@ -5,11 +5,15 @@
 //! - Human vs Human: Two players take turns at the same terminal
 //! - Human vs Computer: Play against a bot
 //! - Server: Run as an HTTP server for bot API
+//!
+//! With `--variant hex` the human and computer modes play Hex instead of Y,
+//! on a rhombus board of the given size.
 
 use crate::{
-    Coordinates, GameAction, Movement, RandomBot, RenderOptions, YBot, YBotRegistry, game,
+    Coordinates, GameAction, HexBot, HexBotRegistry, HexCell, HexDistanceBot, HexGame,
+    HexPlayoutBot, HexRandomBot, Movement, RandomBot, RenderOptions, YBot, YBotRegistry, game,
 };
-use crate::{GameStatus, GameY, PlayerId};
+use crate::{GameRules, GameStatus, GameY, PlayerId};
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
 use rustyline::DefaultEditor;
@@ -22,10 +26,14 @@ use std::sync::Arc;
 #[command(author, version, about)]
 #[command(long_about = "GameY: A command-line implementation of the Game of Y.")]
 pub struct CliArgs {
-    /// Size of the triangular board (length of one side).
+    /// Size of the triangular board (length of one side), or of the Hex rhombus.
     #[arg(short, long, default_value_t = 7)]
     pub size: u32,
 
+    /// Game to play: y (the Game of Y) or hex.
+    #[arg(long, default_value_t = Variant::Y)]
+    pub variant: Variant,
+
     /// Game mode: human (2-player), computer (vs bot), or server (HTTP API).
     #[arg(short, long, default_value_t = Mode::Human)]
     pub mode: Mode,
@@ -37,6 +45,53 @@ pub struct CliArgs {
     /// Port to run the server on (only used with --mode=server)
     #[arg(short, long, default_value_t = 3000)]
     pub port: u16,
+
+    /// Disable the swap rule.
+    #[arg(long)]
+    pub no_swap: bool,
+
+    /// Number of players in the game.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(2..=GameRules::MAX_PLAYERS as i64))]
+    pub players: u32,
+
+    /// The player who makes the first move (0 up to the number of players minus one).
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..GameRules::MAX_PLAYERS as i64))]
+    pub first_player: u32,
+
+    /// Play misère: the player who connects the three sides loses.
+    #[arg(long)]
+    pub misere: bool,
+
+    /// Reject moves played after the game has finished.
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Largest board size allowed.
+    #[arg(long, default_value_t = GameRules::DEFAULT_MAX_BOARD_SIZE)]
+    pub max_size: u32,
+
+    /// Number of handicap stones placed at the standard positions before the game starts.
+    #[arg(long, default_value_t = 0)]
+    pub handicap: u32,
+
+    /// The player who receives the handicap stones.
+    #[arg(long, default_value_t = 0)]
+    pub handicap_player: u32,
+}
+
+impl CliArgs {
+    /// Returns the game rules selected by the arguments.
+    pub fn rules(&self) -> GameRules {
+        GameRules {
+            swap_allowed: !self.no_swap,
+            num_players: self.players,
+            starting_player: PlayerId::new(self.first_player),
+            misere: self.misere,
+            strict_game_over: self.strict,
+            max_board_size: self.max_size,
+            ..GameRules::default()
+        }
+    }
 }
 
 /// The game mode determining how the game is played.
@@ -61,12 +116,34 @@ impl Display for Mode {
     }
 }
 
+/// The game played by the CLI.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum Variant {
+    /// The Game of Y on a triangular board.
+    Y,
+    /// Hex on a rhombus board.
+    Hex,
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Variant::Y => "y",
+            Variant::Hex => "hex",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 /// Runs the interactive CLI game loop.
 ///
 /// This function parses command-line arguments, initializes the game,
 /// and runs the main game loop where players enter moves via the terminal.
 pub fn run_cli_game() -> Result<()> {
     let args = CliArgs::parse();
+    if args.variant == Variant::Hex {
+        return run_hex_game(&args);
+    }
     let mut render_options = crate::RenderOptions::default();
     let mut rl = DefaultEditor::new()?;
     let bots_registry = YBotRegistry::new().with_bot(Arc::new(RandomBot));
@@ -81,7 +158,8 @@ pub fn run_cli_game() -> Result<()> {
             return Ok(());
         }
     };
-    let mut game = game::GameY::new(args.size);
+    let mut game = game::GameY::with_rules(args.size, args.rules())?
+        .with_handicap(PlayerId::new(args.handicap_player), args.handicap)?;
     loop {
         println!("{}", game.render(&render_options));
         let status = game.status();
@@ -143,7 +221,30 @@ fn process_input(
                 player: *player,
                 action: GameAction::Resign,
             };
-            apply_move(game, movement, "Error adding resign move");
+            if apply_move(game, movement, "Error adding resign move")
+                && mode == Mode::Computer
+                && !game.check_game_over()
+            {
+                trigger_bot_move(game, bot, *player);
+            }
+        }
+        Command::Swap => {
+            let movement = Movement::Action {
+                player: *player,
+                action: GameAction::Swap,
+            };
+            if apply_move(game, movement, "Error adding swap move")
+                && mode == Mode::Computer
+                && !game.check_game_over()
+            {
+                trigger_bot_move(game, bot, *player);
+            }
+        }
+        Command::Undo => {
+            handle_undo_command(game, *player, mode);
+        }
+        Command::Redo => {
+            handle_redo_command(game, *player, mode);
         }
         Command::Show3DCoords => {
             render_options.show_3d_coords = !render_options.show_3d_coords;
@@ -216,6 +317,9 @@ pub fn parse_command(input: &str, bound: u32) -> Command {
             }
         }
         "resign" => Command::Resign,
+        "swap" => Command::Swap,
+        "undo" => Command::Undo,
+        "redo" => Command::Redo,
         "help" => Command::Help,
         "exit" => Command::Exit,
         "show_colors" => Command::ShowColors,
@@ -230,11 +334,33 @@ pub fn parse_command(input: &str, bound: u32) -> Command {
     }
 }
 
+/// Parses a user input string into a Command for a Hex board of `size`.
+///
+/// Cells can be given in Hex notation, such as `c4`, or by index. Every other
+/// command is parsed as in [`parse_command`].
+pub fn parse_hex_command(input: &str, size: u32) -> Command {
+    let Some(first) = input.split_whitespace().next() else {
+        return Command::None;
+    };
+    match first.parse::<HexCell>() {
+        Ok(cell) if cell.row() < size && cell.col() < size => Command::Place {
+            idx: cell.row() * size + cell.col(),
+        },
+        Ok(cell) => Command::Error {
+            message: format!("Cell {} is not on the board", cell),
+        },
+        Err(_) => parse_command(input, size * size),
+    }
+}
+
 /// Prints the help message listing all available commands.
 fn print_help() {
     println!("Available commands:");
     println!("  <number>        - Place a piece at the specified index number");
     println!("  resign          - Resign from the game");
+    println!("  swap            - Take over the opening stone (second player's first move)");
+    println!("  undo            - Take back the last move");
+    println!("  redo            - Replay the last move taken back");
     println!("  show_coords     - Toggle showing coordinates on the board");
     println!("  show_idx        - Toggle showing index numbers on the board");
     println!("  show_colors     - Toggle showing colors on the board");
@@ -251,6 +377,12 @@ pub enum Command {
     Place { idx: u32 },
     /// Resign from the game.
     Resign,
+    /// Use the swap rule to take over the opening stone.
+    Swap,
+    /// Take back the last move.
+    Undo,
+    /// Replay the last move taken back.
+    Redo,
     /// No command was entered (empty input).
     None,
     /// An error occurred while parsing the command.
@@ -304,24 +436,156 @@ fn handle_place_command(
     if apply_move(game, movement, "Error adding move") {
         // Only trigger bot if the human move was valid, mode is computer, and game isn't over
         if mode == Mode::Computer && !game.check_game_over() {
-            trigger_bot_move(game, bot);
+            trigger_bot_move(game, bot, player);
         }
     }
 }
 
-/// AI logic extracted to its own function
-fn trigger_bot_move(game: &mut GameY, bot: &dyn YBot) {
-    if let Some(bot_coords) = bot.choose_move(game) {
-        // Assuming next_player() is safe to unwrap here because the game isn't over
-        if let Some(bot_player) = game.next_player() {
-            let bot_movement = Movement::Placement {
-                player: bot_player,
-                coords: bot_coords,
-            };
-            apply_move(game, bot_movement, "Error adding bot move");
+/// Takes back the last move. Against the computer, the bot's reply is taken
+/// back as well so that the human player is to move again.
+fn handle_undo_command(game: &mut GameY, player: PlayerId, mode: Mode) {
+    if let Err(e) = game.undo_move() {
+        println!("Error undoing move: {}", e);
+        return;
+    }
+    while mode == Mode::Computer && game.next_player() != Some(player) {
+        if game.undo_move().is_err() {
+            break;
+        }
+    }
+}
+
+/// Replays the last move taken back. Against the computer, the bot's reply is
+/// replayed as well so that the human player is to move again.
+fn handle_redo_command(game: &mut GameY, player: PlayerId, mode: Mode) {
+    if let Err(e) = game.redo_move() {
+        println!("Error redoing move: {}", e);
+        return;
+    }
+    while mode == Mode::Computer && !game.check_game_over() && game.next_player() != Some(player) {
+        if game.redo_move().is_err() {
+            break;
+        }
+    }
+}
+
+/// AI logic extracted to its own function.
+///
+/// The bot plays for every player other than `human` until it is the human's
+/// turn again or the game is over.
+fn trigger_bot_move(game: &mut GameY, bot: &dyn YBot, human: PlayerId) {
+    while let Some(bot_player) = game.next_player() {
+        if bot_player == human {
+            break;
+        }
+        let Some(bot_coords) = bot.choose_move(game) else {
+            println!("No available moves for the bot.");
+            break;
+        };
+        let bot_movement = Movement::Placement {
+            player: bot_player,
+            coords: bot_coords,
+        };
+        if !apply_move(game, bot_movement, "Error adding bot move") {
+            break;
         }
-    } else {
-        println!("No available moves for the bot.");
+    }
+}
+
+/// Runs the interactive game loop for Hex.
+///
+/// Hex games cannot be saved, and have no swap, resignation or undo.
+fn run_hex_game(args: &CliArgs) -> Result<()> {
+    let mut render_options = crate::RenderOptions::default();
+    let mut rl = DefaultEditor::new()?;
+    let bots_registry = HexBotRegistry::new()
+        .with_bot(Arc::new(HexRandomBot))
+        .with_bot(Arc::new(HexDistanceBot))
+        .with_bot(Arc::new(HexPlayoutBot::new()));
+    let bot: Arc<dyn HexBot> = match bots_registry.find(&args.bot) {
+        Some(b) => b,
+        None => {
+            println!(
+                "Bot '{}' not found. Available bots: {:?}",
+                args.bot,
+                bots_registry.names()
+            );
+            return Ok(());
+        }
+    };
+    let mut game = HexGame::new(args.size)?;
+    loop {
+        println!("{}", game.render(&render_options));
+        let player = match game.status() {
+            GameStatus::Finished { winner } => {
+                println!("Game over! Winner: {}", winner);
+                break;
+            }
+            GameStatus::Ongoing { next_player } => *next_player,
+        };
+        let prompt = format!(
+            "Current player: {}, action (help = show commands)? ",
+            player
+        );
+        let line = match rl.readline(&prompt) {
+            Err(ReadlineError::Interrupted) => {
+                println!("Interrupted");
+                break;
+            }
+            Err(err) => {
+                println!("Error: {:?}", err);
+                continue;
+            }
+            Ok(line) => line,
+        };
+        rl.add_history_entry(line.as_str())?;
+        match parse_hex_command(&line, game.board_size()) {
+            Command::Place { idx } => {
+                let size = game.board_size();
+                let cell = HexCell::new(idx / size, idx % size);
+                match game.play(player, cell) {
+                    Ok(()) if args.mode == Mode::Computer => {
+                        trigger_hex_bot_move(&mut game, bot.as_ref())
+                    }
+                    Ok(()) => {}
+                    Err(e) => println!("Error adding move: {}", e),
+                }
+            }
+            Command::Show3DCoords => render_options.show_3d_coords = !render_options.show_3d_coords,
+            Command::ShowIdx => render_options.show_idx = !render_options.show_idx,
+            Command::ShowColors => render_options.show_colors = !render_options.show_colors,
+            Command::Help => print_hex_help(),
+            Command::Exit => {
+                println!("Exiting the game.");
+                break;
+            }
+            Command::None => println!("No command entered."),
+            Command::Error { message } => println!("Error parsing command: {}", message),
+            _ => println!("This command is not available in Hex."),
+        }
+    }
+    Ok(())
+}
+
+/// Prints the help message listing the commands available in Hex.
+fn print_hex_help() {
+    println!("Available commands:");
+    println!("  <cell>          - Place a piece at a cell in Hex notation, such as c4");
+    println!("  <number>        - Place a piece at the specified index number");
+    println!("  show_coords     - Toggle showing cell names on the board");
+    println!("  show_idx        - Toggle showing index numbers on the board");
+    println!("  show_colors     - Toggle showing colors on the board");
+    println!("  exit            - Exit the game");
+    println!("  help            - Show this help message");
+}
+
+/// Lets the bot play its move in a Hex game, unless the game is over.
+fn trigger_hex_bot_move(game: &mut HexGame, bot: &dyn HexBot) {
+    let (Some(bot_player), Some(cell)) = (game.next_player(), bot.choose_move(game)) else {
+        return;
+    };
+    if let Err(e) = game.play(bot_player, cell) {
+        println!("Error adding bot move: {}", e);
     }
 }
 
@@ -395,6 +659,24 @@ mod tests {
         assert_eq!(cmd, Command::Resign);
     }
 
+    #[test]
+    fn test_parse_command_swap() {
+        let cmd = parse_command("swap", 10);
+        assert_eq!(cmd, Command::Swap);
+    }
+
+    #[test]
+    fn test_parse_command_undo() {
+        let cmd = parse_command("undo", 10);
+        assert_eq!(cmd, Command::Undo);
+    }
+
+    #[test]
+    fn test_parse_command_redo() {
+        let cmd = parse_command("redo", 10);
+        assert_eq!(cmd, Command::Redo);
+    }
+
     #[test]
     fn test_parse_command_help() {
         let cmd = parse_command("help", 10);
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Represents special game actions that are not regular piece placements.
@@ -12,6 +13,18 @@ pub enum GameAction {
     Resign,
 }
 
+/// How the opening stone is handled when the second player uses the swap rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapMode {
+    /// The opening stone stays where it is and changes owner.
+    #[default]
+    Transfer,
+    /// The opening stone is reflected across the axis through the top corner
+    /// (swapping its y and z coordinates) and changes owner.
+    Mirror,
+}
+
 impl Display for GameAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -42,6 +55,11 @@ mod tests {
         assert_ne!(GameAction::Swap, GameAction::Resign);
     }
 
+    #[test]
+    fn test_default_swap_mode_is_transfer() {
+        assert_eq!(SwapMode::default(), SwapMode::Transfer);
+    }
+
     #[test]
     fn test_clone() {
         let action = GameAction::Swap;
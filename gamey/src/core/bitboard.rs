@@ -0,0 +1,93 @@
+/// A set of cell indices stored as a bit array, one bit per cell.
+///
+/// Used to keep the stones of each player so that boards can be cloned and
+/// queried without hashing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Bitboard {
+    words: Vec<u64>,
+}
+
+impl Bitboard {
+    /// Creates an empty bitboard able to hold `len` cells.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// Adds the cell `idx` to the set.
+    pub fn insert(&mut self, idx: usize) {
+        self.words[idx / 64] |= 1 << (idx % 64);
+    }
+
+    /// Removes the cell `idx` from the set.
+    pub fn remove(&mut self, idx: usize) {
+        self.words[idx / 64] &= !(1 << (idx % 64));
+    }
+
+    /// Returns true if the cell `idx` is in the set.
+    pub fn contains(&self, idx: usize) -> bool {
+        self.words
+            .get(idx / 64)
+            .is_some_and(|word| word & (1 << (idx % 64)) != 0)
+    }
+
+    /// Iterates over the cells in the set in increasing index order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word_idx, word)| {
+            let mut bits = *word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(word_idx * 64 + bit)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_bitboard_is_empty() {
+        let bb = Bitboard::new(100);
+        assert_eq!(bb.iter().count(), 0);
+        assert!(!bb.contains(0));
+        assert!(!bb.contains(99));
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut bb = Bitboard::new(130);
+        bb.insert(0);
+        bb.insert(64);
+        bb.insert(129);
+        assert!(bb.contains(0));
+        assert!(bb.contains(64));
+        assert!(bb.contains(129));
+        assert_eq!(bb.iter().count(), 3);
+
+        bb.remove(64);
+        assert!(!bb.contains(64));
+        assert_eq!(bb.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_contains_out_of_range_is_false() {
+        let bb = Bitboard::new(10);
+        assert!(!bb.contains(1000));
+    }
+
+    #[test]
+    fn test_iter_returns_sorted_indices() {
+        let mut bb = Bitboard::new(200);
+        for idx in [150, 3, 64, 63, 0] {
+            bb.insert(idx);
+        }
+        assert_eq!(bb.iter().collect::<Vec<_>>(), vec![0, 3, 63, 64, 150]);
+    }
+}
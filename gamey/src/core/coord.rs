@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GameYError, Symmetry};
+
 /// Represents barycentric coordinates (x, y, z) on a triangular board.
 ///
 /// In a triangular board of size N, valid coordinates satisfy:
@@ -62,26 +64,48 @@ impl Coordinates {
 
     /// Converts these coordinates to a linear index.
     ///
-    /// This is the inverse of `from_index`.
+    /// This is the inverse of `from_index`. The coordinates must be a cell of
+    /// the board (see [`Coordinates::validate`]); otherwise the result is
+    /// meaningless.
     pub fn to_index(&self, board_size: u32) -> u32 {
-        println!(
-    "🔹 board_size={}, x={}, y={}, z={}, (board_size-1)-x={} ",
-            board_size,
-            self.x,
-            self.y,
-            self.z,
-            (board_size - 1).checked_sub(self.x).unwrap_or_else(|| {
-                println!("❌ Overflow detectado!");
-        0
-    })
-);
-
-        let r = (board_size - 1) - self.x;
+        debug_assert!(
+            self.x < board_size,
+            "{} is not a cell of a board of size {}",
+            self,
+            board_size
+        );
+        let r = board_size.saturating_sub(1).saturating_sub(self.x);
         let row_start_index = (r * (r + 1)) / 2;
         let c = self.y;
         row_start_index + c
     }
 
+    /// Checks that these coordinates describe a cell of a board of the given size.
+    ///
+    /// Returns [`GameYError::CoordOutOfRange`] naming the first coordinate that
+    /// is not below the board size. If all of them are, but they do not add up
+    /// to `board_size - 1`, the z coordinate is reported, since it is the one
+    /// determined by the other two.
+    pub fn validate(&self, board_size: u32) -> Result<(), GameYError> {
+        for (id_coord, coord) in [('x', self.x), ('y', self.y), ('z', self.z)] {
+            if coord >= board_size {
+                return Err(GameYError::CoordOutOfRange {
+                    id_coord,
+                    coord,
+                    board_size,
+                });
+            }
+        }
+        if self.x + self.y + self.z != board_size - 1 {
+            return Err(GameYError::CoordOutOfRange {
+                id_coord: 'z',
+                coord: self.z,
+                board_size,
+            });
+        }
+        Ok(())
+    }
+
     /// Creates coordinates from a slice of 3 u32 values.
     ///
     /// Returns `None` if the slice does not have exactly 3 elements.
@@ -96,6 +120,20 @@ impl Coordinates {
         })
     }
 
+    /// Creates coordinates from a slice of values and checks that they are a
+    /// cell of a board of the given size.
+    ///
+    /// Returns [`GameYError::BadCoordsNumber`] if the slice does not have
+    /// exactly 3 elements, or the error of [`Coordinates::validate`].
+    pub fn try_from_vec(coords: &[u32], board_size: u32) -> Result<Self, GameYError> {
+        let coords = Self::from_vec(coords).ok_or(GameYError::BadCoordsNumber {
+            expected: 3,
+            found: coords.len(),
+        })?;
+        coords.validate(board_size)?;
+        Ok(coords)
+    }
+
     /// Returns true if this cell touches side A (x == 0).
     pub fn touches_side_a(&self) -> bool {
         self.x == 0
@@ -110,6 +148,27 @@ impl Coordinates {
     pub fn touches_side_c(&self) -> bool {
         self.z == 0
     }
+
+    /// Returns the cell these coordinates are moved to by `symmetry` on a
+    /// board of the given size.
+    pub fn transform(&self, symmetry: Symmetry, board_size: u32) -> Self {
+        debug_assert_eq!(
+            self.x + self.y + self.z + 1,
+            board_size,
+            "{} is not a cell of a board of size {}",
+            self,
+            board_size
+        );
+        let Self { x, y, z } = *self;
+        match symmetry {
+            Symmetry::Identity => Self::new(x, y, z),
+            Symmetry::Rotate120 => Self::new(z, x, y),
+            Symmetry::Rotate240 => Self::new(y, z, x),
+            Symmetry::ReflectX => Self::new(x, z, y),
+            Symmetry::ReflectY => Self::new(z, y, x),
+            Symmetry::ReflectZ => Self::new(y, x, z),
+        }
+    }
 }
 
 impl From<Coordinates> for Vec<u32> {
@@ -213,9 +272,88 @@ mod tests {
         assert!(!interior.touches_side_c());
     }
 
+    #[test]
+    fn test_validate_accepts_board_cells() {
+        for idx in 0..15 {
+            assert!(Coordinates::from_index(idx, 5).validate(5).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_coordinate_too_large() {
+        let err = Coordinates::new(0, 5, 0).validate(5).unwrap_err();
+        assert!(matches!(
+            err,
+            GameYError::CoordOutOfRange {
+                id_coord: 'y',
+                coord: 5,
+                board_size: 5
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_wrong_sum() {
+        let err = Coordinates::new(1, 1, 1).validate(5).unwrap_err();
+        assert!(matches!(
+            err,
+            GameYError::CoordOutOfRange { id_coord: 'z', .. }
+        ));
+        assert!(Coordinates::new(0, 0, 0).validate(0).is_err());
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        assert_eq!(
+            Coordinates::try_from_vec(&[1, 2, 1], 5).unwrap(),
+            Coordinates::new(1, 2, 1)
+        );
+        assert!(matches!(
+            Coordinates::try_from_vec(&[1, 2], 5),
+            Err(GameYError::BadCoordsNumber {
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            Coordinates::try_from_vec(&[9, 0, 0], 5),
+            Err(GameYError::CoordOutOfRange { id_coord: 'x', .. })
+        ));
+    }
+
+    #[test]
+    fn test_transform_moves_corners() {
+        let top = Coordinates::new(4, 0, 0);
+        assert_eq!(top.transform(Symmetry::Identity, 5), top);
+        assert_eq!(top.transform(Symmetry::Rotate120, 5), Coordinates::new(0, 4, 0));
+        assert_eq!(top.transform(Symmetry::Rotate240, 5), Coordinates::new(0, 0, 4));
+        assert_eq!(top.transform(Symmetry::ReflectX, 5), top);
+    }
+
+    #[test]
+    fn test_transform_reflection_swaps_sides() {
+        let coords = Coordinates::new(0, 1, 3);
+        let reflected = coords.transform(Symmetry::ReflectZ, 5);
+        assert_eq!(reflected, Coordinates::new(1, 0, 3));
+        assert!(coords.touches_side_a());
+        assert!(reflected.touches_side_b());
+    }
+
     // Property-based tests using proptest
 
     proptest! {
+        /// Property: A symmetry maps cells to cells and its inverse maps them back.
+        #[test]
+        fn prop_transform_roundtrip(board_size in 1u32..=20, idx_factor in 0.0f64..1.0, sym in 0usize..6) {
+            let total_cells = (board_size * (board_size + 1)) / 2;
+            let idx = ((idx_factor * total_cells as f64) as u32).min(total_cells - 1);
+            let symmetry = Symmetry::ALL[sym];
+            let coords = Coordinates::from_index(idx, board_size);
+            let moved = coords.transform(symmetry, board_size);
+            prop_assert_eq!(moved.x() + moved.y() + moved.z(), board_size - 1);
+            prop_assert_eq!(moved.transform(symmetry.inverse(), board_size), coords);
+        }
+
         /// Property: Converting an index to coordinates and back yields the same index.
         #[test]
         fn prop_index_to_coords_roundtrip(board_size in 1u32..=20, idx_factor in 0.0f64..1.0) {
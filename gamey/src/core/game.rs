@@ -1,9 +1,15 @@
-use crate::core::SetIdx;
-use crate::core::player_set::PlayerSet;
-use crate::{Coordinates, GameAction, GameYError, Movement, PlayerId, RenderOptions, YEN};
-use std::collections::HashMap;
+use crate::core::bitboard::Bitboard;
+use crate::core::geometry::BoardGeometry;
+use crate::core::player_set::{PlayerSet, SIDE_A, SIDE_B, SIDE_C, SideSets};
+use crate::core::zobrist;
+use crate::{
+    Coordinates, GameAction, GameRules, GameYError, Movement, PlayerId, RenderOptions, SwapMode,
+    Symmetry, BLOCKED_SYMBOL, PLAYER_SYMBOLS, YEN,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Write;
 use std::path::Path;
+use std::sync::Arc;
 
 /// A Result type alias for game operations that may fail with a `GameYError`.
 pub type Result<T> = std::result::Result<T, crate::GameYError>;
@@ -18,18 +24,74 @@ pub struct GameY {
     // Size of the board (length of one side of the triangular board).
     board_size: u32,
 
-    // Mapping from coordinates to identifiers of players who placed stones there.
-    board_map: HashMap<Coordinates, (SetIdx, PlayerId)>,
+    // Cell coordinates and neighbour tables, shared by every game of this size.
+    geometry: Arc<BoardGeometry>,
+
+    // Stones of each player, indexed by player id and then by cell index.
+    stones: Vec<Bitboard>,
+
+    // Zobrist hash of the stones on the board, updated with every stone placed or removed.
+    stones_hash: u64,
 
     status: GameStatus,
 
     // History of moves made in the game.
     history: Vec<Movement>,
 
-    // Union-Find data structure to track connected components for each player
-    sets: Vec<PlayerSet>,
+    // Union-Find data structure to track connected components for each player,
+    // indexed by cell. Empty cells hold a singleton set.
+    sets: SideSets,
+
+    // Cells that are not part of the board.
+    blocked: Bitboard,
 
+    // Singleton set of every cell, recording the sides it touches once the
+    // blocked cells are taken into account.
+    cell_sets: Arc<Vec<PlayerSet>>,
+
+    // Indices of the empty cells, kept sorted.
     available_cells: Vec<u32>,
+
+    // Information needed to revert each move in `history`, kept in the same order.
+    undo_log: Vec<UndoRecord>,
+
+    // Moves that were undone and can be replayed with `redo_move`, most recent last.
+    redo_stack: Vec<Movement>,
+
+    // Rules the game is played by.
+    rules: GameRules,
+
+    // Whether the swap rule has already been used in this game.
+    swapped: bool,
+
+    // Players who resigned or lost a misère game, in order. They are skipped in the turn rotation.
+    eliminated: Vec<PlayerId>,
+}
+
+/// State overwritten by a single move, used to revert it exactly.
+#[derive(Debug, Clone)]
+struct UndoRecord {
+    // Game status before the move was applied.
+    status: GameStatus,
+    // Position in `available_cells` of the cell taken by a placement.
+    available_pos: Option<usize>,
+    // Length of the union-find trail before the move was applied.
+    trail_start: usize,
+    // Opening stone moved by a swap.
+    swapped_stone: Option<SwappedStone>,
+    // Whether the move added its player to `eliminated`.
+    eliminated: bool,
+}
+
+/// The opening stone taken over by a swap.
+#[derive(Debug, Clone)]
+struct SwappedStone {
+    // Cell index of the stone before the swap.
+    from: usize,
+    // Cell index of the stone after the swap (same cell unless mirrored).
+    to: usize,
+    // The player who placed the stone originally.
+    owner: PlayerId,
 }
 
 /// Represents the state of a single cell on the board.
@@ -42,18 +104,256 @@ pub enum Cell {
 }
 
 impl GameY {
-    /// Creates a new game with the specified board size and number of players.
+    /// Creates a new game with the specified board size and the default rules.
+    ///
+    /// The board size limit of the rules is not checked; use
+    /// [`GameY::with_rules`] to enforce it.
     pub fn new(board_size: u32) -> Self {
-        let total_cells = (board_size * (board_size + 1)) / 2;
+        Self::new_unchecked(board_size, GameRules::default())
+    }
+
+    /// Creates a new game with the specified board size played by `rules`.
+    ///
+    /// Returns an error if the board is larger than the rules allow, if the
+    /// number of players is not between 2 and [`GameRules::MAX_PLAYERS`], or if
+    /// the starting player is not one of them.
+    pub fn with_rules(board_size: u32, rules: GameRules) -> Result<Self> {
+        if board_size > rules.max_board_size {
+            return Err(GameYError::BoardTooLarge {
+                size: board_size,
+                max: rules.max_board_size,
+            });
+        }
+        if !(2..=GameRules::MAX_PLAYERS).contains(&rules.num_players) {
+            return Err(GameYError::InvalidNumPlayers {
+                num_players: rules.num_players,
+                expected: rules.num_players.clamp(2, GameRules::MAX_PLAYERS),
+            });
+        }
+        if rules.starting_player.id() >= rules.num_players {
+            return Err(GameYError::InvalidPlayer {
+                player: rules.starting_player,
+                num_players: rules.num_players,
+            });
+        }
+        Ok(Self::new_unchecked(board_size, rules))
+    }
+
+    fn new_unchecked(board_size: u32, rules: GameRules) -> Self {
+        let geometry = BoardGeometry::shared(board_size);
+        let total_cells = geometry.total_cells();
+        let blocked = Bitboard::new(total_cells);
+        let cell_sets = Arc::new(cell_sets(&geometry, &blocked));
         Self {
             board_size,
-            board_map: HashMap::new(),
+            stones: Vec::new(),
+            stones_hash: 0,
             history: Vec::new(),
-            sets: Vec::new(),
+            sets: SideSets::new(cell_sets.to_vec()),
+            blocked,
+            cell_sets,
             status: GameStatus::Ongoing {
-                next_player: PlayerId::new(0),
+                next_player: rules.starting_player,
             },
-            available_cells: (0..total_cells).collect(),
+            available_cells: (0..total_cells as u32).collect(),
+            undo_log: Vec::new(),
+            geometry,
+            redo_stack: Vec::new(),
+            rules,
+            swapped: false,
+            eliminated: Vec::new(),
+        }
+    }
+
+    /// Sets how the opening stone is handled by the swap rule and returns the game.
+    pub fn with_swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.rules.swap_mode = swap_mode;
+        self
+    }
+
+    /// Returns how the opening stone is handled by the swap rule.
+    pub fn swap_mode(&self) -> SwapMode {
+        self.rules.swap_mode
+    }
+
+    /// Returns the rules the game is played by.
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// Returns the number of players in the game.
+    pub fn num_players(&self) -> u32 {
+        self.rules.num_players
+    }
+
+    /// Returns the players still in the game other than `player`, in turn
+    /// order starting with the one who moves after `player`.
+    pub fn opponents(&self, player: PlayerId) -> Vec<PlayerId> {
+        let n = self.rules.num_players;
+        (1..n)
+            .map(|offset| PlayerId::new((player.id() + offset) % n))
+            .filter(|other| !self.eliminated.contains(other))
+            .collect()
+    }
+
+    /// Returns the player who moves after `player`, skipping eliminated players.
+    fn next_in_turn(&self, player: PlayerId) -> PlayerId {
+        self.opponents(player)
+            .first()
+            .copied()
+            .unwrap_or_else(|| following(player, self.rules.num_players))
+    }
+
+    /// Removes `cells` from the board and returns the game.
+    ///
+    /// Blocked cells cannot be played and are stored in YEN with
+    /// [`BLOCKED_SYMBOL`]. A blocked region that reaches the edge of the board
+    /// becomes part of it: the cells around it touch every side the region
+    /// touches. Holes inside the board do not touch any side.
+    ///
+    /// Returns an error if a cell is not on the board or if a move has
+    /// already been played.
+    pub fn with_blocked_cells<I>(mut self, cells: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Coordinates>,
+    {
+        if !self.history.is_empty() {
+            return Err(GameYError::BoardNotEmpty);
+        }
+        for coords in cells {
+            let idx = self.cell_index(&coords)?;
+            self.blocked.insert(idx);
+            if let Ok(pos) = self.available_cells.binary_search(&(idx as u32)) {
+                self.available_cells.remove(pos);
+            }
+        }
+        self.cell_sets = Arc::new(cell_sets(&self.geometry, &self.blocked));
+        self.sets = SideSets::new(self.cell_sets.to_vec());
+        self.redo_stack.clear();
+        Ok(self)
+    }
+
+    /// Blocks the cells within `depth` rows of each corner and returns the game.
+    ///
+    /// See [`GameY::with_blocked_cells`].
+    pub fn with_truncated_corners(self, depth: u32) -> Result<Self> {
+        let min_distance = self.board_size.saturating_sub(depth);
+        let corners: Vec<Coordinates> = (0..self.geometry.total_cells())
+            .map(|idx| self.geometry.coords(idx))
+            .filter(|coords| coords.x().max(coords.y()).max(coords.z()) >= min_distance)
+            .collect();
+        self.with_blocked_cells(corners)
+    }
+
+    /// Gives `player` `count` handicap stones at the standard handicap
+    /// positions and returns the game.
+    ///
+    /// The positions start at the center of the board and continue towards
+    /// the corners and then the sides; blocked cells are skipped. See
+    /// [`GameY::with_handicap_stones`] for how the stones are recorded.
+    ///
+    /// Returns an error if the board has fewer than `count` standard positions.
+    pub fn with_handicap(self, player: PlayerId, count: u32) -> Result<Self> {
+        let positions: Vec<Coordinates> = standard_handicap_positions(self.board_size)
+            .into_iter()
+            .filter(|coords| !self.is_blocked(coords))
+            .collect();
+        if count as usize > positions.len() {
+            return Err(GameYError::TooManyHandicapStones {
+                count,
+                max: positions.len() as u32,
+            });
+        }
+        self.with_handicap_stones(player, positions.into_iter().take(count as usize))
+    }
+
+    /// Gives `player` a handicap stone on each of `cells` and returns the game.
+    ///
+    /// The stones are recorded in the history as [`Movement::Setup`] moves:
+    /// they do not count as turns, so the player to move is unchanged, and
+    /// they cannot be undone. The swap rule cannot be used in a game with
+    /// handicap stones.
+    ///
+    /// Returns an error if a regular move has already been played or if a
+    /// cell cannot take a stone.
+    pub fn with_handicap_stones<I>(mut self, player: PlayerId, cells: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Coordinates>,
+    {
+        for coords in cells {
+            self.add_move(Movement::Setup { player, coords })?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the handicap stones of the game, in the order they were placed.
+    pub fn handicap_stones(&self) -> Vec<Coordinates> {
+        self.history
+            .iter()
+            .filter_map(|movement| match movement {
+                Movement::Setup { coords, .. } => Some(*coords),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns true if `coords` is a blocked cell.
+    pub fn is_blocked(&self, coords: &Coordinates) -> bool {
+        self.geometry
+            .index_of(coords)
+            .is_some_and(|idx| self.blocked.contains(idx))
+    }
+
+    /// Returns the blocked cells, sorted by cell index.
+    pub fn blocked_cells(&self) -> Vec<Coordinates> {
+        self.blocked
+            .iter()
+            .map(|idx| self.geometry.coords(idx))
+            .collect()
+    }
+
+    /// Returns true if the playable cell `coords` touches side A, taking
+    /// blocked cells into account.
+    pub fn touches_side_a(&self, coords: &Coordinates) -> bool {
+        self.cell_set(coords).is_some_and(|set| set.touches(SIDE_A))
+    }
+
+    /// Returns true if the playable cell `coords` touches side B, taking
+    /// blocked cells into account.
+    pub fn touches_side_b(&self, coords: &Coordinates) -> bool {
+        self.cell_set(coords).is_some_and(|set| set.touches(SIDE_B))
+    }
+
+    /// Returns true if the playable cell `coords` touches side C, taking
+    /// blocked cells into account.
+    pub fn touches_side_c(&self, coords: &Coordinates) -> bool {
+        self.cell_set(coords).is_some_and(|set| set.touches(SIDE_C))
+    }
+
+    /// Returns the singleton set of a playable cell, or None for cells that
+    /// are blocked or off the board.
+    fn cell_set(&self, coords: &Coordinates) -> Option<&PlayerSet> {
+        let idx = self.geometry.index_of(coords)?;
+        if self.blocked.contains(idx) {
+            return None;
+        }
+        Some(&self.cell_sets[idx])
+    }
+
+    /// Returns true if the swap rule has been used in this game.
+    pub fn swapped(&self) -> bool {
+        self.swapped
+    }
+
+    /// Returns the opening stone that `player` would take over by using the
+    /// swap rule now, or None if the swap is not available to them.
+    pub fn swap_stone(&self, player: PlayerId) -> Option<Coordinates> {
+        if !self.rules.swap_allowed || self.swapped || self.next_player() != Some(player) {
+            return None;
+        }
+        match self.history.as_slice() {
+            [Movement::Placement { player: owner, coords }] if *owner != player => Some(*coords),
+            _ => None,
         }
     }
 
@@ -70,25 +370,27 @@ impl GameY {
         }
     }
 
-    /// Returns the list of available cell indices where pieces can be placed.
+    /// Returns the list of available cell indices where pieces can be placed,
+    /// in increasing order.
     pub fn available_cells(&self) -> &Vec<u32> {
         &self.available_cells
     }
 
-    /// Returns the total number of cells on the board.
+    /// Returns the total number of cells on the board, blocked cells included.
     pub fn total_cells(&self) -> u32 {
         (self.board_size * (self.board_size + 1)) / 2
     }
 
     /// Checks if the movement is made by the correct player.
     ///
-    /// Returns an error if it's not the specified player's turn.
+    /// Returns an error if it's not the specified player's turn. Setup moves
+    /// are not turns and are always accepted.
     pub fn check_player_turn(&self, movement: &Movement) -> Result<()> {
+        if let Movement::Setup { .. } = movement {
+            return Ok(());
+        }
         if let GameStatus::Ongoing { next_player } = self.status {
-            let player = match movement {
-                Movement::Placement { player, .. } => *player,
-                Movement::Action { player, .. } => *player,
-            };
+            let player = movement.player();
             if player != next_player {
                 return Err(GameYError::InvalidPlayerTurn {
                     expected: next_player,
@@ -108,18 +410,259 @@ impl GameY {
         }
     }
 
+    /// Returns the player whose stone is at `coord`, if any.
     pub fn player_at(&self, coord: &Coordinates) -> Option<PlayerId> {
-        self.board_map.get(coord).map(|(_, p)| *p)
+        self.geometry
+            .index_of(coord)
+            .and_then(|idx| self.owner(idx))
     }
-    
+
     /// Returns the current board state as a vector of (Coordinates, player_id).
     pub fn board_state(&self) -> Vec<(Coordinates, u32)> {
-        self.board_map
+        self.stones
             .iter()
-            .map(|(coords, (_, player_id))| (*coords, player_id.id()))
+            .enumerate()
+            .flat_map(|(player, stones)| {
+                stones
+                    .iter()
+                    .map(move |idx| (self.geometry.coords(idx), player as u32))
+            })
             .collect()
     }
 
+    /// Returns a 64-bit Zobrist hash identifying the current position.
+    ///
+    /// The hash covers the stones on the board and the player to move, so two
+    /// games with the same stones but a different next player hash differently.
+    /// It is maintained incrementally by [`GameY::add_move`] and
+    /// [`GameY::undo_move`]. Finished games hash by their stones only.
+    pub fn position_hash(&self) -> u64 {
+        match self.status {
+            GameStatus::Ongoing { next_player } => {
+                self.stones_hash ^ zobrist::turn_key(next_player)
+            }
+            GameStatus::Finished { .. } => self.stones_hash,
+        }
+    }
+
+    /// Returns the lexicographically smallest YEN layout among the positions
+    /// equivalent to this one under the board symmetries, together with the
+    /// symmetry that produces it.
+    ///
+    /// Equivalent positions always share the same canonical form, so it can be
+    /// used as a key to fold them together. When several symmetries give the
+    /// same layout, the first one in [`Symmetry::ALL`] is returned.
+    pub fn canonical_form(&self) -> (YEN, Symmetry) {
+        let (layout, symmetry) = Symmetry::ALL
+            .into_iter()
+            .map(|symmetry| (self.layout(symmetry), symmetry))
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .expect("there is always at least one symmetry");
+        (self.yen_with_layout(layout, symmetry), symmetry)
+    }
+
+    /// Returns the YEN layout of the board after applying `symmetry` to it.
+    fn layout(&self, symmetry: Symmetry) -> String {
+        let inverse = symmetry.inverse();
+        let mut layout = String::new();
+        for idx in 0..self.geometry.total_cells() {
+            let coords = self.geometry.coords(idx);
+            let source = self
+                .geometry
+                .index_of(&coords.transform(inverse, self.board_size));
+            layout.push(match source {
+                Some(source) if self.blocked.contains(source) => BLOCKED_SYMBOL,
+                Some(source) => match self.owner(source) {
+                    Some(player) => PLAYER_SYMBOLS[player.id() as usize],
+                    None => '.',
+                },
+                None => '.',
+            });
+            if coords.z() == 0 && coords.x() > 0 {
+                layout.push('/');
+            }
+        }
+        layout
+    }
+
+    /// Builds the YEN of this game with the given layout.
+    fn yen_with_layout(&self, layout: String, symmetry: Symmetry) -> YEN {
+        let turn = match self.status {
+            GameStatus::Finished { winner } => following(winner, self.rules.num_players).id(),
+            GameStatus::Ongoing { next_player } => next_player.id(),
+        };
+        let players = PLAYER_SYMBOLS[..self.rules.num_players as usize].to_vec();
+        YEN::new(self.board_size, turn, players, layout)
+            .with_swapped(self.swapped)
+            .with_rules(self.rules)
+            .with_handicap(
+                self.handicap_stones()
+                    .iter()
+                    .map(|coords| coords.transform(symmetry, self.board_size))
+                    .collect(),
+            )
+            .with_eliminated(self.eliminated.iter().map(|player| player.id()).collect())
+    }
+
+    /// Returns the player whose stone is on the cell `idx`, if any.
+    fn owner(&self, idx: usize) -> Option<PlayerId> {
+        self.stones
+            .iter()
+            .position(|stones| stones.contains(idx))
+            .map(|player| PlayerId::new(player as u32))
+    }
+
+    /// Returns the stones of `player`, creating their bitboard if needed.
+    fn stones_mut(&mut self, player: PlayerId) -> &mut Bitboard {
+        let player = player.id() as usize;
+        if self.stones.len() <= player {
+            let total_cells = self.geometry.total_cells();
+            self.stones.resize(player + 1, Bitboard::new(total_cells));
+        }
+        &mut self.stones[player]
+    }
+
+    /// Puts a stone of `player` on the cell `idx`.
+    fn place_stone(&mut self, player: PlayerId, idx: usize) {
+        self.stones_mut(player).insert(idx);
+        self.stones_hash ^= zobrist::stone_key(player, idx);
+    }
+
+    /// Takes the stone of `player` off the cell `idx`.
+    fn remove_stone(&mut self, player: PlayerId, idx: usize) {
+        self.stones_mut(player).remove(idx);
+        self.stones_hash ^= zobrist::stone_key(player, idx);
+    }
+
+    /// Returns the cell index of `coords`, or an error if they are not on the board.
+    fn cell_index(&self, coords: &Coordinates) -> Result<usize> {
+        coords.validate(self.board_size)?;
+        Ok(self
+            .geometry
+            .index_of(coords)
+            .expect("validated coordinates are on the board"))
+    }
+
+    /// Returns true if a stone of `player` placed at `coords` would connect
+    /// the three sides of the board.
+    ///
+    /// Returns false for cells that are off the board, blocked or already
+    /// taken. Under misère rules such a placement loses the game for `player`.
+    pub fn connects_all_sides(&self, player: PlayerId, coords: &Coordinates) -> bool {
+        let Some(idx) = self.geometry.index_of(coords) else {
+            return false;
+        };
+        if self.owner(idx).is_some() || self.blocked.contains(idx) {
+            return false;
+        }
+        let Some(stones) = self.stones.get(player.id() as usize) else {
+            return false;
+        };
+        let mut set = self.cell_sets[idx].clone();
+        for &neighbor_idx in self.geometry.neighbors(idx) {
+            let neighbor_idx = neighbor_idx as usize;
+            if stones.contains(neighbor_idx) {
+                set.merge_sides(&self.sets[self.sets.find_root(neighbor_idx)]);
+            }
+        }
+        set.is_winning_configuration()
+    }
+
+    /// Returns the cells of the connected group that won the game, sorted by
+    /// cell index.
+    ///
+    /// Returns `None` while the game is ongoing, when it was won by
+    /// resignation rather than by connecting the three sides, or when it was
+    /// played under misère rules, where connecting the three sides loses.
+    pub fn winning_group(&self) -> Option<Vec<Coordinates>> {
+        let GameStatus::Finished { winner } = self.status else {
+            return None;
+        };
+        let stones = self.stones.get(winner.id() as usize)?;
+        let root = stones
+            .iter()
+            .map(|idx| self.sets.find_root(idx))
+            .find(|root| self.sets[*root].is_winning_configuration())?;
+
+        let group = stones
+            .iter()
+            .filter(|idx| self.sets.find_root(*idx) == root)
+            .map(|idx| self.geometry.coords(idx))
+            .collect();
+        Some(group)
+    }
+
+    /// Returns a smallest subset of the winning group that is still connected
+    /// and touches all three sides, sorted by cell index.
+    ///
+    /// With three sides to connect, a minimal connection is a "Y": a center
+    /// cell joined to each side by a shortest path. The center is chosen to
+    /// minimise the total length of the three paths.
+    pub fn minimal_winning_path(&self) -> Option<Vec<Coordinates>> {
+        let group: HashSet<Coordinates> = self.winning_group()?.into_iter().collect();
+        let sides: [fn(&PlayerSet) -> bool; 3] = [
+            |set| set.touches(SIDE_A),
+            |set| set.touches(SIDE_B),
+            |set| set.touches(SIDE_C),
+        ];
+        let trees: Vec<HashMap<Coordinates, (u32, Option<Coordinates>)>> = sides
+            .iter()
+            .map(|touches| self.shortest_paths_to_side(&group, *touches))
+            .collect();
+
+        let center = group
+            .iter()
+            .filter(|cell| trees.iter().all(|tree| tree.contains_key(cell)))
+            .min_by_key(|cell| {
+                let length: u32 = trees.iter().map(|tree| tree[cell].0).sum();
+                (length, self.geometry.index_of(cell))
+            })?;
+
+        let mut path: HashSet<Coordinates> = HashSet::new();
+        for tree in &trees {
+            let mut current = Some(*center);
+            while let Some(cell) = current {
+                path.insert(cell);
+                current = tree[&cell].1;
+            }
+        }
+        let mut path: Vec<Coordinates> = path.into_iter().collect();
+        path.sort_by_key(|coords| self.geometry.index_of(coords));
+        Some(path)
+    }
+
+    /// Breadth-first search inside `group` starting from every cell that
+    /// touches a side. Maps each reached cell to its distance, counted in
+    /// cells, and to the next cell on a shortest path towards the side.
+    fn shortest_paths_to_side(
+        &self,
+        group: &HashSet<Coordinates>,
+        touches_side: fn(&PlayerSet) -> bool,
+    ) -> HashMap<Coordinates, (u32, Option<Coordinates>)> {
+        let mut tree = HashMap::new();
+        let mut queue = VecDeque::new();
+        let mut sources: Vec<Coordinates> = group
+            .iter()
+            .filter(|cell| self.cell_set(cell).is_some_and(touches_side))
+            .copied()
+            .collect();
+        sources.sort_by_key(|coords| self.geometry.index_of(coords));
+        for source in sources {
+            tree.insert(source, (1, None));
+            queue.push_back(source);
+        }
+        while let Some(cell) = queue.pop_front() {
+            let distance = tree[&cell].0;
+            for neighbor in self.get_neighbors(&cell) {
+                if group.contains(&neighbor) && !tree.contains_key(&neighbor) {
+                    tree.insert(neighbor, (distance + 1, Some(cell)));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        tree
+    }
+
     /// Loads a game state from a YEN format file.
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let filename = path.as_ref().display().to_string();
@@ -145,123 +688,317 @@ impl GameY {
         Ok(())
     }
 
+    /// Returns the moves played so far, in order.
+    pub fn history(&self) -> &[Movement] {
+        &self.history
+    }
+
     /// Adds a move to the game.
+    ///
+    /// Playing a new move discards any moves that were undone and not redone.
     pub fn add_move(&mut self, movement: Movement) -> Result<()> {
+        self.apply_move(movement)?;
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    /// Takes back the last move, restoring the board, the union-find sets and
+    /// the game status to exactly what they were before it was played.
+    ///
+    /// Returns the undone move, which can be replayed with [`GameY::redo_move`].
+    pub fn undo_move(&mut self) -> Result<Movement> {
+        // Setup stones belong to the starting position.
+        if let Some(Movement::Setup { .. }) = self.history.last() {
+            return Err(GameYError::NothingToUndo);
+        }
+        let (Some(movement), Some(record)) = (self.history.pop(), self.undo_log.pop()) else {
+            return Err(GameYError::NothingToUndo);
+        };
+
+        self.sets.rollback(record.trail_start);
+        if record.eliminated {
+            self.eliminated.pop();
+        }
+        if let Some(stone) = record.swapped_stone {
+            self.revert_swap(movement.player(), stone);
+        } else if let Movement::Placement { player, coords } = &movement
+            && let Some(idx) = self.geometry.index_of(coords)
+        {
+            self.remove_stone(*player, idx);
+            if let Some(pos) = record.available_pos {
+                self.available_cells.insert(pos, idx as u32);
+            }
+        }
+        self.status = record.status;
+        self.redo_stack.push(movement.clone());
+        Ok(movement)
+    }
+
+    /// Replays the last move taken back with [`GameY::undo_move`].
+    ///
+    /// Returns the replayed move.
+    pub fn redo_move(&mut self) -> Result<Movement> {
+        let movement = self.redo_stack.pop().ok_or(GameYError::NothingToRedo)?;
+        self.apply_move(movement.clone())?;
+        Ok(movement)
+    }
+
+    /// Applies a move and records what is needed to undo it.
+    fn apply_move(&mut self, movement: Movement) -> Result<()> {
+        if movement.player().id() >= self.rules.num_players {
+            return Err(GameYError::InvalidPlayer {
+                player: movement.player(),
+                num_players: self.rules.num_players,
+            });
+        }
+        if self.rules.strict_game_over && self.check_game_over() {
+            return Err(GameYError::GameOver { movement });
+        }
+        let mut record = UndoRecord {
+            status: self.status.clone(),
+            available_pos: None,
+            trail_start: self.sets.trail_len(),
+            swapped_stone: None,
+            eliminated: false,
+        };
         match &movement {
             Movement::Placement { player, coords } => {
-                self.handle_placement(*player, *coords)?;
+                self.handle_placement(*player, *coords, &mut record)?;
             }
             Movement::Action { player, action } => {
-                self.handle_action(*player, action);
+                self.handle_action(*player, action, &mut record)?;
+            }
+            Movement::Setup { player, coords } => {
+                self.handle_setup(*player, *coords, &mut record)?;
             }
         }
         self.history.push(movement);
+        self.undo_log.push(record);
         Ok(())
     }
 
     /// Orchestrates the placement logic
-    fn handle_placement(&mut self, player: PlayerId, coords: Coordinates) -> Result<()> {
-        self.validate_placement(player, coords)?;
+    fn handle_placement(
+        &mut self,
+        player: PlayerId,
+        coords: Coordinates,
+        record: &mut UndoRecord,
+    ) -> Result<()> {
+        let cell_idx = self.validate_placement(player, coords)?;
 
-        // Update board state (available cells, sets, board_map)
-        let set_idx = self.register_piece(player, coords);
+        // Update board state (available cells and stones)
+        self.register_piece(player, cell_idx, record);
 
-        // Connect neighbors and determine if this move won the game
-        let won = self.connect_neighbors_and_check_win(coords, player, set_idx);
+        // Connect neighbors and determine if this move connected the three sides
+        let connected = self.connect_neighbors_and_check_win(player, cell_idx);
 
-        self.update_status_after_placement(player, won);
+        self.update_status_after_placement(player, connected, record);
         Ok(())
     }
 
-    /// Iterates over neighbors to union sets and checks for a win condition
-    fn connect_neighbors_and_check_win(
+    /// Places a setup stone. Only allowed before the first regular move; the
+    /// player to move stays the same unless the stone ends the game.
+    fn handle_setup(
         &mut self,
-        coords: Coordinates,
         player: PlayerId,
-        current_set_idx: usize,
-    ) -> bool {
-        // Base win condition: The piece itself touches all required sides
-        let mut won = self.sets[current_set_idx].is_winning_configuration();
-
-        //
-        let neighbors = self.get_neighbors(&coords);
-
-        for neighbor in neighbors {
-            if let Some((neighbor_idx, neighbor_player)) = self.board_map.get(&neighbor)
-                && *neighbor_player == player
-            {
-                // Union returns true if the merge resulted in a winning connection
-                //
-                let connection_won = self.union(current_set_idx, *neighbor_idx);
-                won = won || connection_won;
-            }
+        coords: Coordinates,
+        record: &mut UndoRecord,
+    ) -> Result<()> {
+        let started = self
+            .history
+            .iter()
+            .any(|movement| !matches!(movement, Movement::Setup { .. }));
+        if started {
+            return Err(GameYError::BoardNotEmpty);
+        }
+        let cell_idx = self.validate_placement(player, coords)?;
+        self.register_piece(player, cell_idx, record);
+        if self.connect_neighbors_and_check_win(player, cell_idx) {
+            self.update_status_after_placement(player, true, record);
         }
-        won
+        Ok(())
+    }
+
+    /// Iterates over neighbors to union sets and checks for a win condition
+    fn connect_neighbors_and_check_win(&mut self, player: PlayerId, cell_idx: usize) -> bool {
+        let stones = &self.stones[player.id() as usize];
+        let friends = self
+            .geometry
+            .neighbors(cell_idx)
+            .iter()
+            .map(|&neighbor_idx| neighbor_idx as usize)
+            .filter(|&neighbor_idx| stones.contains(neighbor_idx));
+        // Sides are only ever added, so the group wins if its final set does
+        let root = self.sets.join(cell_idx, friends);
+        self.sets[root].is_winning_configuration()
     }
 
     /// Updates the game status (Finished vs Ongoing)
-    fn update_status_after_placement(&mut self, player: PlayerId, won: bool) {
+    ///
+    /// Under misère rules, the player who connected the three sides is taken
+    /// out of the game instead of winning it.
+    fn update_status_after_placement(
+        &mut self,
+        player: PlayerId,
+        connected: bool,
+        record: &mut UndoRecord,
+    ) {
         if self.check_game_over() {
             tracing::info!("Game was already over. Move ignored for status update.");
-        } else if won {
+        } else if connected && self.rules.misere {
+            tracing::debug!("Player {} connects the three sides and loses!", player);
+            self.eliminate(player, record);
+        } else if connected {
             tracing::debug!("Player {} wins the game!", player);
             self.status = GameStatus::Finished { winner: player };
         } else {
             // tracing::debug!("No win yet..."); // Optional debug
             self.status = GameStatus::Ongoing {
-                next_player: other_player(player),
+                next_player: self.next_in_turn(player),
             };
         }
     }
 
     /// Handles non-placement actions (Resign, Swap, etc.)
-    fn handle_action(&mut self, player: PlayerId, action: &GameAction) {
+    fn handle_action(
+        &mut self,
+        player: PlayerId,
+        action: &GameAction,
+        record: &mut UndoRecord,
+    ) -> Result<()> {
         match action {
             GameAction::Resign => {
-                self.status = GameStatus::Finished {
-                    winner: other_player(player),
-                };
+                self.eliminate(player, record);
             }
             GameAction::Swap => {
+                self.handle_swap(player, record)?;
                 self.status = GameStatus::Ongoing {
-                    next_player: other_player(player),
+                    next_player: self.next_in_turn(player),
                 };
             }
         }
+        Ok(())
+    }
+
+    /// Takes `player` out of the game, either because they resigned or because
+    /// they lost a misère game. When a single player is left they win;
+    /// otherwise the game goes on without the eliminated player.
+    fn eliminate(&mut self, player: PlayerId, record: &mut UndoRecord) {
+        if !self.eliminated.contains(&player) {
+            self.eliminated.push(player);
+            record.eliminated = true;
+        }
+        let remaining = self.opponents(player);
+        if remaining.len() <= 1 {
+            let winner = remaining
+                .first()
+                .copied()
+                .unwrap_or_else(|| following(player, self.rules.num_players));
+            self.status = GameStatus::Finished { winner };
+        } else if self.next_player() == Some(player) {
+            self.status = GameStatus::Ongoing {
+                next_player: self.next_in_turn(player),
+            };
+        }
+    }
+
+    /// Applies the swap rule: `player` takes over the opening stone, which is
+    /// mirrored first when the game uses [`SwapMode::Mirror`].
+    ///
+    /// The swap is only legal as the second player's first move, that is, when
+    /// the only move so far is a placement by the other player.
+    fn handle_swap(&mut self, player: PlayerId, record: &mut UndoRecord) -> Result<()> {
+        if !self.rules.swap_allowed {
+            return Err(GameYError::SwapNotAllowed { player });
+        }
+        let (owner, from) = match self.history.as_slice() {
+            [Movement::Placement { player: owner, coords }]
+                if *owner != player && !self.swapped && !self.check_game_over() =>
+            {
+                (*owner, *coords)
+            }
+            _ => return Err(GameYError::IllegalSwap { player }),
+        };
+        let from = self.cell_index(&from)?;
+        let to = match self.rules.swap_mode {
+            SwapMode::Transfer => from,
+            SwapMode::Mirror => {
+                let coords = self.geometry.coords(from);
+                let mirrored =
+                    self.cell_index(&Coordinates::new(coords.x(), coords.z(), coords.y()))?;
+                // On asymmetric boards the mirrored cell may be blocked; the
+                // stone then stays where it is.
+                if self.blocked.contains(mirrored) {
+                    from
+                } else {
+                    mirrored
+                }
+            }
+        };
+
+        // The opening stone is alone on the board, so its union-find entry is
+        // a singleton both before and after the swap.
+        self.remove_stone(owner, from);
+        self.place_stone(player, to);
+        self.move_available_cell(to, from);
+
+        self.swapped = true;
+        record.swapped_stone = Some(SwappedStone { from, to, owner });
+        Ok(())
+    }
+
+    /// Takes the opening stone from `player`, who swapped it, and gives it back
+    /// to its owner at its original position.
+    fn revert_swap(&mut self, player: PlayerId, stone: SwappedStone) {
+        self.remove_stone(player, stone.to);
+        self.place_stone(stone.owner, stone.from);
+        self.move_available_cell(stone.from, stone.to);
+        self.swapped = false;
+    }
+
+    /// Marks the cell `taken` as occupied and `freed` as available, keeping
+    /// `available_cells` sorted.
+    fn move_available_cell(&mut self, taken: usize, freed: usize) {
+        if taken == freed {
+            return;
+        }
+        if let Ok(pos) = self.available_cells.binary_search(&(taken as u32)) {
+            self.available_cells.remove(pos);
+        }
+        if let Err(pos) = self.available_cells.binary_search(&(freed as u32)) {
+            self.available_cells.insert(pos, freed as u32);
+        }
     }
 
-    /// Handles validation logic (Game Over checks and Occupancy)
-    fn validate_placement(&self, player: PlayerId, coords: Coordinates) -> Result<()> {
+    /// Handles validation logic (Game Over checks, board bounds and Occupancy)
+    /// Returns the index of the cell to play.
+    fn validate_placement(&self, player: PlayerId, coords: Coordinates) -> Result<usize> {
         if self.check_game_over() {
             tracing::info!("Game is already over. Move at {} could be ignored", coords);
         }
 
-        if self.board_map.contains_key(&coords) {
+        let cell_idx = self.cell_index(&coords)?;
+        if self.blocked.contains(cell_idx) {
+            return Err(GameYError::BlockedCell {
+                coordinates: coords,
+            });
+        }
+        if self.owner(cell_idx).is_some() {
             return Err(GameYError::Occupied {
                 coordinates: coords,
                 player,
             });
         }
-        Ok(())
+        Ok(cell_idx)
     }
 
-    /// Updates internal data structures (Available cells, Sets, Map)
-    /// Returns the index of the newly created set.
-    fn register_piece(&mut self, player: PlayerId, coords: Coordinates) -> usize {
-        let cell_idx = coords.to_index(self.board_size);
-        self.available_cells.retain(|&x| x != cell_idx);
-
-        let set_idx = self.sets.len();
-        let new_set = PlayerSet {
-            parent: set_idx,
-            touches_side_a: coords.touches_side_a(),
-            touches_side_b: coords.touches_side_b(),
-            touches_side_c: coords.touches_side_c(),
-        };
-        self.sets.push(new_set);
-        self.board_map.insert(coords, (set_idx, player));
-
-        set_idx
+    /// Updates internal data structures (Available cells and Stones)
+    fn register_piece(&mut self, player: PlayerId, cell_idx: usize, record: &mut UndoRecord) {
+        if let Ok(pos) = self.available_cells.binary_search(&(cell_idx as u32)) {
+            self.available_cells.remove(pos);
+            record.available_pos = Some(pos);
+        }
+        self.place_stone(player, cell_idx);
     }
 
     /// Returns the size of the board (length of one side of the triangle).
@@ -271,24 +1008,14 @@ impl GameY {
 
     /// Returns the neighboring coordinates for a given cell.
     fn get_neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
-        let mut neighbors = Vec::new();
-        let x = coords.x();
-        let y = coords.y();
-        let z = coords.z();
-
-        if x > 0 {
-            neighbors.push(Coordinates::new(x - 1, y + 1, z));
-            neighbors.push(Coordinates::new(x - 1, y, z + 1));
-        }
-        if y > 0 {
-            neighbors.push(Coordinates::new(x + 1, y - 1, z));
-            neighbors.push(Coordinates::new(x, y - 1, z + 1));
-        }
-        if z > 0 {
-            neighbors.push(Coordinates::new(x + 1, y, z - 1));
-            neighbors.push(Coordinates::new(x, y + 1, z - 1));
-        }
-        neighbors
+        let Some(idx) = self.geometry.index_of(coords) else {
+            return Vec::new();
+        };
+        self.geometry
+            .neighbors(idx)
+            .iter()
+            .map(|&neighbor| self.geometry.coords(neighbor as usize))
+            .collect()
     }
 
     /// Renders the current state of the board as a text string.
@@ -395,11 +1122,14 @@ impl GameY {
     }
 
     fn format_cell(&self, coords: Coordinates, options: &RenderOptions, width: usize) -> String {
-        let player = self.board_map.get(&coords).map(|(_, p)| *p);
+        let idx = self.geometry.index_of(&coords);
+        let player = idx.and_then(|idx| self.owner(idx));
+        let blocked = idx.is_some_and(|idx| self.blocked.contains(idx));
 
         // 1. Base symbol
         let mut symbol = match player {
             Some(p) => format!("{}", p),
+            None if blocked => BLOCKED_SYMBOL.to_string(),
             None => ".".to_string(),
         };
 
@@ -413,8 +1143,9 @@ impl GameY {
                 w = width
             ));
         }
-        if options.show_idx {
-            let idx = coords.to_index(self.board_size);
+        if options.show_idx
+            && let Some(idx) = idx
+        {
             symbol.push_str(&format!("({}) ", idx));
         }
 
@@ -425,34 +1156,6 @@ impl GameY {
 
         symbol
     }
-
-    /// Disjoint Set Union 'Find' with path compression
-    fn find(&mut self, i: SetIdx) -> SetIdx {
-        if self.sets[i].parent == i {
-            i
-        } else {
-            self.sets[i].parent = self.find(self.sets[i].parent);
-            self.sets[i].parent
-        }
-    }
-
-    /// Disjoint Set Union 'Union' operation
-    fn union(&mut self, i: SetIdx, j: SetIdx) -> bool {
-        let root_i = self.find(i);
-        let root_j = self.find(j);
-
-        if root_i != root_j {
-            self.sets[root_i].parent = root_j;
-            // Merge side properties
-            self.sets[root_j].touches_side_a |= self.sets[root_i].touches_side_a;
-            self.sets[root_j].touches_side_b |= self.sets[root_i].touches_side_b;
-            self.sets[root_j].touches_side_c |= self.sets[root_i].touches_side_c;
-            return self.sets[root_j].touches_side_a
-                && self.sets[root_j].touches_side_b
-                && self.sets[root_j].touches_side_c;
-        }
-        false
-    }
 }
 
 fn indent(str: &mut String, level: u32) {
@@ -463,7 +1166,20 @@ impl TryFrom<YEN> for GameY {
     type Error = GameYError;
 
     fn try_from(game: YEN) -> Result<Self> {
-        let mut ygame = GameY::new(game.size());
+        // The stones of the layout are placed without enforcing the end of the
+        // game, so that finished positions can be loaded.
+        let rules = game.rules();
+        let placement_rules = GameRules {
+            strict_game_over: false,
+            ..rules
+        };
+        let mut ygame = GameY::with_rules(game.size(), placement_rules)?;
+        if game.players().len() != rules.num_players as usize {
+            return Err(GameYError::InvalidNumPlayers {
+                num_players: game.players().len() as u32,
+                expected: rules.num_players,
+            });
+        }
         let rows: Vec<&str> = game.layout().split('/').collect();
         if rows.len() as u32 != game.size() {
             return Err(GameYError::InvalidYENLayout {
@@ -471,6 +1187,8 @@ impl TryFrom<YEN> for GameY {
                 found: rows.len() as u32,
             });
         }
+        let mut placements = Vec::new();
+        let mut blocked = Vec::new();
         for (row, row_str) in rows.iter().enumerate() {
             let cells: Vec<char> = row_str.chars().collect();
             if cells.len() as u32 != row as u32 + 1 {
@@ -485,79 +1203,188 @@ impl TryFrom<YEN> for GameY {
                 let y = col as u32;
                 let z = game.size() - 1 - x - y;
                 let coords = Coordinates::new(x, y, z);
-                match cell {
-                    'B' => {
-                        ygame.add_move(Movement::Placement {
-                            player: PlayerId::new(0),
-                            coords,
-                        })?;
-                    }
-                    'R' => {
-                        ygame.add_move(Movement::Placement {
-                            player: PlayerId::new(1),
-                            coords,
-                        })?;
-                    }
-                    '.' => {}
-                    _ => {
-                        return Err(GameYError::InvalidCharInLayout {
-                            char: *cell,
-                            row,
-                            col,
-                        });
-                    }
+                if *cell == '.' {
+                    continue;
+                }
+                if *cell == BLOCKED_SYMBOL {
+                    blocked.push(coords);
+                    continue;
+                }
+                let Some(player) = game.players().iter().position(|symbol| symbol == cell)
+                else {
+                    return Err(GameYError::InvalidCharInLayout {
+                        char: *cell,
+                        row,
+                        col,
+                    });
+                };
+                placements.push(Movement::Placement {
+                    player: PlayerId::new(player as u32),
+                    coords,
+                });
+            }
+        }
+        ygame = ygame.with_blocked_cells(blocked)?;
+        ygame.swapped = game.swapped();
+        // Handicap stones go first, as setup moves.
+        for coords in game.handicap() {
+            let Some(pos) = placements.iter().position(|movement| {
+                matches!(movement, Movement::Placement { coords: c, .. } if c == coords)
+            }) else {
+                return Err(GameYError::HandicapCellEmpty {
+                    coordinates: *coords,
+                });
+            };
+            let player = placements.remove(pos).player();
+            ygame.add_move(Movement::Setup {
+                player,
+                coords: *coords,
+            })?;
+        }
+        for movement in placements {
+            ygame.add_move(movement)?;
+        }
+        // Players who resigned or lost a misère game stay out of the turn order.
+        for &id in game.eliminated() {
+            if id >= rules.num_players {
+                return Err(GameYError::InvalidEliminatedPlayer {
+                    player: id,
+                    num_players: rules.num_players,
+                });
+            }
+            let player = PlayerId::new(id);
+            if !ygame.eliminated.contains(&player) {
+                ygame.eliminated.push(player);
+            }
+        }
+        // The stones are placed in layout order, so the player to move is taken
+        // from the YEN itself when it names a player of this game.
+        if let GameStatus::Ongoing { next_player } = ygame.status {
+            let remaining: Vec<PlayerId> = (0..rules.num_players)
+                .map(PlayerId::new)
+                .filter(|player| !ygame.eliminated.contains(player))
+                .collect();
+            if remaining.len() <= 1 {
+                ygame.status = GameStatus::Finished {
+                    winner: remaining.first().copied().unwrap_or(next_player),
+                };
+            } else if game.turn() < rules.num_players {
+                let turn = PlayerId::new(game.turn());
+                if ygame.eliminated.contains(&turn) {
+                    return Err(GameYError::EliminatedPlayerTurn { player: turn });
                 }
+                ygame.status = GameStatus::Ongoing { next_player: turn };
+            } else if ygame.eliminated.contains(&next_player) {
+                ygame.status = GameStatus::Ongoing {
+                    next_player: ygame.next_in_turn(next_player),
+                };
             }
         }
+        ygame.rules = rules;
         Ok(ygame)
     }
 }
 
 impl From<&GameY> for YEN {
     fn from(game: &GameY) -> Self {
-        let size = game.board_size;
-        let turn = match game.status {
-            GameStatus::Finished { winner } => other_player(winner).id() as u32,
-            GameStatus::Ongoing { next_player } => next_player.id(),
-        };
-        let mut layout = String::new();
-        let total_cells = (game.board_size * (game.board_size + 1)) / 2;
-        let players = vec!['B', 'R'];
-        for idx in 0..total_cells {
-            let coords = Coordinates::from_index(idx, game.board_size);
-            let cell_char = match game.board_map.get(&coords) {
-                Some((_, player)) if player.id() == 0 => 'B',
-                Some((_, player)) if player.id() == 1 => 'R',
-                _ => '.',
-            };
-            layout.push(cell_char);
-            if coords.z() == 0 && coords.x() > 0 {
-                layout.push('/');
-            }
+        game.yen_with_layout(game.layout(Symmetry::Identity), Symmetry::Identity)
+    }
+}
+
+/// Returns the standard handicap positions of a board of `board_size`, in the
+/// order they are used: the center, then a point towards each corner, then a
+/// point towards the middle of each side.
+fn standard_handicap_positions(board_size: u32) -> Vec<Coordinates> {
+    let span = board_size.saturating_sub(1);
+    let inset = span / 4;
+    let center_x = span / 3;
+    let center_y = (span - center_x) / 2;
+    // Side points sit closer to the edge than the corner points, so that they
+    // do not fall on the center.
+    let side_inset = (inset / 2).max(1).min(span);
+    let side_y = (span - side_inset) / 2;
+    let candidates = [
+        (center_x, center_y, span - center_x - center_y),
+        (span - 2 * inset, inset, inset),
+        (inset, span - 2 * inset, inset),
+        (inset, inset, span - 2 * inset),
+        (side_inset, side_y, span - side_inset - side_y),
+        (span - side_inset - side_y, side_inset, side_y),
+        (side_y, span - side_inset - side_y, side_inset),
+    ];
+    let mut positions: Vec<Coordinates> = Vec::new();
+    for (x, y, z) in candidates {
+        let coords = Coordinates::new(x, y, z);
+        if !positions.contains(&coords) {
+            positions.push(coords);
         }
-        YEN::new(size, turn, players, layout)
     }
+    positions
 }
 
-fn other_player(player: PlayerId) -> PlayerId {
-    // Assuming two players with IDs 0 and 1
-    if player.id() == 0 {
-        PlayerId::new(1)
-    } else {
-        PlayerId::new(0)
+/// Builds the singleton set of every cell of `geometry`, with the sides each
+/// cell touches once the `blocked` cells are removed.
+///
+/// A blocked region extends every side it touches, so the playable cells
+/// around it touch those sides too.
+fn cell_sets(geometry: &BoardGeometry, blocked: &Bitboard) -> Vec<PlayerSet> {
+    let total_cells = geometry.total_cells();
+    let mut sets: Vec<PlayerSet> = (0..total_cells)
+        .map(|idx| PlayerSet::singleton(idx, &geometry.coords(idx)))
+        .collect();
+    let mut seen = Bitboard::new(total_cells);
+    for start in blocked.iter() {
+        if seen.contains(start) {
+            continue;
+        }
+        seen.insert(start);
+        let mut region = vec![start];
+        let mut sides = sets[start].clone();
+        let mut next = 0;
+        while next < region.len() {
+            let cell = region[next];
+            next += 1;
+            sides.merge_sides(&sets[cell]);
+            for &neighbor in geometry.neighbors(cell) {
+                let neighbor = neighbor as usize;
+                if blocked.contains(neighbor) && !seen.contains(neighbor) {
+                    seen.insert(neighbor);
+                    region.push(neighbor);
+                }
+            }
+        }
+        for &cell in &region {
+            for &neighbor in geometry.neighbors(cell) {
+                let neighbor = neighbor as usize;
+                if !blocked.contains(neighbor) {
+                    sets[neighbor].merge_sides(&sides);
+                }
+            }
+        }
     }
+    sets
 }
 
-fn apply_player_color(symbol: String, player: Option<PlayerId>) -> String {
+/// Returns the player who follows `player` in a game of `num_players`,
+/// regardless of eliminated players.
+fn following(player: PlayerId, num_players: u32) -> PlayerId {
+    PlayerId::new((player.id() + 1) % num_players)
+}
+
+pub(crate) fn apply_player_color(symbol: String, player: Option<PlayerId>) -> String {
     match player {
         Some(p) if p.id() == 0 => format!("\x1b[34m{}\x1b[0m", symbol), // Blue
         Some(p) if p.id() == 1 => format!("\x1b[31m{}\x1b[0m", symbol), // Red
+        Some(p) if p.id() == 2 => format!("\x1b[32m{}\x1b[0m", symbol), // Green
+        Some(p) if p.id() == 3 => format!("\x1b[33m{}\x1b[0m", symbol), // Yellow
+        Some(p) if p.id() == 4 => format!("\x1b[35m{}\x1b[0m", symbol), // Purple
+        Some(p) if p.id() == 5 => format!("\x1b[91m{}\x1b[0m", symbol), // Orange
         _ => symbol,
     }
 }
 
 /// Represents the current status of a game.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameStatus {
     /// The game is still in progress with the specified player to move next.
     Ongoing { next_player: PlayerId },
@@ -565,15 +1392,32 @@ pub enum GameStatus {
     Finished { winner: PlayerId },
 }
 
+impl GameStatus {
+    /// Returns the status once `player` has placed a stone in a game without
+    /// eliminations: won by `player` if the stone `won`, otherwise with the
+    /// following player of `num_players` to move.
+    pub(crate) fn after_placement(player: PlayerId, won: bool, num_players: u32) -> Self {
+        if won {
+            GameStatus::Finished { winner: player }
+        } else {
+            GameStatus::Ongoing {
+                next_player: following(player, num_players),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
 
     #[test]
-    fn test_other_player() {
-        assert_eq!(other_player(PlayerId::new(0)), PlayerId::new(1));
-        assert_eq!(other_player(PlayerId::new(1)), PlayerId::new(0));
+    fn test_following_player() {
+        assert_eq!(following(PlayerId::new(0), 2), PlayerId::new(1));
+        assert_eq!(following(PlayerId::new(1), 2), PlayerId::new(0));
+        assert_eq!(following(PlayerId::new(1), 3), PlayerId::new(2));
+        assert_eq!(following(PlayerId::new(2), 3), PlayerId::new(0));
     }
 
     #[test]
@@ -717,6 +1561,43 @@ mod tests {
         assert_eq!(yen.layout(), yen_loaded.layout());
     }
 
+    #[test]
+    fn test_undo_restores_union_find_exactly() {
+        let mut game = GameY::new(4);
+        let setup = [
+            (0, Coordinates::new(0, 0, 3)),
+            (1, Coordinates::new(3, 0, 0)),
+            (0, Coordinates::new(0, 3, 0)),
+            (1, Coordinates::new(2, 1, 0)),
+            (0, Coordinates::new(0, 1, 2)),
+            (1, Coordinates::new(2, 0, 1)),
+        ];
+        for (player, coords) in setup {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        let sets_before = game.sets.clone();
+        let cells_before = game.available_cells.clone();
+        let status_before = game.status.clone();
+
+        // Merges both chains of player 0 and wins the game
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 2, 1),
+        })
+        .unwrap();
+        assert!(game.check_game_over());
+
+        game.undo_move().unwrap();
+        assert_eq!(game.sets, sets_before);
+        assert_eq!(game.available_cells, cells_before);
+        assert_eq!(game.status, status_before);
+        assert_eq!(game.history.len(), game.undo_log.len());
+    }
+
     // Test loading a YEN representation of a finished game
     #[test]
     fn test_load_yen_end2() {
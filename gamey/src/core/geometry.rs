@@ -0,0 +1,154 @@
+use crate::Coordinates;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Precomputed, read-only description of the cells of a triangular board.
+///
+/// Cells are addressed by their linear index (see [`Coordinates::to_index`]).
+/// The geometry of a board size is built once and shared between every game
+/// of that size, so cloning a game does not copy it.
+#[derive(Debug)]
+pub(crate) struct BoardGeometry {
+    board_size: u32,
+    // Coordinates of each cell index.
+    coords: Vec<Coordinates>,
+    // Neighbours of cell `i` are `neighbors[neighbor_start[i]..neighbor_start[i + 1]]`.
+    neighbor_start: Vec<usize>,
+    neighbors: Vec<u32>,
+}
+
+impl BoardGeometry {
+    /// Returns the shared geometry for boards of the given size.
+    pub fn shared(board_size: u32) -> Arc<BoardGeometry> {
+        static CACHE: OnceLock<Mutex<HashMap<u32, Arc<BoardGeometry>>>> = OnceLock::new();
+        let mut cache = CACHE
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        cache
+            .entry(board_size)
+            .or_insert_with(|| Arc::new(BoardGeometry::new(board_size)))
+            .clone()
+    }
+
+    /// Builds the geometry of a board of the given size.
+    fn new(board_size: u32) -> Self {
+        let total = (board_size * (board_size + 1) / 2) as usize;
+        let mut geometry = Self {
+            board_size,
+            coords: Vec::with_capacity(total),
+            neighbor_start: Vec::with_capacity(total + 1),
+            neighbors: Vec::with_capacity(total * 6),
+        };
+        for row in 0..board_size {
+            for y in 0..=row {
+                let x = board_size - 1 - row;
+                geometry.coords.push(Coordinates::new(x, y, row - y));
+            }
+        }
+        for idx in 0..total {
+            geometry.neighbor_start.push(geometry.neighbors.len());
+            for neighbor in neighbor_coords(&geometry.coords[idx]) {
+                if let Some(neighbor_idx) = geometry.index_of(&neighbor) {
+                    geometry.neighbors.push(neighbor_idx as u32);
+                }
+            }
+        }
+        geometry.neighbor_start.push(geometry.neighbors.len());
+        geometry
+    }
+
+    /// Returns the number of cells on the board.
+    pub fn total_cells(&self) -> usize {
+        self.coords.len()
+    }
+
+    /// Returns the coordinates of the cell with the given index.
+    pub fn coords(&self, idx: usize) -> Coordinates {
+        self.coords[idx]
+    }
+
+    /// Returns the indices of the cells adjacent to the cell `idx`.
+    pub fn neighbors(&self, idx: usize) -> &[u32] {
+        &self.neighbors[self.neighbor_start[idx]..self.neighbor_start[idx + 1]]
+    }
+
+    /// Returns the index of the cell at `coords`, or `None` if the coordinates
+    /// do not describe a cell of this board.
+    pub fn index_of(&self, coords: &Coordinates) -> Option<usize> {
+        let n = self.board_size;
+        if n == 0 || coords.x() >= n || coords.y() >= n || coords.z() >= n {
+            return None;
+        }
+        if coords.x() + coords.y() + coords.z() != n - 1 {
+            return None;
+        }
+        let r = (n - 1 - coords.x()) as usize;
+        Some(r * (r + 1) / 2 + coords.y() as usize)
+    }
+}
+
+/// Returns the barycentric neighbours of a cell, as long as no coordinate
+/// would become negative.
+fn neighbor_coords(coords: &Coordinates) -> Vec<Coordinates> {
+    let mut neighbors = Vec::with_capacity(6);
+    let x = coords.x();
+    let y = coords.y();
+    let z = coords.z();
+
+    if x > 0 {
+        neighbors.push(Coordinates::new(x - 1, y + 1, z));
+        neighbors.push(Coordinates::new(x - 1, y, z + 1));
+    }
+    if y > 0 {
+        neighbors.push(Coordinates::new(x + 1, y - 1, z));
+        neighbors.push(Coordinates::new(x, y - 1, z + 1));
+    }
+    if z > 0 {
+        neighbors.push(Coordinates::new(x + 1, y, z - 1));
+        neighbors.push(Coordinates::new(x, y + 1, z - 1));
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indices_match_coordinates() {
+        let geometry = BoardGeometry::new(6);
+        assert_eq!(geometry.total_cells(), 21);
+        for idx in 0..geometry.total_cells() {
+            let coords = geometry.coords(idx);
+            assert_eq!(coords, Coordinates::from_index(idx as u32, 6));
+            assert_eq!(geometry.index_of(&coords), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_index_of_rejects_cells_off_the_board() {
+        let geometry = BoardGeometry::new(4);
+        assert_eq!(geometry.index_of(&Coordinates::new(4, 0, 0)), None);
+        assert_eq!(geometry.index_of(&Coordinates::new(1, 1, 0)), None);
+        assert_eq!(geometry.index_of(&Coordinates::new(2, 2, 2)), None);
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let geometry = BoardGeometry::new(5);
+        let corner = geometry.index_of(&Coordinates::new(4, 0, 0)).unwrap();
+        let edge = geometry.index_of(&Coordinates::new(0, 2, 2)).unwrap();
+        let interior = geometry.index_of(&Coordinates::new(2, 1, 1)).unwrap();
+        assert_eq!(geometry.neighbors(corner).len(), 2);
+        assert_eq!(geometry.neighbors(edge).len(), 4);
+        assert_eq!(geometry.neighbors(interior).len(), 6);
+    }
+
+    #[test]
+    fn test_shared_geometry_is_reused() {
+        let a = BoardGeometry::shared(7);
+        let b = BoardGeometry::shared(7);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+}
@@ -0,0 +1,557 @@
+//! Hex: two players connect their own pair of opposite edges of a rhombus.
+//!
+//! The board is a `size x size` rhombus of hexagonal cells. Player 0 connects
+//! the top and bottom rows and player 1 connects the left and right columns;
+//! the first player to do so wins, and since a full board always has exactly
+//! one such connection there are no draws.
+//!
+//! Cells are named in the usual Hex notation: a column letter followed by a
+//! 1-based row number, so `a1` is the top-left corner. Groups are tracked
+//! with the same union-find sets as [`crate::GameY`], each carrying the
+//! bitmask of the edges it touches.
+
+use crate::core::bitboard::Bitboard;
+use crate::core::game::apply_player_color;
+use crate::core::player_set::{PlayerSet, SideSets};
+use crate::{GameStatus, GameYError, PlayerId, RenderOptions, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+/// Bit of the edge mask for the top row.
+const EDGE_TOP: u64 = 0b0001;
+/// Bit of the edge mask for the bottom row.
+const EDGE_BOTTOM: u64 = 0b0010;
+/// Bit of the edge mask for the left column.
+const EDGE_LEFT: u64 = 0b0100;
+/// Bit of the edge mask for the right column.
+const EDGE_RIGHT: u64 = 0b1000;
+
+/// Offsets of the six neighbours of a cell, as (row, column) deltas.
+const NEIGHBOR_OFFSETS: [(i32, i32); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+/// A cell of a Hex board, given by its 0-based row and column.
+///
+/// Displays and parses in Hex notation: `HexCell::new(2, 1)` is `b3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HexCell {
+    row: u32,
+    col: u32,
+}
+
+impl HexCell {
+    /// Creates a cell at `row` and `col`, both counted from 0 at the top-left corner.
+    pub fn new(row: u32, col: u32) -> Self {
+        Self { row, col }
+    }
+
+    /// Returns the row of the cell.
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    /// Returns the column of the cell.
+    pub fn col(&self) -> u32 {
+        self.col
+    }
+}
+
+impl Display for HexCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match char::from_u32('a' as u32 + self.col) {
+            Some(letter) if self.col < HexGame::MAX_SIZE => write!(f, "{}{}", letter, self.row + 1),
+            _ => write!(f, "({}, {})", self.row, self.col),
+        }
+    }
+}
+
+impl FromStr for HexCell {
+    type Err = GameYError;
+
+    /// Parses a cell in Hex notation, such as `c4`. Column letters may be upper case.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || GameYError::InvalidHexNotation {
+            notation: s.to_string(),
+        };
+        let mut chars = s.chars();
+        let letter = chars.next().ok_or_else(invalid)?.to_ascii_lowercase();
+        if !letter.is_ascii_lowercase() {
+            return Err(invalid());
+        }
+        let row: u32 = chars.as_str().parse().map_err(|_| invalid())?;
+        if row == 0 {
+            return Err(invalid());
+        }
+        Ok(HexCell::new(row - 1, letter as u32 - 'a' as u32))
+    }
+}
+
+/// The state of a game of Hex.
+#[derive(Debug, Clone)]
+pub struct HexGame {
+    size: u32,
+
+    // Stones of each player, indexed by player id and then by cell index.
+    stones: [Bitboard; 2],
+
+    // Union-find sets indexed by cell, carrying the edges each group touches.
+    // Empty cells hold a singleton set.
+    sets: SideSets,
+
+    // Cells played so far, in order.
+    history: Vec<HexCell>,
+
+    status: GameStatus,
+}
+
+impl HexGame {
+    /// Largest board size, one column per letter of the alphabet.
+    pub const MAX_SIZE: u32 = 26;
+
+    /// Creates an empty `size x size` board. Player 0 moves first.
+    ///
+    /// Returns an error if `size` is 0 or larger than [`Self::MAX_SIZE`].
+    pub fn new(size: u32) -> Result<Self> {
+        if size == 0 || size > Self::MAX_SIZE {
+            return Err(GameYError::InvalidHexSize {
+                size,
+                max: Self::MAX_SIZE,
+            });
+        }
+        let total_cells = (size * size) as usize;
+        let sets = SideSets::new(
+            (0..total_cells)
+                .map(|idx| {
+                    let cell = HexCell::new(idx as u32 / size, idx as u32 % size);
+                    PlayerSet::with_sides(idx, edge_mask(&cell, size))
+                })
+                .collect(),
+        );
+        Ok(Self {
+            size,
+            stones: [Bitboard::new(total_cells), Bitboard::new(total_cells)],
+            sets,
+            history: Vec::new(),
+            status: GameStatus::Ongoing {
+                next_player: PlayerId::new(0),
+            },
+        })
+    }
+
+    /// Creates a `size x size` board and plays the whitespace separated
+    /// cells of `moves` in order, as produced by [`Self::moves_notation`].
+    pub fn from_moves(size: u32, moves: &str) -> Result<Self> {
+        let mut game = Self::new(size)?;
+        for notation in moves.split_whitespace() {
+            let cell: HexCell = notation.parse()?;
+            let player = game.next_player().unwrap_or(PlayerId::new(0));
+            game.play(player, cell)?;
+        }
+        Ok(game)
+    }
+
+    /// Returns the size of the board.
+    pub fn board_size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the total number of cells on the board.
+    pub fn total_cells(&self) -> u32 {
+        self.size * self.size
+    }
+
+    /// Returns the current game status.
+    pub fn status(&self) -> &GameStatus {
+        &self.status
+    }
+
+    /// Returns true if the game has ended.
+    pub fn check_game_over(&self) -> bool {
+        matches!(self.status, GameStatus::Finished { .. })
+    }
+
+    /// Returns the player who should make the next move, or None if the game is over.
+    pub fn next_player(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Ongoing { next_player } => Some(next_player),
+            GameStatus::Finished { .. } => None,
+        }
+    }
+
+    /// Returns the winner, if the game is over.
+    pub fn winner(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Finished { winner } => Some(winner),
+            GameStatus::Ongoing { .. } => None,
+        }
+    }
+
+    /// Returns the cells played so far, in order.
+    pub fn history(&self) -> &[HexCell] {
+        &self.history
+    }
+
+    /// Returns the moves played so far in Hex notation, separated by spaces.
+    pub fn moves_notation(&self) -> String {
+        self.history
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the empty cells, row by row.
+    pub fn available_cells(&self) -> Vec<HexCell> {
+        (0..self.total_cells() as usize)
+            .filter(|&idx| self.owner(idx).is_none())
+            .map(|idx| self.cell(idx))
+            .collect()
+    }
+
+    /// Returns true if `cell` is on the board.
+    pub fn contains(&self, cell: &HexCell) -> bool {
+        cell.row < self.size && cell.col < self.size
+    }
+
+    /// Returns the neighbours of `cell` that are on the board.
+    pub fn neighbors(&self, cell: &HexCell) -> Vec<HexCell> {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .filter_map(|(dr, dc)| {
+                let row = cell.row.checked_add_signed(*dr)?;
+                let col = cell.col.checked_add_signed(*dc)?;
+                let neighbor = HexCell::new(row, col);
+                self.contains(&neighbor).then_some(neighbor)
+            })
+            .collect()
+    }
+
+    /// Returns the player whose stone is at `cell`, if any.
+    pub fn player_at(&self, cell: &HexCell) -> Option<PlayerId> {
+        self.index(cell).ok().and_then(|idx| self.owner(idx))
+    }
+
+    /// Returns true if `cell` lies on the first of the two edges `player` must
+    /// connect: the top row for player 0, the left column for player 1.
+    pub fn touches_start_edge(&self, player: PlayerId, cell: &HexCell) -> bool {
+        let start = if player.id() == 0 {
+            EDGE_TOP
+        } else {
+            EDGE_LEFT
+        };
+        self.contains(cell) && edge_mask(cell, self.size) & start != 0
+    }
+
+    /// Returns true if `cell` lies on the second of the two edges `player`
+    /// must connect: the bottom row for player 0, the right column for player 1.
+    pub fn touches_end_edge(&self, player: PlayerId, cell: &HexCell) -> bool {
+        let end = if player.id() == 0 {
+            EDGE_BOTTOM
+        } else {
+            EDGE_RIGHT
+        };
+        self.contains(cell) && edge_mask(cell, self.size) & end != 0
+    }
+
+    /// Places a stone of `player` on `cell`.
+    ///
+    /// Returns an error if the game is over, if it is not `player`'s turn or
+    /// if the cell is off the board or occupied.
+    pub fn play(&mut self, player: PlayerId, cell: HexCell) -> Result<()> {
+        let next_player = match self.status {
+            GameStatus::Finished { winner } => {
+                return Err(GameYError::HexGameFinished { winner });
+            }
+            GameStatus::Ongoing { next_player } => next_player,
+        };
+        if player != next_player {
+            return Err(GameYError::InvalidPlayerTurn {
+                expected: next_player,
+                found: player,
+            });
+        }
+        let idx = self.index(&cell)?;
+        if self.owner(idx).is_some() {
+            return Err(GameYError::HexCellOccupied { cell });
+        }
+
+        self.stones[player.id() as usize].insert(idx);
+        let friends: Vec<usize> = self
+            .neighbors(&cell)
+            .iter()
+            .filter_map(|neighbor| self.index(neighbor).ok())
+            .filter(|&neighbor_idx| self.stones[player.id() as usize].contains(neighbor_idx))
+            .collect();
+        let root = self.sets.join(idx, friends);
+        self.history.push(cell);
+
+        let won = self.sets[root].touches(goal_mask(player));
+        self.status = GameStatus::after_placement(player, won, 2);
+        Ok(())
+    }
+
+    /// Renders the board as text, one row per line, each row shifted right
+    /// so the board reads as a rhombus.
+    ///
+    /// Hex cells have no barycentric coordinates: `show_3d_coords` appends the
+    /// cell name in Hex notation instead.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut result = String::new();
+        let _ = writeln!(result, "--- Hex (Size {}) ---", self.size);
+        let row_width = self.size.to_string().len();
+        let cell_width = self.cell_width(options);
+
+        result.push_str(&" ".repeat(row_width + 1));
+        for col in 0..self.size {
+            let letter = char::from_u32('a' as u32 + col).unwrap_or('?');
+            let _ = write!(result, "{:<w$}", letter, w = cell_width);
+        }
+        result.push('\n');
+
+        for row in 0..self.size {
+            result.push_str(&" ".repeat(row as usize * cell_width.div_ceil(2)));
+            let _ = write!(result, "{:>w$} ", row + 1, w = row_width);
+            for col in 0..self.size {
+                let cell = HexCell::new(row, col);
+                let symbol = self.format_cell(&cell, options);
+                let padding = cell_width.saturating_sub(self.plain_width(&cell, options));
+                let _ = write!(result, "{}{}", symbol, " ".repeat(padding));
+            }
+            result.push('\n');
+        }
+        result
+    }
+
+    fn format_cell(&self, cell: &HexCell, options: &RenderOptions) -> String {
+        let player = self.player_at(cell);
+        let mut symbol = match player {
+            Some(p) => p.to_string(),
+            None => ".".to_string(),
+        };
+        if options.show_3d_coords {
+            let _ = write!(symbol, "({})", cell);
+        }
+        if options.show_idx {
+            let _ = write!(symbol, "({})", cell.row * self.size + cell.col);
+        }
+        if options.show_colors {
+            symbol = apply_player_color(symbol, player);
+        }
+        symbol
+    }
+
+    /// Returns the width of a cell without colour codes.
+    fn plain_width(&self, cell: &HexCell, options: &RenderOptions) -> usize {
+        let mut width = 1;
+        if options.show_3d_coords {
+            width += cell.to_string().len() + 2;
+        }
+        if options.show_idx {
+            width += (cell.row * self.size + cell.col).to_string().len() + 2;
+        }
+        width
+    }
+
+    /// Returns the width of the widest cell plus the gap between cells.
+    fn cell_width(&self, options: &RenderOptions) -> usize {
+        let last = HexCell::new(self.size - 1, self.size - 1);
+        self.plain_width(&last, options) + 1
+    }
+
+    /// Returns the index of `cell`, or an error if it is not on the board.
+    fn index(&self, cell: &HexCell) -> Result<usize> {
+        if !self.contains(cell) {
+            return Err(GameYError::InvalidHexCell {
+                cell: *cell,
+                size: self.size,
+            });
+        }
+        Ok((cell.row * self.size + cell.col) as usize)
+    }
+
+    /// Returns the cell with index `idx`.
+    fn cell(&self, idx: usize) -> HexCell {
+        HexCell::new(idx as u32 / self.size, idx as u32 % self.size)
+    }
+
+    /// Returns the player whose stone is on the cell `idx`, if any.
+    fn owner(&self, idx: usize) -> Option<PlayerId> {
+        self.stones
+            .iter()
+            .position(|stones| stones.contains(idx))
+            .map(|player| PlayerId::new(player as u32))
+    }
+}
+
+impl Display for HexGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = RenderOptions {
+            show_3d_coords: false,
+            show_idx: false,
+            show_colors: false,
+        };
+        write!(f, "{}", self.render(&options))
+    }
+}
+
+/// Returns the bitmask of the edges of a `size x size` board that `cell` lies on.
+fn edge_mask(cell: &HexCell, size: u32) -> u64 {
+    let mut mask = 0;
+    if cell.row == 0 {
+        mask |= EDGE_TOP;
+    }
+    if cell.row == size - 1 {
+        mask |= EDGE_BOTTOM;
+    }
+    if cell.col == 0 {
+        mask |= EDGE_LEFT;
+    }
+    if cell.col == size - 1 {
+        mask |= EDGE_RIGHT;
+    }
+    mask
+}
+
+/// Returns the edges `player` must connect: top and bottom for player 0,
+/// left and right for player 1.
+fn goal_mask(player: PlayerId) -> u64 {
+    if player.id() == 0 {
+        EDGE_TOP | EDGE_BOTTOM
+    } else {
+        EDGE_LEFT | EDGE_RIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(id: u32) -> PlayerId {
+        PlayerId::new(id)
+    }
+
+    #[test]
+    fn test_invalid_sizes_are_rejected() {
+        assert!(matches!(
+            HexGame::new(0),
+            Err(GameYError::InvalidHexSize { size: 0, max: 26 })
+        ));
+        assert!(HexGame::new(27).is_err());
+        assert!(HexGame::new(26).is_ok());
+    }
+
+    #[test]
+    fn test_cell_notation_roundtrip() {
+        let cell = HexCell::new(2, 1);
+        assert_eq!(cell.to_string(), "b3");
+        assert_eq!("b3".parse::<HexCell>().unwrap(), cell);
+        assert_eq!("B3".parse::<HexCell>().unwrap(), cell);
+        assert_eq!("z26".parse::<HexCell>().unwrap(), HexCell::new(25, 25));
+    }
+
+    #[test]
+    fn test_invalid_notation() {
+        for notation in ["", "a", "a0", "1a", "ab1", "a-1"] {
+            assert!(
+                matches!(
+                    notation.parse::<HexCell>(),
+                    Err(GameYError::InvalidHexNotation { .. })
+                ),
+                "{notation} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let game = HexGame::new(5).unwrap();
+        assert_eq!(game.neighbors(&HexCell::new(0, 0)).len(), 2);
+        assert_eq!(game.neighbors(&HexCell::new(0, 4)).len(), 3);
+        assert_eq!(game.neighbors(&HexCell::new(2, 2)).len(), 6);
+        for cell in game.available_cells() {
+            for neighbor in game.neighbors(&cell) {
+                assert!(game.neighbors(&neighbor).contains(&cell));
+            }
+        }
+    }
+
+    #[test]
+    fn test_player_0_connects_top_and_bottom() {
+        let mut game = HexGame::from_moves(3, "a1 b1 a2 b2").unwrap();
+        assert!(!game.check_game_over());
+        game.play(p(0), "a3".parse().unwrap()).unwrap();
+        assert_eq!(*game.status(), GameStatus::Finished { winner: p(0) });
+        assert_eq!(game.winner(), Some(p(0)));
+    }
+
+    #[test]
+    fn test_player_1_connects_left_and_right() {
+        let game = HexGame::from_moves(3, "a1 a2 a3 b2 b1 c2").unwrap();
+        assert_eq!(*game.status(), GameStatus::Finished { winner: p(1) });
+    }
+
+    #[test]
+    fn test_diagonal_neighbor_connects() {
+        // c1 and b2 are neighbours, a1 and b2 are not
+        let game = HexGame::from_moves(3, "c1 a1 b2 a2 a3").unwrap();
+        assert_eq!(*game.status(), GameStatus::Finished { winner: p(0) });
+    }
+
+    #[test]
+    fn test_play_errors() {
+        let mut game = HexGame::new(3).unwrap();
+        assert!(matches!(
+            game.play(p(1), HexCell::new(0, 0)),
+            Err(GameYError::InvalidPlayerTurn { .. })
+        ));
+        game.play(p(0), HexCell::new(0, 0)).unwrap();
+        assert!(matches!(
+            game.play(p(1), HexCell::new(0, 0)),
+            Err(GameYError::HexCellOccupied { .. })
+        ));
+        assert!(matches!(
+            game.play(p(1), HexCell::new(3, 0)),
+            Err(GameYError::InvalidHexCell { size: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_no_moves_after_win() {
+        let mut game = HexGame::from_moves(2, "a1 b1 a2").unwrap();
+        assert!(matches!(
+            game.play(p(1), HexCell::new(1, 1)),
+            Err(GameYError::HexGameFinished { .. })
+        ));
+    }
+
+    #[test]
+    fn test_moves_notation_roundtrip() {
+        let game = HexGame::from_moves(5, "c3 d2 e1").unwrap();
+        assert_eq!(game.moves_notation(), "c3 d2 e1");
+        assert_eq!(game.history().len(), 3);
+        assert_eq!(game.player_at(&HexCell::new(1, 3)), Some(p(1)));
+    }
+
+    #[test]
+    fn test_full_board_always_has_a_winner() {
+        for size in 1..=7 {
+            let mut game = HexGame::new(size).unwrap();
+            while let Some(player) = game.next_player() {
+                let cells = game.available_cells();
+                let cell = cells[(cells.len() * 5 / 9) % cells.len()];
+                game.play(player, cell).unwrap();
+            }
+            assert!(game.check_game_over());
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let game = HexGame::from_moves(3, "b2 a1").unwrap();
+        let rendered = game.to_string();
+        assert_eq!(
+            rendered,
+            "--- Hex (Size 3) ---\n  a b c \n1 1 . . \n 2 . 0 . \n  3 . . . \n"
+        );
+    }
+}
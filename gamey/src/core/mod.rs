@@ -8,20 +8,38 @@
 //! - [`Movement`]: A move (placement or action) in the game
 //! - [`GameAction`]: Special actions like swap or resign
 //! - [`RenderOptions`]: Configuration for board rendering
+//! - [`GameRules`]: The rules a game is played by
+//! - [`Symmetry`]: The six symmetries of the triangular board
+//! - [`PolyYGame`]: Poly-Y, played on boards shaped as regular polygons
+//! - [`HexGame`]: Hex, played on rhombus boards
+//! - [`TetraGame`]: The tetrahedral 3D variant of Y
 
 pub mod action;
+mod bitboard;
 pub mod coord;
 pub mod game;
+mod geometry;
+pub mod hex;
 pub mod movement;
 pub mod player;
 mod player_set;
+pub mod poly;
 pub mod render_options;
+pub mod rules;
+pub mod symmetry;
+pub mod tetra;
+mod zobrist;
 
 pub use action::*;
 pub use coord::*;
 pub use game::*;
+pub use hex::*;
 pub use movement::*;
 pub use player::*;
+pub use poly::*;
 pub use render_options::*;
+pub use rules::*;
+pub use symmetry::*;
+pub use tetra::*;
 
 type SetIdx = usize;
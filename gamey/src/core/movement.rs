@@ -4,7 +4,8 @@ use std::fmt::Display;
 /// Represents a move that a player can make during the game.
 ///
 /// A movement can either be placing a piece on the board at specific coordinates,
-/// or performing a special game action like swapping or resigning.
+/// performing a special game action like swapping or resigning, or setting up a
+/// stone before the game starts.
 #[derive(Debug, Clone)]
 pub enum Movement {
     /// A piece placement on the board.
@@ -21,6 +22,26 @@ pub enum Movement {
         /// The action being performed.
         action: GameAction,
     },
+    /// A stone placed before the game starts, such as a handicap stone.
+    ///
+    /// Setup stones do not count as turns: the player to move is unchanged.
+    Setup {
+        /// The player who receives the stone.
+        player: PlayerId,
+        /// The coordinates where the stone is placed.
+        coords: Coordinates,
+    },
+}
+
+impl Movement {
+    /// Returns the player making the movement.
+    pub fn player(&self) -> PlayerId {
+        match self {
+            Movement::Placement { player, .. }
+            | Movement::Action { player, .. }
+            | Movement::Setup { player, .. } => *player,
+        }
+    }
 }
 
 impl Display for Movement {
@@ -32,6 +53,9 @@ impl Display for Movement {
             Movement::Action { player, action } => {
                 write!(f, "Player {} performs action {}", player, action)
             }
+            Movement::Setup { player, coords } => {
+                write!(f, "Player {} is given a stone at {}", player, coords)
+            }
         }
     }
 }
@@ -49,6 +73,30 @@ mod tests {
         assert_eq!(format!("{}", movement), "Player 0 places at (1, 2, 3)");
     }
 
+    #[test]
+    fn test_movement_player() {
+        let placement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 2, 3),
+        };
+        let action = Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Resign,
+        };
+        assert_eq!(placement.player(), PlayerId::new(0));
+        assert_eq!(action.player(), PlayerId::new(1));
+    }
+
+    #[test]
+    fn test_setup_display() {
+        let movement = Movement::Setup {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 1, 1),
+        };
+        assert_eq!(format!("{}", movement), "Player 0 is given a stone at (1, 1, 1)");
+        assert_eq!(movement.player(), PlayerId::new(0));
+    }
+
     #[test]
     fn test_action_swap_display() {
         let movement = Movement::Action {
@@ -1,18 +1,202 @@
+use crate::Coordinates;
 use crate::core::SetIdx;
+use std::ops::Index;
+
+/// Bit of the side mask for side A (x == 0) of the triangular board.
+pub(crate) const SIDE_A: u64 = 0b001;
+/// Bit of the side mask for side B (y == 0) of the triangular board.
+pub(crate) const SIDE_B: u64 = 0b010;
+/// Bit of the side mask for side C (z == 0) of the triangular board.
+pub(crate) const SIDE_C: u64 = 0b100;
 
 // Struct to track connected components in the Union-Find structure
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct PlayerSet {
     pub parent: SetIdx,
-    // We track which sides this specific set of pieces is touching
-    pub touches_side_a: bool,
-    pub touches_side_b: bool,
-    pub touches_side_c: bool,
+    // We track which sides this specific set of pieces is touching, one bit
+    // per side. The triangular board uses `SIDE_A`, `SIDE_B` and `SIDE_C`;
+    // polygon boards use bit `i` for side `i`.
+    pub sides: u64,
 }
 
 impl PlayerSet {
-    /// Checks if this set connects all three sides of the board.
+    /// Creates the set of a single stone placed on the cell `idx` at `coords`
+    /// of the triangular board.
+    pub fn singleton(idx: SetIdx, coords: &Coordinates) -> Self {
+        let mut sides = 0;
+        if coords.touches_side_a() {
+            sides |= SIDE_A;
+        }
+        if coords.touches_side_b() {
+            sides |= SIDE_B;
+        }
+        if coords.touches_side_c() {
+            sides |= SIDE_C;
+        }
+        Self::with_sides(idx, sides)
+    }
+
+    /// Creates the set of a single stone on the cell `idx`, touching `sides`.
+    pub fn with_sides(idx: SetIdx, sides: u64) -> Self {
+        Self { parent: idx, sides }
+    }
+
+    /// Adds the sides touched by `other` to the sides touched by this set.
+    pub fn merge_sides(&mut self, other: &PlayerSet) {
+        self.sides |= other.sides;
+    }
+
+    /// Returns true if this set touches every side in `mask`.
+    pub fn touches(&self, mask: u64) -> bool {
+        self.sides & mask == mask
+    }
+
+    /// Checks if this set connects all three sides of the triangular board.
     pub fn is_winning_configuration(&self) -> bool {
-        self.touches_side_a && self.touches_side_b && self.touches_side_c
+        self.touches(SIDE_A | SIDE_B | SIDE_C)
+    }
+}
+
+/// Union-find structure over the cells of a board, with one [`PlayerSet`]
+/// per cell. Every board won by connecting sides tracks its groups with it:
+/// the triangular board of [`crate::GameY`], Poly-Y and Hex.
+///
+/// Each entry overwritten by [`SideSets::find`] or [`SideSets::union`] is
+/// logged in a trail, so that changes can be taken back with
+/// [`SideSets::rollback`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SideSets {
+    sets: Vec<PlayerSet>,
+    // Previous values of the overwritten entries, in order.
+    trail: Vec<(SetIdx, PlayerSet)>,
+}
+
+impl SideSets {
+    /// Creates the structure from the singleton set of every cell.
+    pub fn new(sets: Vec<PlayerSet>) -> Self {
+        Self {
+            sets,
+            trail: Vec::new(),
+        }
+    }
+
+    /// Disjoint Set Union 'Find' without path compression, for read-only queries.
+    pub fn find_root(&self, mut i: SetIdx) -> SetIdx {
+        while self.sets[i].parent != i {
+            i = self.sets[i].parent;
+        }
+        i
+    }
+
+    /// Disjoint Set Union 'Find' with path compression
+    pub fn find(&mut self, i: SetIdx) -> SetIdx {
+        let parent = self.sets[i].parent;
+        if parent == i {
+            i
+        } else {
+            let root = self.find(parent);
+            if root != parent {
+                self.trail.push((i, self.sets[i].clone()));
+                self.sets[i].parent = root;
+            }
+            root
+        }
+    }
+
+    /// Disjoint Set Union 'Union' operation, merging the sides touched.
+    ///
+    /// Returns the root of the merged set, or None if `i` and `j` were
+    /// already in the same set.
+    pub fn union(&mut self, i: SetIdx, j: SetIdx) -> Option<SetIdx> {
+        let root_i = self.find(i);
+        let root_j = self.find(j);
+        if root_i == root_j {
+            return None;
+        }
+        self.trail.push((root_i, self.sets[root_i].clone()));
+        self.trail.push((root_j, self.sets[root_j].clone()));
+        self.sets[root_i].parent = root_j;
+        let absorbed = self.sets[root_i].clone();
+        self.sets[root_j].merge_sides(&absorbed);
+        Some(root_j)
+    }
+
+    /// Merges the set of the cell `idx`, where a stone was just placed, with
+    /// the set of every cell in `friends` and returns the root of the group.
+    pub fn join(&mut self, idx: SetIdx, friends: impl IntoIterator<Item = SetIdx>) -> SetIdx {
+        for friend in friends {
+            self.union(idx, friend);
+        }
+        self.find(idx)
+    }
+
+    /// Returns the number of entries logged in the trail, to be passed to
+    /// [`SideSets::rollback`] later.
+    pub fn trail_len(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Restores every entry overwritten since the trail had `len` entries.
+    pub fn rollback(&mut self, len: usize) {
+        for (idx, previous) in self.trail.drain(len..).rev() {
+            self.sets[idx] = previous;
+        }
+    }
+}
+
+impl Index<SetIdx> for SideSets {
+    type Output = PlayerSet;
+
+    fn index(&self, idx: SetIdx) -> &PlayerSet {
+        &self.sets[idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sets(sides: &[u64]) -> SideSets {
+        SideSets::new(
+            sides
+                .iter()
+                .enumerate()
+                .map(|(idx, &sides)| PlayerSet::with_sides(idx, sides))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_union_merges_sides() {
+        let mut sets = sets(&[SIDE_A, 0, SIDE_B | SIDE_C]);
+        let root = sets.union(0, 1).unwrap();
+        assert!(!sets[root].is_winning_configuration());
+        let root = sets.union(1, 2).unwrap();
+        assert!(sets[root].is_winning_configuration());
+        assert_eq!(sets.find_root(0), root);
+        assert_eq!(sets.union(0, 2), None);
+    }
+
+    #[test]
+    fn test_join_returns_the_root_of_the_group() {
+        let mut sets = sets(&[SIDE_A, SIDE_B, 0, SIDE_C]);
+        let root = sets.join(2, [0, 3]);
+        assert_eq!(root, sets.find_root(0));
+        assert_eq!(root, sets.find_root(3));
+        assert_ne!(root, sets.find_root(1));
+        assert!(sets[root].touches(SIDE_A | SIDE_C));
+    }
+
+    #[test]
+    fn test_rollback_restores_every_entry() {
+        let mut sets = sets(&[SIDE_A, 0, SIDE_B, SIDE_C]);
+        sets.union(0, 1);
+        let before = sets.clone();
+        let len = sets.trail_len();
+        sets.union(2, 3);
+        sets.union(1, 3);
+        sets.find(0);
+        sets.rollback(len);
+        assert_eq!(sets, before);
     }
 }
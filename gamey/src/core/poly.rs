@@ -0,0 +1,560 @@
+//! Poly-Y: Y played on boards shaped as regular polygons.
+//!
+//! A board with `n` sides and radius `r` is made of `n` triangular sectors
+//! around a central cell. Cells are arranged in rings: ring 0 is the center
+//! and ring `k` holds `n * k` cells, numbered from the spoke of sector 0. On a
+//! hexagon every cell but those on the edge has six neighbours; on other
+//! polygons the center has `n`.
+//!
+//! The outer ring forms the `n` sides of the polygon. Corner `c` is the cell
+//! where side `c - 1` ends and side `c` starts, and a player owns the corner
+//! with a single group that connects those two sides and at least one other
+//! side. A stone on the corner cell alone touches both sides but owns
+//! nothing. The player who owns the majority of the corners wins.
+//!
+//! Splitting the outline into side `c - 1`, side `c` and the other sides
+//! turns the board into a Y board, so when the board is full exactly one
+//! player owns each corner. On a board with an even number of sides the
+//! corners can still split evenly; that tie goes to the player who owned
+//! half of the corners first, so the game ends as soon as a player owns half
+//! of them.
+//!
+//! Connected groups are tracked with the same union-find sets as
+//! [`crate::GameY`], each carrying the bitmask of the sides it touches.
+
+use crate::core::SetIdx;
+use crate::core::bitboard::Bitboard;
+use crate::core::player_set::{PlayerSet, SideSets};
+use crate::{GameStatus, GameYError, PlayerId, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A cell of a Poly-Y board, given by its ring and its position in the ring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PolyCell {
+    ring: u32,
+    position: u32,
+}
+
+impl PolyCell {
+    /// Creates a cell in `ring` at `position`, counted from the spoke of sector 0.
+    pub fn new(ring: u32, position: u32) -> Self {
+        Self { ring, position }
+    }
+
+    /// Returns the ring of the cell; the center is ring 0.
+    pub fn ring(&self) -> u32 {
+        self.ring
+    }
+
+    /// Returns the position of the cell in its ring.
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+}
+
+impl Display for PolyCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.ring, self.position)
+    }
+}
+
+/// The state of a two-player game of Poly-Y.
+#[derive(Debug, Clone)]
+pub struct PolyYGame {
+    // Number of sides of the polygon.
+    sides: u32,
+
+    // Number of rings around the center.
+    radius: u32,
+
+    // Neighbours of every cell, indexed by cell.
+    neighbors: Vec<Vec<u32>>,
+
+    // Stones of each player, indexed by player id and then by cell index.
+    stones: [Bitboard; 2],
+
+    // Union-find sets indexed by cell, carrying the sides each group touches.
+    // Empty cells hold a singleton set.
+    sets: SideSets,
+
+    // Owner of each corner, indexed by corner.
+    corners: Vec<Option<PlayerId>>,
+
+    status: GameStatus,
+}
+
+impl PolyYGame {
+    /// Smallest number of sides of a Poly-Y board.
+    pub const MIN_SIDES: u32 = 5;
+
+    /// Largest number of sides of a Poly-Y board, one per bit of the side mask.
+    pub const MAX_SIDES: u32 = u64::BITS;
+
+    /// Largest radius of a Poly-Y board.
+    pub const MAX_RADIUS: u32 = 50;
+
+    /// Creates an empty board with `sides` sides and `radius` rings around
+    /// the center. Player 0 moves first.
+    ///
+    /// Returns an error if `sides` is not between [`Self::MIN_SIDES`] and
+    /// [`Self::MAX_SIDES`] or if `radius` is not between 1 and
+    /// [`Self::MAX_RADIUS`].
+    pub fn new(sides: u32, radius: u32) -> Result<Self> {
+        if !(Self::MIN_SIDES..=Self::MAX_SIDES).contains(&sides)
+            || !(1..=Self::MAX_RADIUS).contains(&radius)
+        {
+            return Err(GameYError::InvalidPolygon { sides, radius });
+        }
+        let total_cells = (1 + sides * radius * (radius + 1) / 2) as usize;
+        let mut game = Self {
+            sides,
+            radius,
+            neighbors: vec![Vec::new(); total_cells],
+            stones: [Bitboard::new(total_cells), Bitboard::new(total_cells)],
+            sets: SideSets::new(Vec::new()),
+            corners: vec![None; sides as usize],
+            status: GameStatus::Ongoing {
+                next_player: PlayerId::new(0),
+            },
+        };
+        game.link_cells();
+        game.sets = SideSets::new(
+            (0..total_cells)
+                .map(|idx| PlayerSet::with_sides(idx, game.side_mask(idx)))
+                .collect(),
+        );
+        Ok(game)
+    }
+
+    /// Returns the number of sides of the board.
+    pub fn sides(&self) -> u32 {
+        self.sides
+    }
+
+    /// Returns the number of rings around the center.
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Returns the total number of cells on the board.
+    pub fn total_cells(&self) -> u32 {
+        self.neighbors.len() as u32
+    }
+
+    /// Returns the current game status.
+    pub fn status(&self) -> &GameStatus {
+        &self.status
+    }
+
+    /// Returns true if the game has ended.
+    pub fn check_game_over(&self) -> bool {
+        matches!(self.status, GameStatus::Finished { .. })
+    }
+
+    /// Returns the player who should make the next move, or None if the game is over.
+    pub fn next_player(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Ongoing { next_player } => Some(next_player),
+            GameStatus::Finished { .. } => None,
+        }
+    }
+
+    /// Returns the empty cells, ring by ring.
+    pub fn available_cells(&self) -> Vec<PolyCell> {
+        (0..self.neighbors.len())
+            .filter(|&idx| self.owner(idx).is_none())
+            .map(|idx| self.cell(idx))
+            .collect()
+    }
+
+    /// Returns the neighbours of `cell`, or an error if it is not on the board.
+    pub fn neighbors(&self, cell: &PolyCell) -> Result<Vec<PolyCell>> {
+        let idx = self.index(cell)?;
+        Ok(self.neighbors[idx]
+            .iter()
+            .map(|&neighbor| self.cell(neighbor as usize))
+            .collect())
+    }
+
+    /// Returns the player whose stone is at `cell`, if any.
+    pub fn player_at(&self, cell: &PolyCell) -> Option<PlayerId> {
+        self.index(cell).ok().and_then(|idx| self.owner(idx))
+    }
+
+    /// Returns true if `cell` lies on side `side` of the polygon.
+    pub fn touches_side(&self, cell: &PolyCell, side: u32) -> bool {
+        side < self.sides
+            && self
+                .index(cell)
+                .is_ok_and(|idx| self.side_mask(idx) & (1 << side) != 0)
+    }
+
+    /// Returns the cell of corner `corner`, where side `corner - 1` meets side `corner`.
+    pub fn corner_cell(&self, corner: u32) -> PolyCell {
+        PolyCell::new(self.radius, (corner % self.sides) * self.radius)
+    }
+
+    /// Returns the player who owns corner `corner`, if any.
+    pub fn corner_owner(&self, corner: u32) -> Option<PlayerId> {
+        self.corners.get(corner as usize).copied().flatten()
+    }
+
+    /// Returns the number of corners owned by `player`.
+    pub fn corners_owned(&self, player: PlayerId) -> u32 {
+        self.corners
+            .iter()
+            .filter(|owner| **owner == Some(player))
+            .count() as u32
+    }
+
+    /// Places a stone of `player` on `cell`.
+    ///
+    /// Returns an error if the game is over, if it is not `player`'s turn or
+    /// if the cell is off the board or occupied.
+    pub fn play(&mut self, player: PlayerId, cell: PolyCell) -> Result<()> {
+        let next_player = match self.status {
+            GameStatus::Finished { winner } => {
+                return Err(GameYError::PolyGameFinished { winner });
+            }
+            GameStatus::Ongoing { next_player } => next_player,
+        };
+        if player != next_player {
+            return Err(GameYError::InvalidPlayerTurn {
+                expected: next_player,
+                found: player,
+            });
+        }
+        let idx = self.index(&cell)?;
+        if self.owner(idx).is_some() {
+            return Err(GameYError::PolyCellOccupied {
+                ring: cell.ring,
+                position: cell.position,
+            });
+        }
+
+        let stones = &mut self.stones[player.id() as usize];
+        stones.insert(idx);
+        let friends = self.neighbors[idx]
+            .iter()
+            .map(|&neighbor| neighbor as usize)
+            .filter(|&neighbor| stones.contains(neighbor));
+        let root = self.sets.join(idx, friends);
+        self.claim_corners(player, root);
+
+        self.status = GameStatus::after_placement(player, self.wins(player), 2);
+        Ok(())
+    }
+
+    /// Returns true if `player` owns the majority of the corners, or half of
+    /// them on a board with an even number of sides.
+    ///
+    /// Players win as soon as they reach half of the corners, so the first
+    /// player to reach half is the one who wins an even split. On a full board
+    /// every corner is owned, so one of the players always wins.
+    fn wins(&self, player: PlayerId) -> bool {
+        self.corners_owned(player) * 2 >= self.sides
+    }
+
+    /// Gives `player` every unowned corner whose two sides the group rooted
+    /// at `root` connects to some other side.
+    fn claim_corners(&mut self, player: PlayerId, root: SetIdx) {
+        let group = &self.sets[root];
+        for corner in 0..self.sides {
+            let previous = (corner + self.sides - 1) % self.sides;
+            let mask = (1u64 << previous) | (1u64 << corner);
+            if self.corners[corner as usize].is_none()
+                && group.touches(mask)
+                && group.sides & !mask != 0
+            {
+                self.corners[corner as usize] = Some(player);
+            }
+        }
+    }
+
+    /// Returns the number of cells in `ring`.
+    fn ring_len(&self, ring: u32) -> u32 {
+        if ring == 0 { 1 } else { self.sides * ring }
+    }
+
+    /// Returns the index of the first cell of `ring`.
+    fn ring_start(&self, ring: u32) -> usize {
+        if ring == 0 {
+            0
+        } else {
+            (1 + self.sides * (ring - 1) * ring / 2) as usize
+        }
+    }
+
+    /// Returns the index of `cell`, or an error if it is not on the board.
+    fn index(&self, cell: &PolyCell) -> Result<usize> {
+        if cell.ring > self.radius || cell.position >= self.ring_len(cell.ring) {
+            return Err(GameYError::InvalidPolyCell {
+                ring: cell.ring,
+                position: cell.position,
+            });
+        }
+        Ok(self.ring_start(cell.ring) + cell.position as usize)
+    }
+
+    /// Returns the cell with index `idx`.
+    fn cell(&self, idx: usize) -> PolyCell {
+        let ring = (0..=self.radius)
+            .rev()
+            .find(|&ring| self.ring_start(ring) <= idx)
+            .unwrap_or(0);
+        PolyCell::new(ring, (idx - self.ring_start(ring)) as u32)
+    }
+
+    /// Returns the bitmask of the sides the cell `idx` lies on.
+    ///
+    /// Side `s` is the stretch of the outer ring from the spoke of sector `s`
+    /// to the spoke of sector `s + 1`, both included.
+    fn side_mask(&self, idx: usize) -> u64 {
+        let cell = self.cell(idx);
+        if cell.ring != self.radius {
+            return 0;
+        }
+        let sector = cell.position / self.radius;
+        let mut mask = 1u64 << sector;
+        if cell.position.is_multiple_of(self.radius) {
+            mask |= 1u64 << ((sector + self.sides - 1) % self.sides);
+        }
+        mask
+    }
+
+    /// Fills the neighbour lists: every cell is linked to the next cell of its
+    /// ring and to the cells it touches in the ring inside it.
+    fn link_cells(&mut self) {
+        for ring in 1..=self.radius {
+            let len = self.ring_len(ring);
+            for position in 0..len {
+                let idx = self.ring_start(ring) + position as usize;
+                let next = self.ring_start(ring) + ((position + 1) % len) as usize;
+                self.link(idx, next);
+
+                let sector = position / ring;
+                let offset = position % ring;
+                let inner_start = self.ring_start(ring - 1);
+                let inner_len = self.ring_len(ring - 1);
+                let inner_base = sector * (ring - 1);
+                if ring == 1 {
+                    self.link(idx, 0);
+                } else if offset == 0 {
+                    self.link(idx, inner_start + inner_base as usize);
+                } else {
+                    let before = inner_base + offset - 1;
+                    let after = (inner_base + offset) % inner_len;
+                    self.link(idx, inner_start + before as usize);
+                    self.link(idx, inner_start + after as usize);
+                }
+            }
+        }
+        for neighbors in &mut self.neighbors {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        if a != b {
+            self.neighbors[a].push(b as u32);
+            self.neighbors[b].push(a as u32);
+        }
+    }
+
+    /// Returns the player whose stone is on the cell `idx`, if any.
+    fn owner(&self, idx: usize) -> Option<PlayerId> {
+        self.stones
+            .iter()
+            .position(|stones| stones.contains(idx))
+            .map(|player| PlayerId::new(player as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(id: u32) -> PlayerId {
+        PlayerId::new(id)
+    }
+
+    #[test]
+    fn test_invalid_polygons_are_rejected() {
+        assert!(matches!(
+            PolyYGame::new(4, 3),
+            Err(GameYError::InvalidPolygon {
+                sides: 4,
+                radius: 3
+            })
+        ));
+        assert!(PolyYGame::new(65, 3).is_err());
+        assert!(PolyYGame::new(5, 0).is_err());
+        assert!(PolyYGame::new(5, 1).is_ok());
+        assert!(PolyYGame::new(64, PolyYGame::MAX_RADIUS).is_ok());
+        assert!(matches!(
+            PolyYGame::new(5, PolyYGame::MAX_RADIUS + 1),
+            Err(GameYError::InvalidPolygon { sides: 5, .. })
+        ));
+        assert!(PolyYGame::new(5, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_total_cells() {
+        let game = PolyYGame::new(5, 3).unwrap();
+        assert_eq!(game.total_cells(), 1 + 5 + 10 + 15);
+        assert_eq!(game.available_cells().len(), 31);
+    }
+
+    #[test]
+    fn test_neighbors_are_symmetric() {
+        for sides in [5, 6, 7] {
+            let game = PolyYGame::new(sides, 4).unwrap();
+            for (idx, neighbors) in game.neighbors.iter().enumerate() {
+                assert!(neighbors.len() <= 6.max(sides as usize));
+                for &neighbor in neighbors {
+                    assert!(game.neighbors[neighbor as usize].contains(&(idx as u32)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_center_has_one_neighbor_per_side() {
+        let game = PolyYGame::new(7, 2).unwrap();
+        assert_eq!(game.neighbors(&PolyCell::new(0, 0)).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_hexagon_inner_cells_have_six_neighbors() {
+        let game = PolyYGame::new(6, 4).unwrap();
+        for ring in 0..4 {
+            for position in 0..game.ring_len(ring) {
+                let cell = PolyCell::new(ring, position);
+                assert_eq!(game.neighbors(&cell).unwrap().len(), 6, "cell {}", cell);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let game = PolyYGame::new(5, 3).unwrap();
+        for idx in 0..game.total_cells() as usize {
+            assert_eq!(game.index(&game.cell(idx)).unwrap(), idx);
+        }
+        assert!(matches!(
+            game.index(&PolyCell::new(1, 5)),
+            Err(GameYError::InvalidPolyCell {
+                ring: 1,
+                position: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn test_sides_and_corners() {
+        let game = PolyYGame::new(5, 2).unwrap();
+        let corner = game.corner_cell(1);
+        assert_eq!(corner, PolyCell::new(2, 2));
+        assert!(game.touches_side(&corner, 0));
+        assert!(game.touches_side(&corner, 1));
+        assert!(!game.touches_side(&corner, 2));
+
+        let edge = PolyCell::new(2, 1);
+        assert!(game.touches_side(&edge, 0));
+        assert!(!game.touches_side(&edge, 1));
+        assert!(!game.touches_side(&PolyCell::new(1, 0), 0));
+    }
+
+    #[test]
+    fn test_connecting_adjacent_sides_owns_corner() {
+        // (2, 1) is on side 0 and (2, 3) on side 1; joined through (1, 1)
+        // they connect both sides of corner 1, but no third side
+        let mut game = PolyYGame::new(5, 2).unwrap();
+        game.play(p(0), PolyCell::new(2, 1)).unwrap();
+        game.play(p(1), PolyCell::new(0, 0)).unwrap();
+        game.play(p(0), PolyCell::new(2, 3)).unwrap();
+        game.play(p(1), PolyCell::new(2, 7)).unwrap();
+        game.play(p(0), PolyCell::new(1, 1)).unwrap();
+        game.play(p(1), PolyCell::new(2, 6)).unwrap();
+        assert_eq!(game.corner_owner(1), None);
+
+        // The corner cell (2, 0) brings side 4 into the group
+        game.play(p(0), PolyCell::new(2, 0)).unwrap();
+        assert_eq!(game.corner_owner(1), Some(p(0)));
+        assert_eq!(game.corner_owner(0), Some(p(0)));
+        assert_eq!(game.corners_owned(p(0)), 2);
+    }
+
+    #[test]
+    fn test_majority_of_corners_wins() {
+        // On a pentagon of radius 1 every ring cell is a corner cell
+        let mut game = PolyYGame::new(5, 1).unwrap();
+        game.play(p(0), PolyCell::new(1, 0)).unwrap();
+        assert_eq!(game.corners_owned(p(0)), 0);
+        game.play(p(1), PolyCell::new(1, 3)).unwrap();
+        // Two corner cells in a row touch three sides and own both corners
+        game.play(p(0), PolyCell::new(1, 1)).unwrap();
+        assert_eq!(game.corners_owned(p(0)), 2);
+        assert!(!game.check_game_over());
+        game.play(p(1), PolyCell::new(0, 0)).unwrap();
+        game.play(p(0), PolyCell::new(1, 2)).unwrap();
+        assert_eq!(game.corners_owned(p(0)), 3);
+        assert_eq!(*game.status(), GameStatus::Finished { winner: p(0) });
+        assert!(matches!(
+            game.play(p(1), PolyCell::new(1, 4)),
+            Err(GameYError::PolyGameFinished { .. })
+        ));
+    }
+
+    #[test]
+    fn test_even_split_goes_to_first_player_to_half() {
+        // On a hexagon of radius 1, player 0 holds the center and ring cells
+        // 3 to 5 while player 1 holds ring cells 0 to 2: the corners split 3-3.
+        let mut game = PolyYGame::new(6, 1).unwrap();
+        game.play(p(0), PolyCell::new(0, 0)).unwrap();
+        for (player, position) in [(1, 0), (0, 3), (1, 1), (0, 4)] {
+            game.play(p(player), PolyCell::new(1, position)).unwrap();
+        }
+        assert_eq!(game.corners_owned(p(0)), 2);
+        assert_eq!(game.corners_owned(p(1)), 2);
+        assert!(!game.check_game_over());
+
+        // Player 1 reaches half of the corners first and wins
+        game.play(p(1), PolyCell::new(1, 2)).unwrap();
+        assert_eq!(game.corners_owned(p(1)), 3);
+        assert_eq!(*game.status(), GameStatus::Finished { winner: p(1) });
+    }
+
+    #[test]
+    fn test_play_errors() {
+        let mut game = PolyYGame::new(5, 2).unwrap();
+        assert!(matches!(
+            game.play(p(1), PolyCell::new(0, 0)),
+            Err(GameYError::InvalidPlayerTurn { .. })
+        ));
+        game.play(p(0), PolyCell::new(0, 0)).unwrap();
+        assert!(matches!(
+            game.play(p(1), PolyCell::new(0, 0)),
+            Err(GameYError::PolyCellOccupied { .. })
+        ));
+        assert!(matches!(
+            game.play(p(1), PolyCell::new(3, 0)),
+            Err(GameYError::InvalidPolyCell { .. })
+        ));
+    }
+
+    #[test]
+    fn test_full_board_always_has_a_winner() {
+        for sides in [5, 6, 7, 8] {
+            let mut game = PolyYGame::new(sides, 3).unwrap();
+            while let Some(player) = game.next_player() {
+                let cells = game.available_cells();
+                let cell = cells[(cells.len() * 7 / 11) % cells.len()];
+                game.play(player, cell).unwrap();
+            }
+            assert!(game.check_game_over());
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{PlayerId, SwapMode};
+use serde::{Deserialize, Serialize};
+
+/// The rules a game of Y is played by.
+///
+/// Rules are chosen when a game is created with [`crate::GameY::with_rules`]
+/// and cannot change during the game. Every field has a default, so a rule set
+/// can be deserialized from a partial JSON object such as `{"swap_allowed": false}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRules {
+    /// Whether the second player may use the swap rule on their first move.
+    pub swap_allowed: bool,
+    /// How the opening stone is handled when the swap rule is used.
+    pub swap_mode: SwapMode,
+    /// The number of players, who take turns in increasing id order.
+    pub num_players: u32,
+    /// The player who makes the first move.
+    pub starting_player: PlayerId,
+    /// Whether the game is played misère: the player who connects the three
+    /// sides loses instead of winning.
+    pub misere: bool,
+    /// Whether moves played after the game has finished are rejected.
+    ///
+    /// When false, such moves are accepted and only logged.
+    pub strict_game_over: bool,
+    /// The largest board size allowed.
+    pub max_board_size: u32,
+}
+
+impl GameRules {
+    /// Largest board size allowed by the default rules.
+    pub const DEFAULT_MAX_BOARD_SIZE: u32 = 50;
+
+    /// Largest number of players a game can have.
+    pub const MAX_PLAYERS: u32 = 6;
+
+    /// Returns true if these are the default rules.
+    pub fn is_default(&self) -> bool {
+        *self == GameRules::default()
+    }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            swap_allowed: true,
+            swap_mode: SwapMode::default(),
+            num_players: 2,
+            starting_player: PlayerId::new(0),
+            misere: false,
+            strict_game_over: false,
+            max_board_size: Self::DEFAULT_MAX_BOARD_SIZE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let rules = GameRules::default();
+        assert!(rules.swap_allowed);
+        assert_eq!(rules.swap_mode, SwapMode::Transfer);
+        assert_eq!(rules.num_players, 2);
+        assert_eq!(rules.starting_player, PlayerId::new(0));
+        assert!(!rules.misere);
+        assert!(!rules.strict_game_over);
+        assert_eq!(rules.max_board_size, GameRules::DEFAULT_MAX_BOARD_SIZE);
+        assert!(rules.is_default());
+    }
+
+    #[test]
+    fn test_deserialize_partial_rules() {
+        let rules: GameRules =
+            serde_json::from_str(r#"{"swap_allowed": false, "starting_player": 1}"#).unwrap();
+        assert!(!rules.swap_allowed);
+        assert_eq!(rules.starting_player, PlayerId::new(1));
+        assert_eq!(rules.max_board_size, GameRules::DEFAULT_MAX_BOARD_SIZE);
+        assert!(!rules.is_default());
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let rules = GameRules {
+            swap_mode: SwapMode::Mirror,
+            misere: true,
+            strict_game_over: true,
+            ..GameRules::default()
+        };
+        let json = serde_json::to_string(&rules).unwrap();
+        assert!(json.contains("\"swap_mode\":\"mirror\""));
+        assert!(json.contains("\"misere\":true"));
+        let restored: GameRules = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, rules);
+    }
+}
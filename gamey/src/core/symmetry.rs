@@ -0,0 +1,67 @@
+/// One of the six symmetries of the triangular board.
+///
+/// Every symmetry permutes the three barycentric coordinates of a cell: the
+/// rotations cycle them and each reflection keeps one of them fixed while
+/// swapping the other two. See [`crate::Coordinates::transform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Leaves every cell where it is.
+    Identity,
+    /// Rotates the board by 120 degrees: (x, y, z) becomes (z, x, y).
+    Rotate120,
+    /// Rotates the board by 240 degrees: (x, y, z) becomes (y, z, x).
+    Rotate240,
+    /// Reflects the board across the axis through the top corner, keeping x:
+    /// (x, y, z) becomes (x, z, y).
+    ReflectX,
+    /// Reflects the board keeping y: (x, y, z) becomes (z, y, x).
+    ReflectY,
+    /// Reflects the board keeping z: (x, y, z) becomes (y, x, z).
+    ReflectZ,
+}
+
+impl Symmetry {
+    /// All the symmetries of the board, starting with [`Symmetry::Identity`].
+    pub const ALL: [Symmetry; 6] = [
+        Symmetry::Identity,
+        Symmetry::Rotate120,
+        Symmetry::Rotate240,
+        Symmetry::ReflectX,
+        Symmetry::ReflectY,
+        Symmetry::ReflectZ,
+    ];
+
+    /// Returns the symmetry that undoes this one.
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate120 => Symmetry::Rotate240,
+            Symmetry::Rotate240 => Symmetry::Rotate120,
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_contains_six_distinct_symmetries() {
+        let all: std::collections::HashSet<_> = Symmetry::ALL.iter().collect();
+        assert_eq!(all.len(), 6);
+        assert_eq!(Symmetry::ALL[0], Symmetry::Identity);
+    }
+
+    #[test]
+    fn test_inverse_of_inverse_is_itself() {
+        for symmetry in Symmetry::ALL {
+            assert_eq!(symmetry.inverse().inverse(), symmetry);
+        }
+    }
+
+    #[test]
+    fn test_rotations_are_inverse_of_each_other() {
+        assert_eq!(Symmetry::Rotate120.inverse(), Symmetry::Rotate240);
+        assert_eq!(Symmetry::ReflectY.inverse(), Symmetry::ReflectY);
+    }
+}
@@ -0,0 +1,981 @@
+//! The tetrahedral variant of Y, played inside a 3D tetrahedron.
+//!
+//! Cells are given by four barycentric coordinates `(a, b, c, d)` that add up
+//! to `size - 1`, the 3D counterpart of [`Coordinates`](crate::Coordinates).
+//! A cell lies on face A when `a == 0`, and likewise for the other three
+//! faces. Two cells are neighbours when one coordinate is one higher and
+//! another one lower.
+//!
+//! # Winning
+//!
+//! The rule is the direct generalisation of Y: a player wins as soon as one
+//! of their connected groups touches all four faces. There is no other
+//! requirement on the shape of the group; in particular it does not need to
+//! branch; a path from one face to another that passes along the other two
+//! wins, just as a single bent line can win a game of Y. Whether a group
+//! branches is still tracked, since clients display it.
+
+use crate::core::SetIdx;
+use crate::core::bitboard::Bitboard;
+use crate::{
+    GameStatus, GameYError, PLAYER_SYMBOLS, PlayerId, Result, TETRA_LAYER_SEPARATOR, TetraYEN,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::path::Path;
+
+/// Names of the four faces of the tetrahedron, indexed by face.
+pub const TETRA_FACES: [&str; 4] = ["A", "B", "C", "D"];
+
+/// Barycentric coordinates `(a, b, c, d)` of a cell of the tetrahedral board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TetraCoordinates {
+    a: u32,
+    b: u32,
+    c: u32,
+    d: u32,
+}
+
+impl TetraCoordinates {
+    /// Creates new coordinates. They are only on a board of size `n` if they add up to `n - 1`.
+    pub fn new(a: u32, b: u32, c: u32, d: u32) -> Self {
+        Self { a, b, c, d }
+    }
+
+    /// Returns the a coordinate.
+    pub fn a(&self) -> u32 {
+        self.a
+    }
+
+    /// Returns the b coordinate.
+    pub fn b(&self) -> u32 {
+        self.b
+    }
+
+    /// Returns the c coordinate.
+    pub fn c(&self) -> u32 {
+        self.c
+    }
+
+    /// Returns the d coordinate.
+    pub fn d(&self) -> u32 {
+        self.d
+    }
+
+    /// Returns the four coordinates as an array, in `a, b, c, d` order.
+    pub fn values(&self) -> [u32; 4] {
+        [self.a, self.b, self.c, self.d]
+    }
+
+    /// Returns which of the four faces the cell lies on, indexed as [`TETRA_FACES`].
+    pub fn touched_faces(&self) -> [bool; 4] {
+        self.values().map(|value| value == 0)
+    }
+
+    fn from_values(values: [u32; 4]) -> Self {
+        Self::new(values[0], values[1], values[2], values[3])
+    }
+}
+
+impl Display for TetraCoordinates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.a, self.b, self.c, self.d)
+    }
+}
+
+/// An edge between two neighbouring stones of the same player, with the
+/// smaller coordinates first.
+pub type TetraEdge = (TetraCoordinates, TetraCoordinates);
+
+/// A connected group of stones of one player.
+#[derive(Debug, Clone, Default)]
+pub struct TetraComponentInfo {
+    nodes: Vec<TetraCoordinates>,
+    faces: [bool; 4],
+    has_branch: bool,
+    path_edges: Vec<TetraEdge>,
+}
+
+impl TetraComponentInfo {
+    /// Returns the stones of the group.
+    pub fn nodes(&self) -> &[TetraCoordinates] {
+        &self.nodes
+    }
+
+    /// Returns which faces the group touches, indexed as [`TETRA_FACES`].
+    pub fn faces(&self) -> [bool; 4] {
+        self.faces
+    }
+
+    /// Returns the number of faces the group touches.
+    pub fn face_count(&self) -> usize {
+        self.faces.iter().filter(|value| **value).count()
+    }
+
+    /// Returns true if some stone of the group has three or more neighbours in it.
+    pub fn has_branch(&self) -> bool {
+        self.has_branch
+    }
+
+    /// Returns every edge between neighbouring stones of the group.
+    pub fn path_edges(&self) -> &[TetraEdge] {
+        &self.path_edges
+    }
+}
+
+// Connected group in the union-find structure, the tetrahedral counterpart
+// of `PlayerSet`. Only the fields of a root are meaningful.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TetraSet {
+    parent: SetIdx,
+    // One bit per face touched, bit `i` for face `TETRA_FACES[i]`.
+    faces: u8,
+    // Number of stones in the group.
+    stones: u32,
+    // Whether some stone of the group has three or more neighbours in it.
+    branched: bool,
+}
+
+impl TetraSet {
+    fn singleton(idx: SetIdx, coords: &TetraCoordinates) -> Self {
+        Self {
+            parent: idx,
+            faces: face_mask(coords),
+            stones: 1,
+            branched: false,
+        }
+    }
+
+    fn face_count(&self) -> u32 {
+        self.faces.count_ones()
+    }
+
+    /// Key ordering groups by how close they are to winning: more faces
+    /// first, then branching as a tie-break, then more stones.
+    fn rank(&self) -> (u32, bool, u32) {
+        (self.face_count(), self.branched, self.stones)
+    }
+}
+
+/// The state of a two-player game on the tetrahedral board.
+///
+/// Groups are tracked incrementally with a disjoint-set structure, so
+/// placing a stone only looks at its neighbours.
+#[derive(Debug, Clone)]
+pub struct TetraGame {
+    size: u32,
+
+    // Every cell of the board, in index order.
+    cells: Vec<TetraCoordinates>,
+
+    // Index of the first cell with each value of `a`.
+    layer_offsets: Vec<usize>,
+
+    // Stones of each player, indexed by player id and then by cell index.
+    stones: [Bitboard; 2],
+
+    // Union-find sets indexed by cell. Empty cells hold a singleton set.
+    sets: Vec<TetraSet>,
+
+    // Number of neighbours of each stone that belong to the same player.
+    friendly_neighbors: Vec<u8>,
+
+    status: GameStatus,
+}
+
+impl TetraGame {
+    /// Smallest board size; smaller sizes are raised to it.
+    pub const MIN_SIZE: u32 = 2;
+
+    /// Largest board size. The board holds `size³ / 6` cells or so, and a
+    /// board of this size already has a few thousand of them.
+    pub const MAX_SIZE: u32 = 30;
+
+    /// Creates an empty board with `size` cells along each edge. Player 0 moves first.
+    ///
+    /// Returns an error if `size` is larger than [`Self::MAX_SIZE`].
+    pub fn new(size: u32) -> Result<Self> {
+        if size > Self::MAX_SIZE {
+            return Err(GameYError::InvalidTetraSize {
+                size,
+                max: Self::MAX_SIZE,
+            });
+        }
+        let size = size.max(Self::MIN_SIZE);
+        let total = size - 1;
+        let mut cells = Vec::new();
+        let mut layer_offsets = Vec::with_capacity(size as usize);
+        for a in 0..=total {
+            layer_offsets.push(cells.len());
+            for b in 0..=total - a {
+                for c in 0..=total - a - b {
+                    cells.push(TetraCoordinates::new(a, b, c, total - a - b - c));
+                }
+            }
+        }
+        let sets = cells
+            .iter()
+            .enumerate()
+            .map(|(idx, coords)| TetraSet::singleton(idx, coords))
+            .collect();
+        let total_cells = cells.len();
+        Ok(Self {
+            size,
+            cells,
+            layer_offsets,
+            stones: [Bitboard::new(total_cells), Bitboard::new(total_cells)],
+            sets,
+            friendly_neighbors: vec![0; total_cells],
+            status: GameStatus::Ongoing {
+                next_player: PlayerId::new(0),
+            },
+        })
+    }
+
+    /// Returns the number of cells along each edge of the board.
+    pub fn board_size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the current game status.
+    pub fn status(&self) -> &GameStatus {
+        &self.status
+    }
+
+    /// Returns true if the game has ended.
+    pub fn check_game_over(&self) -> bool {
+        matches!(self.status, GameStatus::Finished { .. })
+    }
+
+    /// Returns the player who should make the next move, or None if the game is over.
+    pub fn next_player(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Ongoing { next_player } => Some(next_player),
+            GameStatus::Finished { .. } => None,
+        }
+    }
+
+    /// Returns the winner, if the game is over.
+    pub fn winner(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Finished { winner } => Some(winner),
+            GameStatus::Ongoing { .. } => None,
+        }
+    }
+
+    /// Returns true if `coords` is a cell of this board.
+    pub fn is_valid_coord(&self, coords: &TetraCoordinates) -> bool {
+        coords.values().iter().sum::<u32>() == self.size - 1
+    }
+
+    /// Returns every cell of the board, sorted by coordinates.
+    pub fn all_cells(&self) -> Vec<TetraCoordinates> {
+        self.cells.clone()
+    }
+
+    /// Returns the empty cells, sorted by coordinates.
+    pub fn available_moves(&self) -> Vec<TetraCoordinates> {
+        (0..self.cells.len())
+            .filter(|&idx| self.owner(idx).is_none())
+            .map(|idx| self.cells[idx])
+            .collect()
+    }
+
+    /// Returns the player whose stone is at `coords`, if any.
+    pub fn player_at(&self, coords: &TetraCoordinates) -> Option<PlayerId> {
+        self.index(coords).and_then(|idx| self.owner(idx))
+    }
+
+    /// Returns the neighbours of `coords` on the board.
+    pub fn neighbors(&self, coords: &TetraCoordinates) -> Vec<TetraCoordinates> {
+        let values = coords.values();
+        let mut neighbors = Vec::with_capacity(12);
+
+        for from in 0..4 {
+            for to in 0..4 {
+                if from == to || values[from] == 0 {
+                    continue;
+                }
+
+                let mut next = values;
+                next[from] -= 1;
+                next[to] += 1;
+                let next_coords = TetraCoordinates::from_values(next);
+
+                if self.is_valid_coord(&next_coords) {
+                    neighbors.push(next_coords);
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Returns which faces the group of `player` closest to winning touches,
+    /// indexed as [`TETRA_FACES`].
+    pub fn connected_faces(&self, player: PlayerId) -> [bool; 4] {
+        let faces = self
+            .best_root(player)
+            .map_or(0, |root| self.sets[root].faces);
+        std::array::from_fn(|face| faces & (1 << face) != 0)
+    }
+
+    /// Returns true if the group of `player` closest to winning branches.
+    pub fn has_branch(&self, player: PlayerId) -> bool {
+        self.best_root(player)
+            .is_some_and(|root| self.sets[root].branched)
+    }
+
+    /// Returns the group of `player` that is closest to winning: the one
+    /// touching most faces, then branching, then the largest.
+    pub fn best_component_for_player(&self, player: PlayerId) -> TetraComponentInfo {
+        let Some(root) = self.best_root(player) else {
+            return TetraComponentInfo::default();
+        };
+
+        let nodes = self.stones[player.id() as usize]
+            .iter()
+            .filter(|&idx| self.find_root(idx) == root)
+            .map(|idx| self.cells[idx])
+            .collect::<Vec<_>>();
+
+        let mut path_edges = Vec::new();
+        for node in &nodes {
+            for neighbor in self.neighbors(node) {
+                if *node < neighbor && self.player_at(&neighbor) == Some(player) {
+                    path_edges.push((*node, neighbor));
+                }
+            }
+        }
+
+        TetraComponentInfo {
+            nodes,
+            faces: self.connected_faces(player),
+            has_branch: self.sets[root].branched,
+            path_edges,
+        }
+    }
+
+    /// Returns true if a stone of `player` on the empty cell `coords` would
+    /// give them a group touching all four faces.
+    ///
+    /// Returns false for players other than 0 and 1.
+    pub fn completes_win(&self, coords: &TetraCoordinates, player: PlayerId) -> bool {
+        let Some(stones) = self.player_stones(player) else {
+            return false;
+        };
+        let faces = self
+            .neighbors(coords)
+            .iter()
+            .filter_map(|neighbor| self.index(neighbor))
+            .filter(|&idx| stones.contains(idx))
+            .fold(face_mask(coords), |faces, idx| {
+                faces | self.sets[self.find_root(idx)].faces
+            });
+        faces == 0b1111
+    }
+
+    /// Places a stone of `player` on `coords`.
+    ///
+    /// Returns an error if the game is over, if it is not `player`'s turn or
+    /// if the cell is off the board or occupied.
+    pub fn place(&mut self, coords: TetraCoordinates, player: PlayerId) -> Result<()> {
+        let next_player = match self.status {
+            GameStatus::Finished { winner } => {
+                return Err(GameYError::TetraGameFinished { winner });
+            }
+            GameStatus::Ongoing { next_player } => next_player,
+        };
+
+        if player != next_player {
+            return Err(GameYError::InvalidPlayerTurn {
+                expected: next_player,
+                found: player,
+            });
+        }
+
+        let Some(idx) = self.index(&coords) else {
+            return Err(GameYError::InvalidTetraCoordinates {
+                coordinates: coords,
+                board_size: self.size,
+            });
+        };
+
+        if self.owner(idx).is_some() {
+            return Err(GameYError::TetraCellOccupied {
+                coordinates: coords,
+            });
+        }
+
+        let root = self.add_stone(idx, player);
+        self.status = if self.sets[root].face_count() == 4 {
+            GameStatus::Finished { winner: player }
+        } else {
+            GameStatus::Ongoing {
+                next_player: PlayerId::new(1 - player.id()),
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Loads a game from a file in [`TetraYEN`] format.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let filename = path.as_ref().display().to_string();
+        let file_content = std::fs::read_to_string(path).map_err(|e| GameYError::IoError {
+            message: format!("Failed to read file: {}", filename),
+            error: e.to_string(),
+        })?;
+        let yen: TetraYEN =
+            serde_json::from_str(&file_content).map_err(|e| GameYError::SerdeError { error: e })?;
+        TetraGame::try_from(yen)
+    }
+
+    /// Saves the game state to a file in [`TetraYEN`] format.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let yen: TetraYEN = self.into();
+        let json_content =
+            serde_json::to_string_pretty(&yen).map_err(|e| GameYError::SerdeError { error: e })?;
+        let filename = path.as_ref().display().to_string();
+        std::fs::write(path, json_content).map_err(|e| GameYError::IoError {
+            message: format!("Failed to write file: {}", filename),
+            error: e.to_string(),
+        })?;
+        Ok(())
+    }
+
+    /// Puts a stone of `player` on the empty cell `idx` and merges it with
+    /// its neighbours, without checking turns. Returns the root of its group.
+    fn add_stone(&mut self, idx: usize, player: PlayerId) -> SetIdx {
+        let coords = self.cells[idx];
+        let player_idx = player.id() as usize;
+        self.stones[player_idx].insert(idx);
+
+        let mut branched = false;
+        for neighbor in self.neighbors(&coords) {
+            let Some(neighbor_idx) = self.index(&neighbor) else {
+                continue;
+            };
+            if !self.stones[player_idx].contains(neighbor_idx) {
+                continue;
+            }
+            self.friendly_neighbors[idx] += 1;
+            self.friendly_neighbors[neighbor_idx] += 1;
+            branched |= self.friendly_neighbors[neighbor_idx] >= 3;
+            self.union(idx, neighbor_idx);
+        }
+        branched |= self.friendly_neighbors[idx] >= 3;
+
+        let root = self.find(idx);
+        self.sets[root].branched |= branched;
+        root
+    }
+
+    /// Returns the index of `coords`, or None if they are not on the board.
+    pub(crate) fn index(&self, coords: &TetraCoordinates) -> Option<usize> {
+        if !self.is_valid_coord(coords) {
+            return None;
+        }
+        let (b, c) = (coords.b as usize, coords.c as usize);
+        // Rows of the layer hold `remaining + 1`, `remaining`, ... cells
+        let remaining = (self.size - 1 - coords.a) as usize;
+        let row_offset = b * (remaining + 1) - b * b.saturating_sub(1) / 2;
+        Some(self.layer_offsets[coords.a as usize] + row_offset + c)
+    }
+
+    /// Returns the player whose stone is on the cell `idx`, if any.
+    fn owner(&self, idx: usize) -> Option<PlayerId> {
+        self.stones
+            .iter()
+            .position(|stones| stones.contains(idx))
+            .map(|player| PlayerId::new(player as u32))
+    }
+
+    /// Returns the stones of `player`, or None for players other than 0 and 1.
+    fn player_stones(&self, player: PlayerId) -> Option<&Bitboard> {
+        self.stones.get(player.id() as usize)
+    }
+
+    /// Returns the root of the group of `player` closest to winning.
+    fn best_root(&self, player: PlayerId) -> Option<SetIdx> {
+        let mut best: Option<SetIdx> = None;
+        for idx in self.player_stones(player)?.iter() {
+            let root = self.find_root(idx);
+            if best.is_none_or(|best| self.sets[root].rank() > self.sets[best].rank()) {
+                best = Some(root);
+            }
+        }
+        best
+    }
+
+    /// Disjoint Set Union 'Find' without path compression, for read-only queries.
+    fn find_root(&self, mut i: SetIdx) -> SetIdx {
+        while self.sets[i].parent != i {
+            i = self.sets[i].parent;
+        }
+        i
+    }
+
+    /// Disjoint Set Union 'Find' with path compression
+    fn find(&mut self, i: SetIdx) -> SetIdx {
+        let parent = self.sets[i].parent;
+        if parent == i {
+            i
+        } else {
+            let root = self.find(parent);
+            self.sets[i].parent = root;
+            root
+        }
+    }
+
+    /// Disjoint Set Union 'Union' by size, merging faces, stone counts and branching.
+    fn union(&mut self, i: SetIdx, j: SetIdx) {
+        let mut root_i = self.find(i);
+        let mut root_j = self.find(j);
+        if root_i == root_j {
+            return;
+        }
+        if self.sets[root_i].stones > self.sets[root_j].stones {
+            std::mem::swap(&mut root_i, &mut root_j);
+        }
+        let absorbed = self.sets[root_i].clone();
+        let root = &mut self.sets[root_j];
+        root.faces |= absorbed.faces;
+        root.stones += absorbed.stones;
+        root.branched |= absorbed.branched;
+        self.sets[root_i].parent = root_j;
+    }
+}
+
+impl TryFrom<TetraYEN> for TetraGame {
+    type Error = GameYError;
+
+    fn try_from(yen: TetraYEN) -> Result<Self> {
+        if yen.players().len() != 2 {
+            return Err(GameYError::InvalidNumPlayers {
+                num_players: yen.players().len() as u32,
+                expected: 2,
+            });
+        }
+        if yen.turn() >= 2 {
+            return Err(GameYError::InvalidPlayer {
+                player: PlayerId::new(yen.turn()),
+                num_players: 2,
+            });
+        }
+        // The size and the layout are checked before the board is allocated
+        if yen.size() > TetraGame::MAX_SIZE {
+            return Err(GameYError::InvalidTetraSize {
+                size: yen.size(),
+                max: TetraGame::MAX_SIZE,
+            });
+        }
+        let size = yen.size().max(TetraGame::MIN_SIZE);
+        let layers: Vec<&str> = yen.layout().split(TETRA_LAYER_SEPARATOR).collect();
+        if layers.len() as u32 != size {
+            return Err(GameYError::InvalidTetraLayout {
+                expected: size,
+                found: layers.len() as u32,
+            });
+        }
+        let mut game = TetraGame::new(size)?;
+        let total = game.size - 1;
+        for (layer, layer_str) in layers.iter().enumerate() {
+            let rows: Vec<&str> = layer_str.split('/').collect();
+            if rows.len() != layer + 1 {
+                return Err(GameYError::InvalidTetraLayoutLayer {
+                    layer: layer as u32,
+                    expected: layer as u32 + 1,
+                    found: rows.len() as u32,
+                });
+            }
+            for (row, row_str) in rows.iter().enumerate() {
+                let cells: Vec<char> = row_str.chars().collect();
+                if cells.len() != row + 1 {
+                    return Err(GameYError::InvalidTetraLayoutRow {
+                        layer: layer as u32,
+                        row: row as u32,
+                        expected: row as u32 + 1,
+                        found: cells.len() as u32,
+                    });
+                }
+                for (col, cell) in cells.iter().enumerate() {
+                    if *cell == '.' {
+                        continue;
+                    }
+                    let Some(player) = yen.players().iter().position(|symbol| symbol == cell)
+                    else {
+                        return Err(GameYError::InvalidCharInTetraLayout {
+                            char: *cell,
+                            layer,
+                            row,
+                            col,
+                        });
+                    };
+                    let (layer, row, col) = (layer as u32, row as u32, col as u32);
+                    let coords =
+                        TetraCoordinates::new(total - layer, layer - row, col, row - col);
+                    let idx = game.index(&coords).expect("layout cells are on the board");
+                    game.add_stone(idx, PlayerId::new(player as u32));
+                }
+            }
+        }
+        // Stones are placed in layout order, so a finished position is told
+        // apart by the groups alone. The player who just moved is checked first.
+        let turn = PlayerId::new(yen.turn());
+        let winner = [PlayerId::new(1 - turn.id()), turn]
+            .into_iter()
+            .find(|player| game.connected_faces(*player) == [true; 4]);
+        game.status = match winner {
+            Some(winner) => GameStatus::Finished { winner },
+            None => GameStatus::Ongoing { next_player: turn },
+        };
+        Ok(game)
+    }
+}
+
+impl From<&TetraGame> for TetraYEN {
+    fn from(game: &TetraGame) -> Self {
+        let total = game.size - 1;
+        let layers: Vec<String> = (0..game.size)
+            .map(|layer| {
+                let rows: Vec<String> = (0..=layer)
+                    .map(|row| {
+                        (0..=row)
+                            .map(|col| {
+                                let coords =
+                                    TetraCoordinates::new(total - layer, layer - row, col, row - col);
+                                match game.player_at(&coords) {
+                                    Some(player) => PLAYER_SYMBOLS[player.id() as usize],
+                                    None => '.',
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                rows.join("/")
+            })
+            .collect();
+        let turn = match game.status {
+            GameStatus::Finished { winner } => 1 - winner.id(),
+            GameStatus::Ongoing { next_player } => next_player.id(),
+        };
+        TetraYEN::new(
+            game.size,
+            turn,
+            PLAYER_SYMBOLS[..2].to_vec(),
+            layers.join(&TETRA_LAYER_SEPARATOR.to_string()),
+        )
+    }
+}
+
+/// Returns the bitmask of the faces `coords` lies on, bit `i` for face `TETRA_FACES[i]`.
+fn face_mask(coords: &TetraCoordinates) -> u8 {
+    coords
+        .touched_faces()
+        .iter()
+        .enumerate()
+        .filter(|(_, touched)| **touched)
+        .fold(0, |mask, (face, _)| mask | (1 << face))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn p(id: u32) -> PlayerId {
+        PlayerId::new(id)
+    }
+
+    fn t(a: u32, b: u32, c: u32, d: u32) -> TetraCoordinates {
+        TetraCoordinates::new(a, b, c, d)
+    }
+
+    #[test]
+    fn test_size_is_at_least_two() {
+        assert_eq!(TetraGame::new(0).unwrap().board_size(), 2);
+        assert_eq!(TetraGame::new(5).unwrap().board_size(), 5);
+    }
+
+    #[test]
+    fn test_sizes_above_max_are_rejected() {
+        assert!(TetraGame::new(TetraGame::MAX_SIZE).is_ok());
+        assert!(matches!(
+            TetraGame::new(100_000),
+            Err(GameYError::InvalidTetraSize {
+                size: 100_000,
+                max: TetraGame::MAX_SIZE
+            })
+        ));
+    }
+
+    #[test]
+    fn test_queries_for_unknown_players_are_empty() {
+        let mut game = TetraGame::new(3).unwrap();
+        game.place(TetraCoordinates::new(1, 1, 0, 0), PlayerId::new(0))
+            .unwrap();
+        let stranger = PlayerId::new(2);
+        assert_eq!(game.connected_faces(stranger), [false; 4]);
+        assert!(!game.has_branch(stranger));
+        assert!(game.best_component_for_player(stranger).nodes().is_empty());
+        assert!(!game.completes_win(&TetraCoordinates::new(2, 0, 0, 0), stranger));
+    }
+
+    #[test]
+    fn test_cell_count_is_tetrahedral_number() {
+        for size in 2..7 {
+            let game = TetraGame::new(size).unwrap();
+            let expected = size * (size + 1) * (size + 2) / 6;
+            assert_eq!(game.all_cells().len() as u32, expected);
+            assert_eq!(game.available_moves().len() as u32, expected);
+        }
+    }
+
+    #[test]
+    fn test_corner_and_inner_neighbors() {
+        let game = TetraGame::new(4).unwrap();
+        assert_eq!(game.neighbors(&t(3, 0, 0, 0)).len(), 3);
+        assert_eq!(game.neighbors(&t(1, 1, 1, 0)).len(), 9);
+        for cell in game.all_cells() {
+            for neighbor in game.neighbors(&cell) {
+                assert!(game.neighbors(&neighbor).contains(&cell));
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_matches_cell_order() {
+        for size in 2..7 {
+            let game = TetraGame::new(size).unwrap();
+            for (idx, coords) in game.all_cells().iter().enumerate() {
+                assert_eq!(game.index(coords), Some(idx));
+            }
+            assert_eq!(game.index(&t(size, 0, 0, 0)), None);
+        }
+    }
+
+    /// Groups of `player` found by flood fill, as (faces, branched, stones).
+    fn flood_fill_groups(game: &TetraGame, player: PlayerId) -> Vec<(u8, bool, u32)> {
+        let mut seen = std::collections::HashSet::new();
+        let mut groups = Vec::new();
+        for start in game.all_cells() {
+            if game.player_at(&start) != Some(player) || !seen.insert(start) {
+                continue;
+            }
+            let (mut faces, mut branched, mut stones) = (0, false, 0);
+            let mut stack = vec![start];
+            while let Some(current) = stack.pop() {
+                stones += 1;
+                faces |= face_mask(&current);
+                let friends: Vec<_> = game
+                    .neighbors(&current)
+                    .into_iter()
+                    .filter(|n| game.player_at(n) == Some(player))
+                    .collect();
+                branched |= friends.len() >= 3;
+                for friend in friends {
+                    if seen.insert(friend) {
+                        stack.push(friend);
+                    }
+                }
+            }
+            groups.push((faces, branched, stones));
+        }
+        groups
+    }
+
+    #[test]
+    fn test_incremental_groups_match_flood_fill() {
+        for size in [3, 4, 5] {
+            let mut game = TetraGame::new(size).unwrap();
+            let cells = game.all_cells();
+            let mut step = 0;
+            while let Some(player) = game.next_player() {
+                let available = game.available_moves();
+                if available.is_empty() {
+                    break;
+                }
+                step = (step + 7) % cells.len();
+                let coords = available[step % available.len()];
+                game.place(coords, player).unwrap();
+
+                for id in 0..2 {
+                    let player = p(id);
+                    let best = flood_fill_groups(&game, player).into_iter().max_by_key(
+                        |(faces, branched, stones)| (faces.count_ones(), *branched, *stones),
+                    );
+                    let expected_faces = best.map_or(0, |(faces, _, _)| faces.count_ones());
+                    let component = game.best_component_for_player(player);
+                    assert_eq!(component.face_count() as u32, expected_faces);
+                    assert_eq!(game.has_branch(player), best.is_some_and(|(_, b, _)| b));
+                    assert_eq!(
+                        component.nodes().len() as u32,
+                        best.map_or(0, |(_, _, stones)| stones)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_touched_faces() {
+        assert_eq!(t(0, 1, 0, 2).touched_faces(), [true, false, true, false]);
+        assert_eq!(t(1, 1, 1, 1).touched_faces(), [false; 4]);
+    }
+
+    #[test]
+    fn test_place_errors() {
+        let mut game = TetraGame::new(3).unwrap();
+        assert!(matches!(
+            game.place(t(2, 0, 0, 0), p(1)),
+            Err(GameYError::InvalidPlayerTurn { .. })
+        ));
+        assert!(matches!(
+            game.place(t(2, 1, 0, 0), p(0)),
+            Err(GameYError::InvalidTetraCoordinates { board_size: 3, .. })
+        ));
+        game.place(t(2, 0, 0, 0), p(0)).unwrap();
+        assert!(matches!(
+            game.place(t(2, 0, 0, 0), p(1)),
+            Err(GameYError::TetraCellOccupied { .. })
+        ));
+        assert_eq!(game.next_player(), Some(p(1)));
+    }
+
+    #[test]
+    fn test_component_tracks_faces_and_edges() {
+        let mut game = TetraGame::new(3).unwrap();
+        game.place(t(2, 0, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 0, 2), p(1)).unwrap();
+        game.place(t(1, 1, 0, 0), p(0)).unwrap();
+
+        let component = game.best_component_for_player(p(0));
+        assert_eq!(component.nodes().len(), 2);
+        assert_eq!(component.faces(), [false, true, true, true]);
+        assert_eq!(component.path_edges(), &[(t(1, 1, 0, 0), t(2, 0, 0, 0))]);
+        assert!(!component.has_branch());
+    }
+
+    #[test]
+    fn test_win_needs_four_faces() {
+        let mut game = TetraGame::new(3).unwrap();
+        for (player, coords) in [
+            (0, t(1, 1, 0, 0)),
+            (1, t(2, 0, 0, 0)),
+            (0, t(0, 1, 1, 0)),
+            (1, t(0, 2, 0, 0)),
+            (0, t(0, 1, 0, 1)),
+        ] {
+            game.place(coords, p(player)).unwrap();
+        }
+        // Face B is still missing
+        assert_eq!(game.connected_faces(p(0)), [true, false, true, true]);
+        assert!(!game.check_game_over());
+        game.place(t(0, 0, 2, 0), p(1)).unwrap();
+        assert!(!game.check_game_over());
+        game.place(t(1, 0, 1, 0), p(0)).unwrap();
+        assert_eq!(game.winner(), Some(p(0)));
+        assert!(matches!(
+            game.place(t(0, 0, 0, 2), p(1)),
+            Err(GameYError::TetraGameFinished { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unbranched_path_wins() {
+        let mut game = TetraGame::new(3).unwrap();
+        game.place(t(2, 0, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 0, 2), p(1)).unwrap();
+        game.place(t(1, 1, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 2, 0), p(1)).unwrap();
+        assert!(!game.check_game_over());
+        // (2,0,0,0) touches B, C and D; (0,1,1,0) adds A
+        game.place(t(0, 1, 1, 0), p(0)).unwrap();
+        assert_eq!(game.winner(), Some(p(0)));
+        assert!(!game.has_branch(p(0)));
+    }
+
+    #[test]
+    fn test_two_adjacent_stones_win_the_smallest_board() {
+        // Every cell of the size-2 board touches three faces and all four are neighbours
+        let mut game = TetraGame::new(2).unwrap();
+        game.place(t(1, 0, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 0, 1), p(1)).unwrap();
+        assert!(!game.check_game_over());
+        game.place(t(0, 1, 0, 0), p(0)).unwrap();
+        assert_eq!(game.winner(), Some(p(0)));
+    }
+
+    #[test]
+    fn test_completes_win() {
+        let mut game = TetraGame::new(3).unwrap();
+        game.place(t(2, 0, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 0, 2), p(1)).unwrap();
+        game.place(t(1, 1, 0, 0), p(0)).unwrap();
+        game.place(t(0, 0, 2, 0), p(1)).unwrap();
+        assert!(game.completes_win(&t(0, 1, 1, 0), p(0)));
+        assert!(!game.completes_win(&t(0, 1, 1, 0), p(1)));
+        assert!(!game.completes_win(&t(1, 0, 0, 1), p(0)));
+    }
+
+    /// Brute-force winner check: does some group of `player` touch all four faces?
+    fn brute_force_wins(game: &TetraGame, player: PlayerId) -> bool {
+        flood_fill_groups(game, player)
+            .iter()
+            .any(|(faces, _, _)| *faces == 0b1111)
+    }
+
+    // Property-based tests using proptest
+
+    proptest! {
+        /// Property: The game ends exactly when the mover first has a group touching all four faces.
+        #[test]
+        fn prop_win_matches_brute_force(
+            size in 2u32..=5,
+            picks in prop::collection::vec(any::<prop::sample::Index>(), 0..60),
+        ) {
+            let mut game = TetraGame::new(size).unwrap();
+            for pick in picks {
+                let Some(player) = game.next_player() else { break };
+                let available = game.available_moves();
+                if available.is_empty() {
+                    break;
+                }
+                game.place(*pick.get(&available), player).unwrap();
+
+                let opponent = p(1 - player.id());
+                prop_assert_eq!(game.winner() == Some(player), brute_force_wins(&game, player));
+                prop_assert!(!brute_force_wins(&game, opponent));
+            }
+        }
+
+        /// Property: The reported faces and size of the best group match a flood fill.
+        #[test]
+        fn prop_best_group_matches_flood_fill(
+            size in 2u32..=6,
+            picks in prop::collection::vec(any::<prop::sample::Index>(), 0..80),
+        ) {
+            let mut game = TetraGame::new(size).unwrap();
+            for pick in picks {
+                let Some(player) = game.next_player() else { break };
+                let available = game.available_moves();
+                if available.is_empty() {
+                    break;
+                }
+                game.place(*pick.get(&available), player).unwrap();
+            }
+            for id in 0..2 {
+                let best_faces = flood_fill_groups(&game, p(id))
+                    .iter()
+                    .map(|(faces, _, _)| faces.count_ones())
+                    .max()
+                    .unwrap_or(0);
+                let component = game.best_component_for_player(p(id));
+                prop_assert_eq!(component.face_count() as u32, best_faces);
+                let stones = component.nodes().len();
+                prop_assert!(component.path_edges().len() + 1 >= stones);
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@
 
 use thiserror::Error;
 
-use crate::{Coordinates, Movement, PlayerId};
+use crate::{Coordinates, HexCell, Movement, PlayerId, TetraCoordinates};
 
 /// Errors that can occur during Y game operations.
 ///
@@ -87,6 +87,239 @@ pub enum GameYError {
         found: PlayerId,
     },
 
+    /// The swap rule was invoked when it is not allowed.
+    #[error("Player {player} cannot swap: the swap is only allowed as the second player's first move")]
+    IllegalSwap {
+        /// The player who attempted the swap.
+        player: PlayerId,
+    },
+
+    /// The swap rule was invoked in a game whose rules do not allow it.
+    #[error("Player {player} cannot swap: the swap rule is disabled in this game")]
+    SwapNotAllowed {
+        /// The player who attempted the swap.
+        player: PlayerId,
+    },
+
+    /// A stone was placed on a cell that is not part of the board.
+    #[error("Cell {coordinates} is blocked")]
+    BlockedCell {
+        /// The coordinates of the blocked cell.
+        coordinates: Coordinates,
+    },
+
+    /// The board was set up (shape or handicap stones) after the game had started.
+    #[error("The board can only be set up before the first move")]
+    BoardNotEmpty,
+
+    /// More standard handicap stones were requested than the board has positions for.
+    #[error("Cannot place {count} handicap stones: this board has {max} handicap positions")]
+    TooManyHandicapStones {
+        /// The number of stones requested.
+        count: u32,
+        /// The number of standard handicap positions on the board.
+        max: u32,
+    },
+
+    /// A YEN handicap stone refers to a cell without a stone in the layout.
+    #[error("Handicap cell {coordinates} has no stone in the layout")]
+    HandicapCellEmpty {
+        /// The coordinates of the empty cell.
+        coordinates: Coordinates,
+    },
+
+    /// A YEN lists an eliminated player that is not part of the game.
+    #[error("Eliminated player {player} is not one of the {num_players} players")]
+    InvalidEliminatedPlayer {
+        /// The player index listed as eliminated.
+        player: u32,
+        /// The number of players in the game.
+        num_players: u32,
+    },
+
+    /// A YEN gives the turn to a player who is out of the game.
+    #[error("Player {player} has been eliminated and cannot have the turn")]
+    EliminatedPlayerTurn {
+        /// The eliminated player.
+        player: PlayerId,
+    },
+
+    /// A Poly-Y board was requested with an unsupported shape.
+    #[error(
+        "Poly-Y boards need between 5 and 64 sides and a radius between 1 and 50 (got {sides} sides, radius {radius})"
+    )]
+    InvalidPolygon {
+        /// The number of sides requested.
+        sides: u32,
+        /// The radius requested.
+        radius: u32,
+    },
+
+    /// A Poly-Y cell is not on the board.
+    #[error("Cell ({ring}, {position}) is not on the board")]
+    InvalidPolyCell {
+        /// The ring of the cell.
+        ring: u32,
+        /// The position of the cell in its ring.
+        position: u32,
+    },
+
+    /// A Poly-Y cell already holds a stone.
+    #[error("Cell ({ring}, {position}) is already occupied")]
+    PolyCellOccupied {
+        /// The ring of the cell.
+        ring: u32,
+        /// The position of the cell in its ring.
+        position: u32,
+    },
+
+    /// A stone was played in a Poly-Y game that has already finished.
+    #[error("The game is over: player {winner} won")]
+    PolyGameFinished {
+        /// The winner of the game.
+        winner: PlayerId,
+    },
+
+    /// A Hex board was requested with an unsupported size.
+    #[error("Hex boards need a size between 1 and {max}, got {size}")]
+    InvalidHexSize {
+        /// The size requested.
+        size: u32,
+        /// The largest size supported.
+        max: u32,
+    },
+
+    /// A Hex cell is not on the board.
+    #[error("Cell {cell} is not on a {size}x{size} Hex board")]
+    InvalidHexCell {
+        /// The cell that was requested.
+        cell: HexCell,
+        /// The size of the board.
+        size: u32,
+    },
+
+    /// A Hex cell already holds a stone.
+    #[error("Cell {cell} is already occupied")]
+    HexCellOccupied {
+        /// The occupied cell.
+        cell: HexCell,
+    },
+
+    /// A Hex cell name could not be parsed.
+    #[error("Invalid Hex cell '{notation}': expected a column letter and a row number, such as c4")]
+    InvalidHexNotation {
+        /// The text that failed to parse.
+        notation: String,
+    },
+
+    /// A stone was played in a Hex game that has already finished.
+    #[error("The game is over: player {winner} won")]
+    HexGameFinished {
+        /// The winner of the game.
+        winner: PlayerId,
+    },
+
+    /// A tetrahedral board was requested with an unsupported size.
+    #[error("Tetrahedral boards need a size of at most {max}, got {size}")]
+    InvalidTetraSize {
+        /// The size requested.
+        size: u32,
+        /// The largest size supported.
+        max: u32,
+    },
+
+    /// Tetrahedral coordinates do not add up to the board size minus one.
+    #[error(
+        "Coordinates {coordinates} are not on a tetrahedral board of size {board_size} (a + b + c + d must be {})",
+        board_size.saturating_sub(1)
+    )]
+    InvalidTetraCoordinates {
+        /// The coordinates that were requested.
+        coordinates: TetraCoordinates,
+        /// The size of the board.
+        board_size: u32,
+    },
+
+    /// A cell of the tetrahedral board already holds a stone.
+    #[error("Cell {coordinates} is already occupied")]
+    TetraCellOccupied {
+        /// The occupied cell.
+        coordinates: TetraCoordinates,
+    },
+
+    /// A stone was played in a tetrahedral game that has already finished.
+    #[error("The game is over: player {winner} won")]
+    TetraGameFinished {
+        /// The winner of the game.
+        winner: PlayerId,
+    },
+
+    /// Tetrahedral YEN layout has the wrong number of layers.
+    #[error("Invalid tetra layout: expected {expected} layers, found {found} layers")]
+    InvalidTetraLayout {
+        /// Expected number of layers.
+        expected: u32,
+        /// Actual number of layers found.
+        found: u32,
+    },
+
+    /// A layer of a tetrahedral YEN layout has the wrong number of rows.
+    #[error("Invalid tetra layout: expected {expected} rows, found {found} rows in layer {layer}")]
+    InvalidTetraLayoutLayer {
+        /// The layer with the error.
+        layer: u32,
+        /// Expected number of rows in the layer.
+        expected: u32,
+        /// Actual number of rows found.
+        found: u32,
+    },
+
+    /// A row of a tetrahedral YEN layout has the wrong number of cells.
+    #[error(
+        "Invalid tetra layout: expected {expected} cells, found {found} cells in layer {layer}, row {row}"
+    )]
+    InvalidTetraLayoutRow {
+        /// The layer with the error.
+        layer: u32,
+        /// The row of the layer with the error.
+        row: u32,
+        /// Expected number of cells in the row.
+        expected: u32,
+        /// Actual number of cells found.
+        found: u32,
+    },
+
+    /// Invalid character found in a tetrahedral YEN layout.
+    #[error("Invalid character '{char}' in tetra layout at layer {layer}, row {row}, column {col}")]
+    InvalidCharInTetraLayout {
+        /// The invalid character.
+        char: char,
+        /// Layer index where the character was found.
+        layer: usize,
+        /// Row index within the layer.
+        row: usize,
+        /// Column index within the row.
+        col: usize,
+    },
+
+    /// A movement or rule refers to a player that does not take part in the game.
+    #[error("Player {player} does not exist in a game of {num_players} players")]
+    InvalidPlayer {
+        /// The unknown player.
+        player: PlayerId,
+        /// The number of players in the game.
+        num_players: u32,
+    },
+
+    /// The board size exceeds the limit set by the game rules.
+    #[error("Board size {size} is larger than the maximum allowed size {max}")]
+    BoardTooLarge {
+        /// The requested board size.
+        size: u32,
+        /// The largest size allowed by the rules.
+        max: u32,
+    },
+
     /// Invalid number of players specified.
     #[error("Invalid number of players: {num_players}, expected {expected}")]
     InvalidNumPlayers {
@@ -116,6 +349,14 @@ pub enum GameYError {
         line: u32,
     },
 
+    /// Attempted to undo a move when no move has been played.
+    #[error("There is no move to undo")]
+    NothingToUndo,
+
+    /// Attempted to redo a move when no move has been undone.
+    #[error("There is no move to redo")]
+    NothingToRedo,
+
     /// Server operation failed.
     #[error("Server error: {message}")]
     ServerError {
@@ -198,6 +439,162 @@ mod tests {
         assert!(msg.contains("found player 1"));
     }
 
+    #[test]
+    fn test_illegal_swap_display() {
+        let err = GameYError::IllegalSwap {
+            player: PlayerId::new(0),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Player 0"));
+        assert!(msg.contains("swap"));
+    }
+
+    #[test]
+    fn test_swap_not_allowed_display() {
+        let err = GameYError::SwapNotAllowed {
+            player: PlayerId::new(1),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Player 1"));
+        assert!(msg.contains("disabled"));
+    }
+
+    #[test]
+    fn test_blocked_cell_display() {
+        let err = GameYError::BlockedCell {
+            coordinates: Coordinates::new(1, 0, 1),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("blocked"));
+    }
+
+    #[test]
+    fn test_too_many_handicap_stones_display() {
+        let err = GameYError::TooManyHandicapStones { count: 9, max: 7 };
+        let msg = format!("{}", err);
+        assert!(msg.contains("9 handicap stones"));
+        assert!(msg.contains("7 handicap positions"));
+    }
+
+    #[test]
+    fn test_invalid_polygon_display() {
+        let err = GameYError::InvalidPolygon { sides: 4, radius: 3 };
+        let msg = format!("{}", err);
+        assert!(msg.contains("4 sides"));
+        assert!(msg.contains("radius 3"));
+    }
+
+    #[test]
+    fn test_invalid_hex_cell_display() {
+        let err = GameYError::InvalidHexCell {
+            cell: HexCell::new(3, 0),
+            size: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("a4"));
+        assert!(msg.contains("3x3"));
+    }
+
+    #[test]
+    fn test_invalid_tetra_size_display() {
+        let err = GameYError::InvalidTetraSize { size: 100, max: 30 };
+        let msg = format!("{}", err);
+        assert!(msg.contains("at most 30"));
+        assert!(msg.contains("100"));
+    }
+
+    #[test]
+    fn test_invalid_tetra_coordinates_display() {
+        let err = GameYError::InvalidTetraCoordinates {
+            coordinates: TetraCoordinates::new(1, 1, 1, 1),
+            board_size: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("(1, 1, 1, 1)"));
+        assert!(msg.contains("must be 2"));
+    }
+
+    #[test]
+    fn test_invalid_tetra_layout_display() {
+        let err = GameYError::InvalidTetraLayout {
+            expected: 4,
+            found: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("expected 4 layers"));
+        assert!(msg.contains("found 3 layers"));
+    }
+
+    #[test]
+    fn test_invalid_tetra_layout_layer_display() {
+        let err = GameYError::InvalidTetraLayoutLayer {
+            layer: 2,
+            expected: 3,
+            found: 1,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("expected 3 rows"));
+        assert!(msg.contains("layer 2"));
+    }
+
+    #[test]
+    fn test_invalid_tetra_layout_row_display() {
+        let err = GameYError::InvalidTetraLayoutRow {
+            layer: 2,
+            row: 1,
+            expected: 2,
+            found: 4,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("found 4 cells"));
+        assert!(msg.contains("layer 2, row 1"));
+    }
+
+    #[test]
+    fn test_invalid_char_in_tetra_layout_display() {
+        let err = GameYError::InvalidCharInTetraLayout {
+            char: 'X',
+            layer: 3,
+            row: 1,
+            col: 0,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("'X'"));
+        assert!(msg.contains("layer 3, row 1, column 0"));
+    }
+
+    #[test]
+    fn test_invalid_player_display() {
+        let err = GameYError::InvalidPlayer {
+            player: PlayerId::new(3),
+            num_players: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Player 3"));
+        assert!(msg.contains("3 players"));
+    }
+
+    #[test]
+    fn test_board_too_large_display() {
+        let err = GameYError::BoardTooLarge { size: 80, max: 50 };
+        let msg = format!("{}", err);
+        assert!(msg.contains("80"));
+        assert!(msg.contains("50"));
+    }
+
+    #[test]
+    fn test_eliminated_player_errors_display() {
+        let err = GameYError::InvalidEliminatedPlayer {
+            player: 4,
+            num_players: 3,
+        };
+        assert!(format!("{}", err).contains("4 is not one of the 3 players"));
+        let err = GameYError::EliminatedPlayerTurn {
+            player: PlayerId::new(2),
+        };
+        assert!(format!("{}", err).contains("Player 2 has been eliminated"));
+    }
+
     #[test]
     fn test_invalid_num_players_display() {
         let err = GameYError::InvalidNumPlayers {
@@ -243,6 +640,18 @@ mod tests {
         assert!(msg.contains("Failed to bind to port 3000"));
     }
 
+    #[test]
+    fn test_nothing_to_undo_display() {
+        let msg = format!("{}", GameYError::NothingToUndo);
+        assert!(msg.contains("undo"));
+    }
+
+    #[test]
+    fn test_nothing_to_redo_display() {
+        let msg = format!("{}", GameYError::NothingToRedo);
+        assert!(msg.contains("redo"));
+    }
+
     #[test]
     fn test_error_is_debug() {
         let err = GameYError::IoError {
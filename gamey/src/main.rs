@@ -2,26 +2,55 @@ use actix_web::{web, App, HttpServer, HttpResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use gamey::GameY;
-use gamey::PlayerId;
 use gamey::YEN;
 use gamey::core::coord::Coordinates;
 use gamey::core::movement::Movement;
 use gamey::core::game::GameStatus;
 use std::convert::TryFrom;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
-mod bot;
-use crate::bot::{RandomBot, IntermediateBot, HardBot, YBotRegistry, YBot};
+use std::time::Duration;
+use gamey::{GameAction, GameRules, GameYError, PlayerId, RandomBot, IntermediateBot, HardBot, MctsBot, SearchBot, SearchBudget, YBotRegistry};
+use gamey::{TETRA_FACES, TetraBotRegistry, TetraCoordinates, TetraDistanceBot, TetraEdge, TetraGame, TetraPlayoutBot, TetraRandomBot, TetraYEN};
+use gamey::{HexBotRegistry, HexCell, HexDistanceBot, HexGame, HexPlayoutBot, HexRandomBot};
+
+/// Tamaño máximo de las partidas tetraédricas del servidor, el mayor que
+/// ofrece el servicio de juego. Los bots tardan demasiado en tableros mayores.
+const TETRA_SESSION_MAX_SIZE: u32 = 6;
 
 /* STRUCTS (lo que recibimos)*/
 
 /// Estructura para la solicitud de iniciar un juego.
-/// Contiene el tamaño del tablero y el ID del juego.
+/// Contiene el tamaño del tablero, el ID del juego y, opcionalmente, las reglas
+/// y la forma del tablero.
 #[derive(Debug, Deserialize)]
 struct StartGameRequest {
     board_size: u32,
     game_id: String,
+    /// Reglas de la partida. Los campos omitidos toman su valor por defecto.
+    #[serde(default)]
+    rules: GameRules,
+    /// Casillas bloqueadas, que no forman parte del tablero.
+    #[serde(default)]
+    blocked: Vec<Coordinates>,
+    /// Número de filas que se recortan en cada esquina del tablero.
+    #[serde(default)]
+    truncated_corners: u32,
+    /// Piedras de hándicap colocadas antes de empezar la partida.
+    #[serde(default)]
+    handicap: Option<HandicapRequest>,
+}
+
+/// Piedras de hándicap para uno de los jugadores.
+/// Si se indican `stones` se usan esas posiciones; si no, se colocan `count`
+/// piedras en las posiciones estándar.
+#[derive(Debug, Deserialize)]
+struct HandicapRequest {
+    player: u32,
+    #[serde(default)]
+    count: u32,
+    #[serde(default)]
+    stones: Vec<Coordinates>,
 }
 
 /// Estructura para la solicitud de movimiento.
@@ -43,10 +72,21 @@ struct EndGameRequest {
 }
 
 /// Estructura para la solicitud de movimiento del bot.
-/// Contiene el ID del juego.
+/// Contiene el ID del juego y, opcionalmente, el tiempo que le queda al bot en
+/// el reloj.
 #[derive(Debug, Deserialize)]
 struct BotMoveRequest {
     game_id: String,
+    /// Tiempo restante en el reloj del bot, en milisegundos.
+    #[serde(default)]
+    time_ms: Option<u64>,
+}
+
+/// Estructura para las solicitudes de deshacer y rehacer movimientos.
+/// Contiene el ID del juego.
+#[derive(Debug, Deserialize)]
+struct HistoryRequest {
+    game_id: String,
 }
 
 /// Parámetros de la query del endpoint público /play.
@@ -57,12 +97,26 @@ struct PlayQuery {
     position: String,
     /// Identificador del bot a usar. Por defecto: hard_bot.
     bot_id: Option<String>,
+    /// Tiempo restante en el reloj del bot, en milisegundos. Sin límite si se omite.
+    time_ms: Option<u64>,
 }
+/// Parámetros de la query del endpoint de análisis de posiciones.
+#[derive(Debug, Deserialize)]
+struct AnalyzeQuery {
+    /// Número máximo de jugadas a devolver. Por defecto: 5.
+    count: Option<usize>,
+}
+
+/// Estructura para la solicitud de iniciar un juego tetraédrico.
+/// Contiene el tamaño del tablero y el ID del juego.
 #[derive(Debug, Deserialize)]
 struct TetraStartRequest {
     size: u32,
+    game_id: String,
 }
 
+/// Estructura para la solicitud de movimiento en el juego tetraédrico.
+/// Contiene las coordenadas, el jugador que hace el movimiento y el ID del juego.
 #[derive(Debug, Deserialize)]
 struct TetraMoveRequest {
     a: i32,
@@ -70,313 +124,54 @@ struct TetraMoveRequest {
     c: i32,
     d: i32,
     player: u32,
+    game_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct TetraCellResponse {
-    a: u32,
-    b: u32,
-    c: u32,
-    d: u32,
-    player: Option<u32>,
+/// Estructura para la solicitud de restaurar un juego tetraédrico.
+/// Contiene la posición en formato TetraYEN y el ID del juego.
+#[derive(Debug, Deserialize)]
+struct TetraRestoreRequest {
+    position: TetraYEN,
+    game_id: String,
 }
 
-#[derive(Debug, Clone)]
-struct TetraGame {
+/// Estructura para la solicitud de iniciar un juego de Hex.
+/// Contiene el tamaño del rombo y el ID del juego.
+#[derive(Debug, Deserialize)]
+struct HexStartRequest {
     size: u32,
-    cells: HashMap<(u32, u32, u32, u32), u32>,
-    next_player: u32,
-    winner: Option<u32>,
-}
-
-impl TetraGame {
-    fn collect_moves_for_prefix(
-        &self,
-        total: u32,
-        a: u32,
-        b: u32,
-        moves: &mut Vec<(u32, u32, u32, u32)>,
-    ) {
-        for c in 0..=total - a - b {
-            let d = total - a - b - c;
-            let coord = (a, b, c, d);
-            if !self.cells.contains_key(&coord) {
-                moves.push(coord);
-            }
-        }
-    }
-
-    fn collect_moves_for_a(
-        &self,
-        total: u32,
-        a: u32,
-        moves: &mut Vec<(u32, u32, u32, u32)>,
-    ) {
-        for b in 0..=total - a {
-            self.collect_moves_for_prefix(total, a, b, moves);
-        }
-    }
-
-    fn merge_faces(into: &mut [bool; 4], from: [bool; 4]) {
-        for idx in 0..4 {
-            into[idx] = into[idx] || from[idx];
-        }
-    }
-
-    fn collect_component_nodes(
-        &self,
-        start: (u32, u32, u32, u32),
-        player: u32,
-        global_visited: &mut HashSet<(u32, u32, u32, u32)>,
-    ) -> (Vec<(u32, u32, u32, u32)>, [bool; 4]) {
-        let mut stack = vec![start];
-        let mut component_nodes = Vec::new();
-        let mut faces = [false, false, false, false];
-
-        global_visited.insert(start);
-
-        while let Some(current) = stack.pop() {
-            component_nodes.push(current);
-            Self::merge_faces(&mut faces, Self::touched_faces(current));
-
-            for neighbor in self.neighbors(current) {
-                if global_visited.contains(&neighbor) {
-                    continue;
-                }
-
-                if self.cells.get(&neighbor) == Some(&player) {
-                    global_visited.insert(neighbor);
-                    stack.push(neighbor);
-                }
-            }
-        }
-
-        (component_nodes, faces)
-    }
-
-    fn normalized_edge(
-        node_a: (u32, u32, u32, u32),
-        node_b: (u32, u32, u32, u32),
-    ) -> ((u32, u32, u32, u32), (u32, u32, u32, u32)) {
-        if node_a <= node_b {
-            (node_a, node_b)
-        } else {
-            (node_b, node_a)
-        }
-    }
-
-    fn build_component_edges(
-        &self,
-        component_nodes: &[(u32, u32, u32, u32)],
-    ) -> (bool, Vec<((u32, u32, u32, u32), (u32, u32, u32, u32))>) {
-        let node_set = component_nodes.iter().copied().collect::<HashSet<_>>();
-        let mut has_branch = false;
-        let mut path_edges = Vec::new();
-        let mut seen_edges = HashSet::new();
-
-        for node in component_nodes {
-            let player_neighbors = self
-                .neighbors(*node)
-                .into_iter()
-                .filter(|neighbor| node_set.contains(neighbor))
-                .collect::<Vec<_>>();
-
-            if player_neighbors.len() >= 3 {
-                has_branch = true;
-            }
-
-            for neighbor in player_neighbors {
-                let edge = Self::normalized_edge(*node, neighbor);
-                if seen_edges.insert(edge) {
-                    path_edges.push(edge);
-                }
-            }
-        }
-
-        (has_branch, path_edges)
-    }
-
-    fn new(size: u32) -> Self {
-        Self {
-            size: size.max(2),
-            cells: HashMap::new(),
-            next_player: 0,
-            winner: None,
-        }
-    }
-
-    fn is_valid_coord(&self, coord: (u32, u32, u32, u32)) -> bool {
-        coord.0 + coord.1 + coord.2 + coord.3 == self.size.saturating_sub(1)
-    }
-
-    fn available_moves(&self) -> Vec<(u32, u32, u32, u32)> {
-        let mut moves = Vec::new();
-        let total = self.size.saturating_sub(1);
-        for a in 0..=total {
-            self.collect_moves_for_a(total, a, &mut moves);
-        }
-        moves
-    }
-
-    fn board_response(&self) -> Vec<TetraCellResponse> {
-        let mut cells = self
-            .available_moves()
-            .into_iter()
-            .map(|(a, b, c, d)| TetraCellResponse {
-                a,
-                b,
-                c,
-                d,
-                player: self.cells.get(&(a, b, c, d)).copied(),
-            })
-            .collect::<Vec<_>>();
-
-        for (coord, player) in &self.cells {
-            if !cells.iter().any(|cell| (cell.a, cell.b, cell.c, cell.d) == *coord) {
-                cells.push(TetraCellResponse {
-                    a: coord.0,
-                    b: coord.1,
-                    c: coord.2,
-                    d: coord.3,
-                    player: Some(*player),
-                });
-            }
-        }
-
-        cells.sort_by_key(|cell| (cell.a, cell.b, cell.c, cell.d));
-        cells
-    }
-
-    fn neighbors(&self, coord: (u32, u32, u32, u32)) -> Vec<(u32, u32, u32, u32)> {
-        let mut set = HashSet::new();
-        let values = [coord.0, coord.1, coord.2, coord.3];
-
-        for from in 0..4 {
-            for to in 0..4 {
-                if from == to || values[from] == 0 {
-                    continue;
-                }
-
-                let mut next = values;
-                next[from] -= 1;
-                next[to] += 1;
-                let next_coord = (next[0], next[1], next[2], next[3]);
-
-                if self.is_valid_coord(next_coord) {
-                    set.insert(next_coord);
-                }
-            }
-        }
-
-        set.into_iter().collect()
-    }
-
-    fn touched_faces(coord: (u32, u32, u32, u32)) -> [bool; 4] {
-        [coord.0 == 0, coord.1 == 0, coord.2 == 0, coord.3 == 0]
-    }
-
-    fn component_info(
-        &self,
-        start: (u32, u32, u32, u32),
-        player: u32,
-        global_visited: &mut HashSet<(u32, u32, u32, u32)>,
-    ) -> TetraComponentInfo {
-        let (component_nodes, faces) =
-            self.collect_component_nodes(start, player, global_visited);
-        let (has_branch, path_edges) = self.build_component_edges(&component_nodes);
-
-        TetraComponentInfo {
-            nodes: component_nodes,
-            faces,
-            has_branch,
-            path_edges,
-        }
-    }
-
-    fn best_component_for_player(&self, player: u32) -> TetraComponentInfo {
-        let player_cells = self
-            .cells
-            .iter()
-            .filter_map(|(coord, owner)| if *owner == player { Some(*coord) } else { None })
-            .collect::<Vec<_>>();
-
-        let mut best_component = TetraComponentInfo::default();
-        let mut visited = HashSet::new();
-
-        for start in player_cells {
-            if visited.contains(&start) {
-                continue;
-            }
-
-            let component = self.component_info(start, player, &mut visited);
-
-            if component.is_better_than(&best_component) {
-                best_component = component;
-            }
-        }
-
-        best_component
-    }
-
-    fn place(&mut self, coord: (u32, u32, u32, u32), player: u32) -> Result<(), String> {
-        if self.winner.is_some() {
-            return Err("El juego ya termino".to_string());
-        }
-
-        if player != self.next_player {
-            return Err("No es tu turno".to_string());
-        }
-
-        if !self.is_valid_coord(coord) {
-            return Err("Coordenadas invalidas".to_string());
-        }
-
-        if self.cells.contains_key(&coord) {
-            return Err("Casilla ocupada".to_string());
-        }
-
-        self.cells.insert(coord, player);
-
-        let best_component = self.best_component_for_player(player);
-        if best_component.faces.iter().all(|value| *value) && best_component.has_branch {
-            self.winner = Some(player);
-        } else {
-            self.next_player = 1 - self.next_player;
-        }
-
-        Ok(())
-    }
+    game_id: String,
 }
 
-#[derive(Debug, Clone, Default)]
-struct TetraComponentInfo {
-    nodes: Vec<(u32, u32, u32, u32)>,
-    faces: [bool; 4],
-    has_branch: bool,
-    path_edges: Vec<((u32, u32, u32, u32), (u32, u32, u32, u32))>,
+/// Estructura para la solicitud de movimiento en el juego de Hex.
+/// Contiene la casilla en notación de Hex (por ejemplo `c4`), el jugador que
+/// hace el movimiento y el ID del juego.
+#[derive(Debug, Deserialize)]
+struct HexMoveRequest {
+    cell: String,
+    player: u32,
+    game_id: String,
 }
 
-impl TetraComponentInfo {
-    fn face_count(&self) -> usize {
-        self.faces.iter().filter(|value| **value).count()
-    }
-
-    fn is_better_than(&self, other: &Self) -> bool {
-        if self.face_count() != other.face_count() {
-            return self.face_count() > other.face_count();
-        }
-
-        if self.has_branch != other.has_branch {
-            return self.has_branch;
-        }
+#[derive(Debug, Clone, Serialize)]
+struct HexCellResponse {
+    row: u32,
+    col: u32,
+    cell: String,
+    player: Option<u32>,
+}
 
-        self.nodes.len() > other.nodes.len()
-    }
+#[derive(Debug, Clone, Serialize)]
+struct TetraCellResponse {
+    a: u32,
+    b: u32,
+    c: u32,
+    d: u32,
+    player: Option<u32>,
 }
 
 fn faces_to_labels(faces: [bool; 4]) -> Vec<&'static str> {
-    let labels = ["A", "B", "C", "D"];
-    labels
+    TETRA_FACES
         .into_iter()
         .enumerate()
         .filter_map(|(idx, label)| if faces[idx] { Some(label) } else { None })
@@ -384,105 +179,190 @@ fn faces_to_labels(faces: [bool; 4]) -> Vec<&'static str> {
 }
 
 fn edges_to_response(
-    edges: &[((u32, u32, u32, u32), (u32, u32, u32, u32))],
+    edges: &[TetraEdge],
 ) -> Vec<serde_json::Value> {
     edges
         .iter()
-        .map(|(from, to)| {
-            json!({
-                "from": {
-                    "a": from.0,
-                    "b": from.1,
-                    "c": from.2,
-                    "d": from.3,
-                },
-                "to": {
-                    "a": to.0,
-                    "b": to.1,
-                    "c": to.2,
-                    "d": to.3,
-                }
-            })
+        .map(|(from, to)| json!({ "from": from, "to": to }))
+        .collect()
+}
+
+/// Devuelve todas las celdas del tablero tetraédrico con el jugador que las ocupa.
+fn tetra_board_response(game: &TetraGame) -> Vec<TetraCellResponse> {
+    game.all_cells()
+        .into_iter()
+        .map(|coords| TetraCellResponse {
+            a: coords.a(),
+            b: coords.b(),
+            c: coords.c(),
+            d: coords.d(),
+            player: game.player_at(&coords).map(|player| player.id()),
         })
         .collect()
 }
 
 fn tetra_status(game: &TetraGame) -> &'static str {
-    if game.winner.is_some() { "finished" } else { "active" }
+    if game.check_game_over() { "finished" } else { "active" }
 }
 
 fn tetra_response(game: &TetraGame) -> serde_json::Value {
-    let component_0 = game.best_component_for_player(0);
-    let component_1 = game.best_component_for_player(1);
+    let (player_0, player_1) = (PlayerId::new(0), PlayerId::new(1));
     json!({
         "valid": true,
-        "board": game.board_response(),
-        "turn": if game.winner.is_some() { serde_json::Value::Null } else { json!(game.next_player) },
+        "board": tetra_board_response(game),
+        "turn": game.next_player().map(|player| player.id()),
         "status": tetra_status(game),
-        "winner": game.winner,
+        "winner": game.winner().map(|player| player.id()),
         "connectedFaces": {
-            "0": faces_to_labels(component_0.faces),
-            "1": faces_to_labels(component_1.faces),
+            "0": faces_to_labels(game.connected_faces(player_0)),
+            "1": faces_to_labels(game.connected_faces(player_1)),
         },
         "connectionEdges": {
-            "0": edges_to_response(&component_0.path_edges),
-            "1": edges_to_response(&component_1.path_edges),
+            "0": edges_to_response(game.best_component_for_player(player_0).path_edges()),
+            "1": edges_to_response(game.best_component_for_player(player_1).path_edges()),
         },
         "hasBranch": {
-            "0": component_0.has_branch,
-            "1": component_1.has_branch,
-        }
+            "0": game.has_branch(player_0),
+            "1": game.has_branch(player_1),
+        },
+        "position": TetraYEN::from(game),
     })
 }
 
-fn tetra_pick_move(game: &TetraGame, bot_name: &str) -> Option<(u32, u32, u32, u32)> {
-    let available = game.available_moves();
-    if available.is_empty() {
-        return None;
+/// Traduce un error del juego tetraédrico al mensaje que se muestra al usuario.
+fn tetra_error_message(error: &GameYError) -> String {
+    match error {
+        GameYError::TetraGameFinished { .. } => "El juego ya termino".to_string(),
+        GameYError::InvalidPlayerTurn { .. } => "No es tu turno".to_string(),
+        GameYError::InvalidTetraCoordinates { .. } => "Coordenadas invalidas".to_string(),
+        GameYError::TetraCellOccupied { .. } => "Casilla ocupada".to_string(),
+        other => format!("Movimiento inválido: {}", other),
     }
+}
 
-    if bot_name == "random_bot" {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.subsec_nanos() as usize)
-            .unwrap_or(0);
-        return available.get(nanos % available.len()).copied();
-    }
+/// Respuesta de error para un movimiento rechazado en el juego tetraédrico.
+fn tetra_error_response(game: &TetraGame, error: &GameYError) -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "message": tetra_error_message(error),
+        "status": tetra_status(game),
+        "winner": game.winner().map(|player| player.id()),
+    }))
+}
 
-    let player = game.next_player;
-    let mut scored = available
-        .into_iter()
-        .map(|coord| {
-            let mut score = 0i32;
-            let faces = TetraGame::touched_faces(coord);
-            score += faces.iter().filter(|face| **face).count() as i32 * 8;
-
-            for neighbor in game.neighbors(coord) {
-                if game.cells.get(&neighbor) == Some(&player) {
-                    score += if bot_name == "hard_bot" { 7 } else { 4 };
-                }
+/// Devuelve todas las casillas del tablero de Hex con el jugador que las ocupa.
+fn hex_board_response(game: &HexGame) -> Vec<HexCellResponse> {
+    let size = game.board_size();
+    (0..size * size)
+        .map(|idx| {
+            let cell = HexCell::new(idx / size, idx % size);
+            HexCellResponse {
+                row: cell.row(),
+                col: cell.col(),
+                cell: cell.to_string(),
+                player: game.player_at(&cell).map(|player| player.id()),
             }
+        })
+        .collect()
+}
 
-            let values = [coord.0, coord.1, coord.2, coord.3];
-            let spread = values.iter().filter(|value| **value > 0).count() as i32;
-            score += if bot_name == "hard_bot" { spread * 2 } else { spread };
+fn hex_status(game: &HexGame) -> &'static str {
+    if game.check_game_over() { "finished" } else { "active" }
+}
 
-            (coord, score)
-        })
-        .collect::<Vec<_>>();
+fn hex_response(game: &HexGame) -> serde_json::Value {
+    json!({
+        "valid": true,
+        "size": game.board_size(),
+        "board": hex_board_response(game),
+        "turn": game.next_player().map(|player| player.id()),
+        "status": hex_status(game),
+        "winner": game.winner().map(|player| player.id()),
+        "moves": game.moves_notation(),
+    })
+}
+
+/// Traduce un error del juego de Hex al mensaje que se muestra al usuario.
+fn hex_error_message(error: &GameYError) -> String {
+    match error {
+        GameYError::HexGameFinished { .. } => "El juego ya termino".to_string(),
+        GameYError::InvalidPlayerTurn { .. } => "No es tu turno".to_string(),
+        GameYError::InvalidHexCell { .. } | GameYError::InvalidHexNotation { .. } => {
+            "Casilla invalida".to_string()
+        }
+        GameYError::HexCellOccupied { .. } => "Casilla ocupada".to_string(),
+        other => format!("Movimiento inválido: {}", other),
+    }
+}
 
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
-    scored.first().map(|entry| entry.0)
+/// Respuesta de error para un movimiento rechazado en el juego de Hex.
+fn hex_error_response(game: &HexGame, error: &GameYError) -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "message": hex_error_message(error),
+        "status": hex_status(game),
+        "winner": game.winner().map(|player| player.id()),
+    }))
 }
 
 /* HELPERS */
 
+/// Construye el presupuesto de búsqueda de un bot a partir del tiempo que le
+/// queda en el reloj. Sin tiempo indicado, el bot piensa con sus propios límites.
+fn clock_budget(time_ms: Option<u64>) -> SearchBudget {
+    match time_ms {
+        Some(ms) => SearchBudget::from_clock(Duration::from_millis(ms)),
+        None => SearchBudget::new(),
+    }
+}
+
+/// Respuesta para una partida que no existe en el servidor.
+fn game_not_started_response() -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "message": "El juego no ha sido iniciado"
+    }))
+}
+
+/// Respuesta para una partida tetraédrica que no existe en el servidor.
+fn tetra_not_started_response() -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "message": "El juego tetraedrico no ha sido iniciado"
+    }))
+}
+
+/// Respuesta para una partida de Hex que no existe en el servidor.
+fn hex_not_started_response() -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "message": "El juego de Hex no ha sido iniciado"
+    }))
+}
+
+/// Respuesta para un bot cuya búsqueda no llegó a terminar.
+fn bot_failed_response() -> HttpResponse {
+    HttpResponse::InternalServerError().json(json!({
+        "valid": false,
+        "message": "El bot no pudo elegir un movimiento"
+    }))
+}
+
+/// Respuesta para una partida que cambió mientras el bot pensaba.
+fn game_changed_response() -> HttpResponse {
+    HttpResponse::Conflict().json(json!({
+        "valid": false,
+        "message": "La partida cambió mientras el bot pensaba"
+    }))
+}
+
 /// Lógica compartida para ejecutar el movimiento de cualquier bot.
 /// Recibe el nombre del bot para buscarlo en el registro.
 ///
 /// # Parámetros
 /// - `bot_name`: Nombre del bot a usar.
 /// - `game_id`: ID del juego sobre el que actuar.
+/// - `time_ms`: Tiempo restante en el reloj del bot, en milisegundos, si lo hay.
 /// - `state`: Estado compartido con el mapa de juegos.
 /// - `registry`: Registro de bots disponibles.
 ///
@@ -491,22 +371,18 @@ fn tetra_pick_move(game: &TetraGame, bot_name: &str) -> Option<(u32, u32, u32, u
 async fn execute_bot_move(
     bot_name: &str,
     game_id: &str,
+    time_ms: Option<u64>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
     registry: web::Data<Arc<YBotRegistry>>,
 ) -> HttpResponse {
-    let mut games = state.lock().unwrap();
-    let game = match games.get_mut(game_id) {
-        Some(g) => g,
-        None => {
-            return HttpResponse::BadRequest().json(json!({
-                "valid": false,
-                "message": "El juego no ha sido iniciado"
-            }));
-        }
+    // Se copia la partida para que el bot piense sin bloquear el resto de sesiones
+    let snapshot = match state.lock().unwrap().get(game_id) {
+        Some(g) => g.clone(),
+        None => return game_not_started_response(),
     };
 
     // Verificar si se termino el juego
-    if let GameStatus::Finished { winner } = game.status() {
+    if let GameStatus::Finished { winner } = snapshot.status() {
         return HttpResponse::Ok().json(json!({
             "valid": false,
             "message": "El juego ya terminó",
@@ -526,113 +402,265 @@ async fn execute_bot_move(
         }
     };
 
-    // Pedir movimiento al bot
-    let bot_coords: Coordinates = match bot.choose_move(game) {
-        Some(mv) => mv,
-        None => {
+    // Pedir la decisión al bot, dentro del tiempo que le queda: colocar una
+    // ficha, usar la regla de intercambio o abandonar. La búsqueda corre en el
+    // pool de hilos bloqueantes para no ocupar un worker de actix
+    let budget = clock_budget(time_ms);
+    let hash = snapshot.position_hash();
+    let plies = snapshot.history().len();
+    let choice = web::block(move || bot.choose_movement(&snapshot, &budget)).await;
+    let movement = match choice {
+        Ok(Some(mv)) => mv,
+        Ok(None) => {
             return HttpResponse::Ok().json(json!({
                 "valid": false,
                 "message": "No hay movimientos disponibles"
             }));
         }
+        Err(_) => return bot_failed_response(),
     };
 
-    // Obtener el jugador cuyo turno es
-    let next_player = match game.next_player() {
-        Some(p) => p,
-        None => {
-            return HttpResponse::Ok().json(json!({
-                "valid": false,
-                "message": "El juego ya terminó",
-                "winner": null,
-                "status": "finished"
-            }));
-        }
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(game_id) {
+        Some(g) => g,
+        None => return game_not_started_response(),
     };
+    // Otra petición pudo mover o deshacer mientras el bot pensaba
+    if game.position_hash() != hash || game.history().len() != plies {
+        return game_changed_response();
+    }
 
-    println!(
-        "[{}] elige: x={} y={} z={} (player {})",
-        bot_name,
-        bot_coords.x(),
-        bot_coords.y(),
-        bot_coords.z(),
-        next_player.id()
-    );
+    println!("[{}] elige: {}", bot_name, movement);
 
-    let movement = Movement::Placement {
-        player: next_player,
-        coords: bot_coords,
-    };
-
-    match game.add_move(movement) {
+    match game.add_move(movement.clone()) {
         Ok(_) => {
             println!("Movimiento del bot '{}' aplicado", bot_name);
 
-            let board = game
-                .board_state()
-                .into_iter()
-                .map(|(coords, player_id)| {
-                    json!({
+            let mut response = game_state_response(game, "Movimiento registrado");
+            match &movement {
+                Movement::Placement { coords, .. } => {
+                    response["lastMove"] = json!({
                         "x": coords.x(),
                         "y": coords.y(),
-                        "z": coords.z(),
-                        "player": player_id,
-                    })
-                })
-                .collect::<Vec<_>>();
-
-            HttpResponse::Ok().json(json!({
-                "valid": true,
-                "message": "Movimiento registrado",
-                "board": board,
-                "turn": game.next_player().map(|p| p.id()),
-                "status": match game.status() {
-                    GameStatus::Ongoing { .. } => "active",
-                    GameStatus::Finished { .. } => "finished",
-                },
-                "winner": match game.status() {
-                    GameStatus::Finished { winner } => Some(winner.id()),
-                    _ => None,
-                },
-                "lastMove": {
-                    "x": bot_coords.x(),
-                    "y": bot_coords.y(),
-                    "z": bot_coords.z()
+                        "z": coords.z()
+                    });
+                }
+                Movement::Action { action, .. } => {
+                    response["action"] = json!(action_name(action));
                 }
-            }))
+                Movement::Setup { .. } => {}
+            }
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             println!("Movimiento inválido del bot: {:?}", e);
-            HttpResponse::BadRequest().json(json!({
-                "valid": false,
-                "message": format!("Movimiento inválido: {:?}", e),
-            }))
+            move_error_response(&e)
         }
     }
 }
 
+/// Devuelve el nombre con el que se comunica una acción en las respuestas.
+fn action_name(action: &GameAction) -> &'static str {
+    match action {
+        GameAction::Swap => "swap",
+        GameAction::Resign => "resign",
+    }
+}
+
+/// Construye la respuesta de error para un movimiento rechazado.
+///
+/// Incluye en `reason` un código estable que identifica el motivo y en
+/// `message` una explicación legible.
+fn move_error_response(error: &GameYError) -> HttpResponse {
+    let (reason, message) = match error {
+        GameYError::CoordOutOfRange {
+            id_coord,
+            coord,
+            board_size,
+        } => (
+            "coord_out_of_range",
+            format!(
+                "La coordenada {}={} no es válida en un tablero de tamaño {} \
+                 (cada coordenada debe ser menor que {} y x + y + z debe ser {})",
+                id_coord,
+                coord,
+                board_size,
+                board_size,
+                board_size.saturating_sub(1)
+            ),
+        ),
+        GameYError::BadCoordsNumber { expected, found } => (
+            "bad_coords_number",
+            format!("Se esperaban {} coordenadas, se recibieron {}", expected, found),
+        ),
+        GameYError::Occupied { coordinates, .. } => (
+            "occupied",
+            format!("La celda {} ya está ocupada", coordinates),
+        ),
+        GameYError::InvalidPlayerTurn { expected, .. } => (
+            "wrong_turn",
+            format!("No es tu turno: le toca al jugador {}", expected),
+        ),
+        GameYError::GameOver { .. } => ("game_over", "El juego ya terminó".to_string()),
+        GameYError::IllegalSwap { .. } => (
+            "illegal_swap",
+            "El intercambio solo se permite como primer movimiento del segundo jugador"
+                .to_string(),
+        ),
+        GameYError::SwapNotAllowed { .. } => (
+            "swap_not_allowed",
+            "Las reglas de esta partida no permiten el intercambio".to_string(),
+        ),
+        GameYError::BlockedCell { coordinates } => (
+            "blocked_cell",
+            format!("La celda {} está bloqueada", coordinates),
+        ),
+        GameYError::InvalidPlayer {
+            player,
+            num_players,
+        } => (
+            "invalid_player",
+            format!(
+                "El jugador {} no existe en una partida de {} jugadores",
+                player, num_players
+            ),
+        ),
+        other => ("invalid_move", format!("Movimiento inválido: {}", other)),
+    };
+    HttpResponse::BadRequest().json(json!({
+        "valid": false,
+        "reason": reason,
+        "message": message,
+    }))
+}
+
+/// Construye la respuesta JSON con el estado actual de un juego.
+///
+/// Si el juego terminó por conexión de los tres lados, incluye en `winningPath`
+/// las celdas del camino ganador mínimo y en `winningGroup` todo el grupo ganador.
+///
+/// # Parámetros
+/// - `game`: Juego del que se construye la respuesta.
+/// - `message`: Mensaje descriptivo a incluir en la respuesta.
+fn game_state_response(game: &GameY, message: &str) -> serde_json::Value {
+    let board = game
+        .board_state()
+        .into_iter()
+        .map(|(coords, player_id)| {
+            json!({
+                "x": coords.x(),
+                "y": coords.y(),
+                "z": coords.z(),
+                "player": player_id,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "valid": true,
+        "message": message,
+        "board": board,
+        "turn": game.next_player().map(|p| p.id()),
+        "numPlayers": game.num_players(),
+        "blocked": cells_to_json(&game.blocked_cells()),
+        "status": match game.status() {
+            GameStatus::Ongoing { .. } => "active",
+            GameStatus::Finished { .. } => "finished",
+        },
+        "winner": match game.status() {
+            GameStatus::Finished { winner } => Some(winner.id()),
+            _ => None,
+        },
+        "winningPath": cells_to_json(&game.minimal_winning_path().unwrap_or_default()),
+        "winningGroup": cells_to_json(&game.winning_group().unwrap_or_default()),
+    })
+}
+
+/// Convierte una lista de celdas en su representación JSON.
+fn cells_to_json(cells: &[Coordinates]) -> Vec<serde_json::Value> {
+    cells
+        .iter()
+        .map(|coords| {
+            json!({
+                "x": coords.x(),
+                "y": coords.y(),
+                "z": coords.z(),
+            })
+        })
+        .collect()
+}
+
+/// Convierte un movimiento en su representación JSON.
+fn movement_to_json(movement: &Movement) -> serde_json::Value {
+    match movement {
+        Movement::Placement { player, coords } => json!({
+            "player": player.id(),
+            "x": coords.x(),
+            "y": coords.y(),
+            "z": coords.z(),
+        }),
+        Movement::Action { player, action } => json!({
+            "player": player.id(),
+            "action": action.to_string(),
+        }),
+        Movement::Setup { player, coords } => json!({
+            "player": player.id(),
+            "x": coords.x(),
+            "y": coords.y(),
+            "z": coords.z(),
+            "setup": true,
+        }),
+    }
+}
+
 /* ENDPOINTS */
 
-/// Inicia un nuevo juego con el tamaño de tablero especificado.
+/// Inicia un nuevo juego con el tamaño de tablero y las reglas especificadas.
 ///
 /// # Parámetros
-/// - `req`: Solicitud JSON con el tamaño del tablero y el ID del juego.
+/// - `req`: Solicitud JSON con el tamaño del tablero, el ID del juego y las reglas.
 /// - `state`: Estado compartido con el mapa de juegos.
 ///
 /// # Retorna
-/// Una respuesta HTTP confirmando el inicio del juego.
+/// Una respuesta HTTP confirmando el inicio del juego, o un error si el
+/// tamaño del tablero no está permitido por las reglas.
 async fn start_game(
     req: web::Json<StartGameRequest>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
 ) -> HttpResponse {
+    let game = match GameY::with_rules(req.board_size, req.rules)
+        .and_then(|g| g.with_blocked_cells(req.blocked.iter().copied()))
+        .and_then(|g| g.with_truncated_corners(req.truncated_corners))
+        .and_then(|g| match &req.handicap {
+            None => Ok(g),
+            Some(h) if !h.stones.is_empty() => {
+                g.with_handicap_stones(PlayerId::new(h.player), h.stones.iter().copied())
+            }
+            Some(h) => g.with_handicap(PlayerId::new(h.player), h.count),
+        })
+    {
+        Ok(g) => g,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "valid": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+
+    let blocked = cells_to_json(&game.blocked_cells());
+    let handicap = cells_to_json(&game.handicap_stones());
     let mut games = state.lock().unwrap();
-    games.insert(req.game_id.clone(), GameY::new(req.board_size));
+    games.insert(req.game_id.clone(), game);
 
     println!("[Rust] start_game — game_id: {} tamaño: {}", req.game_id, req.board_size);
 
     HttpResponse::Ok().json(json!({
         "status": "started",
-        "board_size": req.board_size
+        "board_size": req.board_size,
+        "rules": req.rules,
+        "blocked": blocked,
+        "handicap": handicap,
     }))
 }
 
@@ -644,7 +672,7 @@ async fn start_game(
 ///
 /// # Retorna
 /// Una respuesta HTTP con el resultado del movimiento.
-pub async fn user_move(
+async fn user_move(
     req: web::Json<MoveRequest>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
 ) -> HttpResponse {
@@ -697,16 +725,23 @@ pub async fn user_move(
     }
 
     // Convertir coordenadas i32 → u32
-    let coords = match (req.x.try_into(), req.y.try_into(), req.z.try_into()) {
-        (Ok(x), Ok(y), Ok(z)) => Coordinates::new(x, y, z),
+    let values: Vec<u32> = match (req.x.try_into(), req.y.try_into(), req.z.try_into()) {
+        (Ok(x), Ok(y), Ok(z)) => vec![x, y, z],
         _ => {
             return HttpResponse::BadRequest().json(json!({
                 "valid": false,
+                "reason": "negative_coordinate",
                 "message": "Coordenadas inválidas (deben ser >= 0)"
             }));
         }
     };
 
+    // Comprobar que las coordenadas son una celda del tablero
+    let coords = match Coordinates::try_from_vec(&values, game.board_size()) {
+        Ok(c) => c,
+        Err(e) => return move_error_response(&e),
+    };
+
     let movement = Movement::Placement {
         player: next_player,
         coords,
@@ -715,41 +750,11 @@ pub async fn user_move(
     match game.add_move(movement) {
         Ok(_) => {
             println!("Movimiento aplicado para player {}", req.player);
-
-            let board = game
-                .board_state()
-                .into_iter()
-                .map(|(coords, player_id)| {
-                    json!({
-                        "x": coords.x(),
-                        "y": coords.y(),
-                        "z": coords.z(),
-                        "player": player_id,
-                    })
-                })
-                .collect::<Vec<_>>();
-
-            HttpResponse::Ok().json(json!({
-                "valid": true,
-                "message": "Movimiento registrado",
-                "board": board,
-                "turn": game.next_player().map(|p| p.id()),
-                "status": match game.status() {
-                    GameStatus::Ongoing { .. } => "active",
-                    GameStatus::Finished { .. } => "finished",
-                },
-                "winner": match game.status() {
-                    GameStatus::Finished { winner } => Some(winner.id()),
-                    _ => None,
-                }
-            }))
+            HttpResponse::Ok().json(game_state_response(game, "Movimiento registrado"))
         }
         Err(e) => {
             println!("Movimiento inválido: {:?}", e);
-            HttpResponse::BadRequest().json(json!({
-                "valid": false,
-                "message": format!("Movimiento inválido: {:?}", e),
-            }))
+            move_error_response(&e)
         }
     }
 }
@@ -772,55 +777,163 @@ async fn end_game(
     web::Json(json!({ "status": "finished" }))
 }
 
-/// Ejecuta el movimiento del bot aleatorio.
+/// Deshace el último movimiento de un juego.
+///
+/// # Parámetros
+/// - `req`: Solicitud JSON con el ID del juego.
+/// - `state`: Estado compartido con el mapa de juegos.
+///
+/// # Retorna
+/// Una respuesta HTTP con el estado del juego tras deshacer el movimiento.
+async fn undo_move(
+    req: web::Json<HistoryRequest>,
+    state: web::Data<Mutex<HashMap<String, GameY>>>,
+) -> HttpResponse {
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(&req.game_id) {
+        Some(g) => g,
+        None => {
+            return HttpResponse::BadRequest().json(json!({
+                "valid": false,
+                "message": "El juego no ha sido iniciado"
+            }));
+        }
+    };
+
+    match game.undo_move() {
+        Ok(movement) => {
+            println!("[Rust] undo_move — game_id: {} deshace: {}", req.game_id, movement);
+            let mut response = game_state_response(game, "Movimiento deshecho");
+            response["undoneMove"] = movement_to_json(&movement);
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "valid": false,
+            "message": format!("No se puede deshacer: {}", e),
+        })),
+    }
+}
+
+/// Rehace el último movimiento deshecho de un juego.
 ///
 /// # Parámetros
 /// - `req`: Solicitud JSON con el ID del juego.
 /// - `state`: Estado compartido con el mapa de juegos.
+///
+/// # Retorna
+/// Una respuesta HTTP con el estado del juego tras rehacer el movimiento.
+async fn redo_move(
+    req: web::Json<HistoryRequest>,
+    state: web::Data<Mutex<HashMap<String, GameY>>>,
+) -> HttpResponse {
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(&req.game_id) {
+        Some(g) => g,
+        None => {
+            return HttpResponse::BadRequest().json(json!({
+                "valid": false,
+                "message": "El juego no ha sido iniciado"
+            }));
+        }
+    };
+
+    match game.redo_move() {
+        Ok(movement) => {
+            println!("[Rust] redo_move — game_id: {} rehace: {}", req.game_id, movement);
+            let mut response = game_state_response(game, "Movimiento rehecho");
+            response["redoneMove"] = movement_to_json(&movement);
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "valid": false,
+            "message": format!("No se puede rehacer: {}", e),
+        })),
+    }
+}
+
+/// Ejecuta el movimiento del bot aleatorio.
+///
+/// # Parámetros
+/// - `req`: Solicitud JSON con el ID del juego y el tiempo restante opcional.
+/// - `state`: Estado compartido con el mapa de juegos.
 /// - `registry`: Registro de bots.
 ///
 /// # Retorna
 /// Una respuesta HTTP con el movimiento del bot.
-pub async fn bot_move_random(
+async fn bot_move_random(
     req: web::Json<BotMoveRequest>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
     registry: web::Data<Arc<YBotRegistry>>,
 ) -> HttpResponse {
-    execute_bot_move("random_bot", &req.game_id, state, registry).await
+    execute_bot_move("random_bot", &req.game_id, req.time_ms, state, registry).await
 }
 
 /// Ejecuta el movimiento del bot intermedio.
 ///
 /// # Parámetros
-/// - `req`: Solicitud JSON con el ID del juego.
+/// - `req`: Solicitud JSON con el ID del juego y el tiempo restante opcional.
 /// - `state`: Estado compartido con el mapa de juegos.
 /// - `registry`: Registro de bots.
 ///
 /// # Retorna
 /// Una respuesta HTTP con el movimiento del bot.
-pub async fn bot_move_intermediate(
+async fn bot_move_intermediate(
     req: web::Json<BotMoveRequest>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
     registry: web::Data<Arc<YBotRegistry>>,
 ) -> HttpResponse {
-    execute_bot_move("intermediate_bot", &req.game_id, state, registry).await
+    execute_bot_move("intermediate_bot", &req.game_id, req.time_ms, state, registry).await
 }
 
 /// Ejecuta el movimiento del bot difícil.
 ///
 /// # Parámetros
-/// - `req`: Solicitud JSON con el ID del juego.
+/// - `req`: Solicitud JSON con el ID del juego y el tiempo restante opcional.
 /// - `state`: Estado compartido con el mapa de juegos.
 /// - `registry`: Registro de bots.
 ///
 /// # Retorna
 /// Una respuesta HTTP con el movimiento del bot.
-pub async fn bot_move_hard(
+async fn bot_move_hard(
     req: web::Json<BotMoveRequest>,
     state: web::Data<Mutex<HashMap<String, GameY>>>,
     registry: web::Data<Arc<YBotRegistry>>,
 ) -> HttpResponse {
-    execute_bot_move("hard_bot", &req.game_id, state, registry).await
+    execute_bot_move("hard_bot", &req.game_id, req.time_ms, state, registry).await
+}
+
+/// Ejecuta el movimiento del bot de búsqueda Monte-Carlo (MCTS).
+///
+/// # Parámetros
+/// - `req`: Solicitud JSON con el ID del juego y el tiempo restante opcional.
+/// - `state`: Estado compartido con el mapa de juegos.
+/// - `registry`: Registro de bots.
+///
+/// # Retorna
+/// Una respuesta HTTP con el movimiento del bot.
+async fn bot_move_mcts(
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, GameY>>>,
+    registry: web::Data<Arc<YBotRegistry>>,
+) -> HttpResponse {
+    execute_bot_move("mcts_bot", &req.game_id, req.time_ms, state, registry).await
+}
+
+/// Ejecuta el movimiento del bot de búsqueda alfa-beta.
+///
+/// # Parámetros
+/// - `req`: Solicitud JSON con el ID del juego y el tiempo restante opcional.
+/// - `state`: Estado compartido con el mapa de juegos.
+/// - `registry`: Registro de bots.
+///
+/// # Retorna
+/// Una respuesta HTTP con el movimiento del bot.
+async fn bot_move_search(
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, GameY>>>,
+    registry: web::Data<Arc<YBotRegistry>>,
+) -> HttpResponse {
+    execute_bot_move("search_bot", &req.game_id, req.time_ms, state, registry).await
 }
 
 /// Endpoint público para que bots externos obtengan el siguiente movimiento.
@@ -831,11 +944,13 @@ pub async fn bot_move_hard(
 ///
 /// # Parámetros de query
 /// - `position`: JSON YEN del estado actual (obligatorio).
-/// - `bot_id`: Identificador del bot (`random_bot`, `intermediate_bot`, `hard_bot`). Por defecto `hard_bot`.
+/// - `bot_id`: Identificador del bot (`random_bot`, `intermediate_bot`, `hard_bot`, `mcts_bot`, `search_bot`). Por defecto `hard_bot`.
+/// - `time_ms`: Tiempo restante en el reloj del bot, en milisegundos (opcional).
 ///
 /// # Respuesta
-/// `{"coords":{"x":1,"y":1,"z":0}}` o `{"action":"resign"}` si no hay movimientos.
-pub async fn play(
+/// `{"coords":{"x":1,"y":1,"z":0}}`, `{"action":"swap"}` si el bot usa la regla de
+/// intercambio, o `{"action":"resign"}` si abandona o no hay movimientos.
+async fn play(
     query: web::Query<PlayQuery>,
     registry: web::Data<Arc<YBotRegistry>>,
 ) -> HttpResponse {
@@ -867,53 +982,155 @@ pub async fn play(
         }
     };
 
-    match bot.choose_move(&game) {
-        Some(coords) => HttpResponse::Ok().json(json!({
+    match bot.choose_movement(&game, &clock_budget(query.time_ms)) {
+        Some(Movement::Placement { coords, .. }) => HttpResponse::Ok().json(json!({
             "coords": {
                 "x": coords.x(),
                 "y": coords.y(),
                 "z": coords.z()
             }
         })),
-        None => HttpResponse::Ok().json(json!({ "action": "resign" })),
+        Some(Movement::Action { action, .. }) => {
+            HttpResponse::Ok().json(json!({ "action": action_name(&action) }))
+        }
+        // Sin movimientos posibles el bot abandona
+        Some(Movement::Setup { .. }) | None => HttpResponse::Ok().json(json!({ "action": "resign" })),
+    }
+}
+
+/// Analiza una posición con el bot indicado y devuelve sus mejores jugadas,
+/// para mostrar pistas o revisar una partida terminada.
+///
+/// # Ruta
+/// `POST /v1/ybot/analyze/{bot_id}?count=N`, con el estado del tablero en
+/// formato YEN como cuerpo. `count` es opcional y por defecto vale 5.
+///
+/// # Respuesta
+/// `{"bot_id":"hard_bot","moves":[{"coords":{"x":1,"y":1,"z":0},"score":12.5,"reason":"bridge"}]}`,
+/// con las jugadas ordenadas de mejor a peor. `reason` indica el motivo
+/// principal de cada jugada: `win`, `block`, `connect`, `bridge` o `centrality`.
+/// Los bots que no puntúan las casillas (como `random_bot`) responden con un 400.
+async fn analyze_position(
+    path: web::Path<String>,
+    query: web::Query<AnalyzeQuery>,
+    yen: web::Json<YEN>,
+    registry: web::Data<Arc<YBotRegistry>>,
+) -> HttpResponse {
+    let bot_id = path.into_inner();
+    let game = match GameY::try_from(yen.into_inner()) {
+        Ok(g) => g,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "error": format!("Estado de juego inválido: {}", e)
+            }));
+        }
+    };
+
+    let bot = match registry.find(&bot_id) {
+        Some(b) => b,
+        None => {
+            return HttpResponse::NotFound().json(json!({
+                "error": format!("Bot '{}' no encontrado en el registro", bot_id)
+            }));
+        }
+    };
+
+    match bot.analyze(&game, query.count.unwrap_or(5)) {
+        Some(moves) => HttpResponse::Ok().json(json!({
+            "bot_id": bot_id,
+            "moves": moves,
+        })),
+        None => HttpResponse::BadRequest().json(json!({
+            "error": format!("El bot '{}' no puede analizar esta posición", bot_id)
+        })),
     }
 }
 
 async fn start_tetra_game(
     req: web::Json<TetraStartRequest>,
-    state: web::Data<Mutex<Option<TetraGame>>>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
 ) -> HttpResponse {
-    let mut game_lock = state.lock().unwrap();
-    *game_lock = Some(TetraGame::new(req.size));
+    if req.size > TETRA_SESSION_MAX_SIZE {
+        return HttpResponse::BadRequest().json(json!({
+            "valid": false,
+            "message": format!(
+                "Tamaño inválido: las partidas tetraédricas admiten como máximo {} celdas por arista",
+                TETRA_SESSION_MAX_SIZE
+            )
+        }));
+    }
+    let game = match TetraGame::new(req.size) {
+        Ok(game) => game,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "valid": false,
+                "message": format!("Tamaño inválido: {}", e)
+            }));
+        }
+    };
+    let size = game.board_size();
+    let mut games = state.lock().unwrap();
+    games.insert(req.game_id.clone(), game);
+
+    println!("[Rust] start_tetra_game — game_id: {} tamaño: {}", req.game_id, size);
 
     HttpResponse::Ok().json(json!({
         "status": "started",
-        "size": req.size.max(2),
+        "size": size,
     }))
 }
 
-async fn tetra_move(
-    req: web::Json<TetraMoveRequest>,
-    state: web::Data<Mutex<Option<TetraGame>>>,
+/// Restaura un juego tetraédrico a partir de una posición en formato TetraYEN.
+///
+/// Sustituye la sesión `game_id` si ya existía.
+async fn restore_tetra_game(
+    req: web::Json<TetraRestoreRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
 ) -> HttpResponse {
-    let mut game_lock = state.lock().unwrap();
-    let game = match game_lock.as_mut() {
-        Some(game) => game,
-        None => {
+    let TetraRestoreRequest { position, game_id } = req.into_inner();
+    if position.size() > TETRA_SESSION_MAX_SIZE {
+        return HttpResponse::BadRequest().json(json!({
+            "valid": false,
+            "message": format!(
+                "Posición inválida: las partidas tetraédricas admiten como máximo {} celdas por arista",
+                TETRA_SESSION_MAX_SIZE
+            )
+        }));
+    }
+    let game = match TetraGame::try_from(position) {
+        Ok(game) => game,
+        Err(e) => {
             return HttpResponse::BadRequest().json(json!({
                 "valid": false,
-                "message": "El juego tetraedrico no ha sido iniciado"
+                "message": format!("Posición inválida: {}", e)
             }));
         }
     };
 
-    let coord = match (
+    println!("[Rust] restore_tetra_game — game_id: {} tamaño: {}", game_id, game.board_size());
+
+    let response = tetra_response(&game);
+    state.lock().unwrap().insert(game_id, game);
+    HttpResponse::Ok().json(response)
+}
+
+async fn tetra_move(
+    req: web::Json<TetraMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+) -> HttpResponse {
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(&req.game_id) {
+        Some(game) => game,
+        None => return tetra_not_started_response(),
+    };
+
+    let coords = match (
         u32::try_from(req.a),
         u32::try_from(req.b),
         u32::try_from(req.c),
         u32::try_from(req.d),
     ) {
-        (Ok(a), Ok(b), Ok(c), Ok(d)) => (a, b, c, d),
+        (Ok(a), Ok(b), Ok(c), Ok(d)) => TetraCoordinates::new(a, b, c, d),
         _ => {
             return HttpResponse::BadRequest().json(json!({
                 "valid": false,
@@ -922,103 +1139,220 @@ async fn tetra_move(
         }
     };
 
-    match game.place(coord, req.player) {
+    match game.place(coords, PlayerId::new(req.player)) {
         Ok(_) => HttpResponse::Ok().json(tetra_response(game)),
-        Err(message) => HttpResponse::BadRequest().json(json!({
-            "valid": false,
-            "message": message,
-            "status": tetra_status(game),
-            "winner": game.winner,
-        })),
+        Err(error) => tetra_error_response(game, &error),
     }
 }
 
 async fn tetra_bot_move(
     bot_name: &str,
-    state: web::Data<Mutex<Option<TetraGame>>>,
+    game_id: &str,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+    registry: web::Data<Arc<TetraBotRegistry>>,
 ) -> HttpResponse {
-    let mut game_lock = state.lock().unwrap();
-    let game = match game_lock.as_mut() {
-        Some(game) => game,
+    let bot = match registry.find(bot_name) {
+        Some(b) => b,
         None => {
-            return HttpResponse::BadRequest().json(json!({
+            return HttpResponse::InternalServerError().json(json!({
                 "valid": false,
-                "message": "El juego tetraedrico no ha sido iniciado"
+                "message": format!("Bot '{}' no encontrado en el registro", bot_name)
             }));
         }
     };
 
-    if game.winner.is_some() {
-        return HttpResponse::Ok().json(tetra_response(game));
-    }
-
-    let coord = match tetra_pick_move(game, bot_name) {
-        Some(coord) => coord,
-        None => {
+    // El bot piensa sobre una copia, sin bloquear el resto de sesiones
+    let snapshot = match state.lock().unwrap().get(game_id) {
+        Some(game) => game.clone(),
+        None => return tetra_not_started_response(),
+    };
+    let Some(player) = snapshot.next_player() else {
+        return HttpResponse::Ok().json(tetra_response(&snapshot));
+    };
+    let position = TetraYEN::from(&snapshot);
+    let choice = web::block(move || {
+        let coords = bot.choose_move(&snapshot);
+        (coords, snapshot)
+    })
+    .await;
+    let coords = match choice {
+        Ok((Some(coords), _)) => coords,
+        Ok((None, snapshot)) => {
             return HttpResponse::Ok().json(json!({
                 "valid": false,
                 "message": "No hay movimientos disponibles",
-                "status": tetra_status(game),
-                "winner": game.winner,
+                "status": tetra_status(&snapshot),
+                "winner": snapshot.winner().map(|player| player.id()),
             }));
         }
+        Err(_) => return bot_failed_response(),
     };
 
-    match game.place(coord, game.next_player) {
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(game_id) {
+        Some(game) => game,
+        None => return tetra_not_started_response(),
+    };
+    // Otra petición pudo mover o restaurar la partida mientras el bot pensaba
+    if TetraYEN::from(&*game) != position {
+        return game_changed_response();
+    }
+
+    match game.place(coords, player) {
         Ok(_) => {
-            let component_0 = game.best_component_for_player(0);
-            let component_1 = game.best_component_for_player(1);
-            HttpResponse::Ok().json(json!({
-            "valid": true,
-            "board": game.board_response(),
-            "turn": if game.winner.is_some() { serde_json::Value::Null } else { json!(game.next_player) },
-            "status": tetra_status(game),
-            "winner": game.winner,
-            "connectedFaces": {
-                "0": faces_to_labels(component_0.faces),
-                "1": faces_to_labels(component_1.faces),
-            },
-            "connectionEdges": {
-                "0": edges_to_response(&component_0.path_edges),
-                "1": edges_to_response(&component_1.path_edges),
-            },
-            "hasBranch": {
-                "0": component_0.has_branch,
-                "1": component_1.has_branch,
-            },
-            "lastMove": {
-                "a": coord.0,
-                "b": coord.1,
-                "c": coord.2,
-                "d": coord.3,
-            }
-        }))
-        },
-        Err(message) => HttpResponse::BadRequest().json(json!({
-            "valid": false,
-            "message": message,
-            "status": tetra_status(game),
-            "winner": game.winner,
-        })),
+            let mut response = tetra_response(game);
+            response["lastMove"] = json!(coords);
+            HttpResponse::Ok().json(response)
+        }
+        Err(error) => tetra_error_response(game, &error),
     }
 }
 
+/// Finaliza un juego tetraédrico y libera su estado.
+async fn end_tetra_game(
+    req: web::Json<EndGameRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+) -> web::Json<serde_json::Value> {
+    let mut games = state.lock().unwrap();
+    games.remove(&req.game_id);
+    println!("Finalizando juego tetraedrico — game_id: {}", req.game_id);
+    web::Json(json!({ "status": "finished" }))
+}
+
 async fn tetra_bot_move_random(
-    state: web::Data<Mutex<Option<TetraGame>>>,
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+    registry: web::Data<Arc<TetraBotRegistry>>,
 ) -> HttpResponse {
-    tetra_bot_move("random_bot", state).await
+    tetra_bot_move("random_bot", &req.game_id, state, registry).await
 }
 
 async fn tetra_bot_move_intermediate(
-    state: web::Data<Mutex<Option<TetraGame>>>,
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+    registry: web::Data<Arc<TetraBotRegistry>>,
 ) -> HttpResponse {
-    tetra_bot_move("intermediate_bot", state).await
+    tetra_bot_move("intermediate_bot", &req.game_id, state, registry).await
 }
 
 async fn tetra_bot_move_hard(
-    state: web::Data<Mutex<Option<TetraGame>>>,
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, TetraGame>>>,
+    registry: web::Data<Arc<TetraBotRegistry>>,
 ) -> HttpResponse {
-    tetra_bot_move("hard_bot", state).await
+    tetra_bot_move("hard_bot", &req.game_id, state, registry).await
+}
+
+async fn start_hex_game(
+    req: web::Json<HexStartRequest>,
+    state: web::Data<Mutex<HashMap<String, HexGame>>>,
+) -> HttpResponse {
+    let game = match HexGame::new(req.size) {
+        Ok(game) => game,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "valid": false,
+                "message": format!("Tamaño inválido: {}", e)
+            }));
+        }
+    };
+    println!("[Rust] start_hex_game — game_id: {} tamaño: {}", req.game_id, req.size);
+
+    let response = hex_response(&game);
+    state.lock().unwrap().insert(req.game_id.clone(), game);
+    HttpResponse::Ok().json(response)
+}
+
+async fn hex_move(
+    req: web::Json<HexMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, HexGame>>>,
+) -> HttpResponse {
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(&req.game_id) {
+        Some(game) => game,
+        None => return hex_not_started_response(),
+    };
+
+    let result = req
+        .cell
+        .parse::<HexCell>()
+        .and_then(|cell| game.play(PlayerId::new(req.player), cell));
+    match result {
+        Ok(()) => HttpResponse::Ok().json(hex_response(game)),
+        Err(error) => hex_error_response(game, &error),
+    }
+}
+
+/// Juega el movimiento del bot `bot_id` en la partida de Hex indicada.
+///
+/// # Ruta
+/// `POST /v1/hex/bot/{bot_id}`, con `{"game_id": "..."}` como cuerpo. Los
+/// bots disponibles son `random_bot`, `intermediate_bot` y `hard_bot`.
+async fn hex_bot_move(
+    path: web::Path<String>,
+    req: web::Json<BotMoveRequest>,
+    state: web::Data<Mutex<HashMap<String, HexGame>>>,
+    registry: web::Data<Arc<HexBotRegistry>>,
+) -> HttpResponse {
+    let bot_id = path.into_inner();
+    let bot = match registry.find(&bot_id) {
+        Some(b) => b,
+        None => {
+            return HttpResponse::NotFound().json(json!({
+                "valid": false,
+                "message": format!("Bot '{}' no encontrado en el registro", bot_id)
+            }));
+        }
+    };
+
+    // El bot piensa sobre una copia, sin bloquear el resto de sesiones
+    let snapshot = match state.lock().unwrap().get(&req.game_id) {
+        Some(game) => game.clone(),
+        None => return hex_not_started_response(),
+    };
+    let Some(player) = snapshot.next_player() else {
+        return HttpResponse::Ok().json(hex_response(&snapshot));
+    };
+    let history = snapshot.history().to_vec();
+    let choice = web::block(move || {
+        let cell = bot.choose_move(&snapshot);
+        (cell, snapshot)
+    })
+    .await;
+    let cell = match choice {
+        Ok((Some(cell), _)) => cell,
+        Ok((None, snapshot)) => return HttpResponse::Ok().json(hex_response(&snapshot)),
+        Err(_) => return bot_failed_response(),
+    };
+
+    let mut games = state.lock().unwrap();
+    let game = match games.get_mut(&req.game_id) {
+        Some(game) => game,
+        None => return hex_not_started_response(),
+    };
+    // Otra petición pudo mover o reiniciar la partida mientras el bot pensaba
+    if game.history() != history.as_slice() {
+        return game_changed_response();
+    }
+
+    match game.play(player, cell) {
+        Ok(()) => {
+            let mut response = hex_response(game);
+            response["lastMove"] = json!(cell.to_string());
+            HttpResponse::Ok().json(response)
+        }
+        Err(error) => hex_error_response(game, &error),
+    }
+}
+
+/// Finaliza un juego de Hex y libera su estado.
+async fn end_hex_game(
+    req: web::Json<EndGameRequest>,
+    state: web::Data<Mutex<HashMap<String, HexGame>>>,
+) -> web::Json<serde_json::Value> {
+    state.lock().unwrap().remove(&req.game_id);
+    println!("Finalizando juego de Hex — game_id: {}", req.game_id);
+    web::Json(json!({ "status": "finished" }))
 }
 
 /* MAIN */
@@ -1031,37 +1365,68 @@ async fn main() -> std::io::Result<()> {
     println!("Servidor Rust escuchando en el puerto 4000");
 
     let shared_games = web::Data::new(Mutex::new(HashMap::<String, GameY>::new()));
-    let shared_tetra_game = web::Data::new(Mutex::new(None::<TetraGame>));
+    let shared_tetra_games = web::Data::new(Mutex::new(HashMap::<String, TetraGame>::new()));
+    let shared_hex_games = web::Data::new(Mutex::new(HashMap::<String, HexGame>::new()));
 
 
     let registry = Arc::new(
         YBotRegistry::new()
             .with_bot(Arc::new(RandomBot))
             .with_bot(Arc::new(IntermediateBot))
-            .with_bot(Arc::new(HardBot)),
+            .with_bot(Arc::new(HardBot))
+            .with_bot(Arc::new(MctsBot::new().with_time_limit(Duration::from_secs(2))))
+            .with_bot(Arc::new(SearchBot::new().with_time_limit(Duration::from_secs(2)))),
     );
     let shared_registry = web::Data::new(registry);
+    let shared_tetra_registry = web::Data::new(Arc::new(
+        TetraBotRegistry::new()
+            .with_bot(Arc::new(TetraRandomBot))
+            .with_bot(Arc::new(TetraDistanceBot))
+            .with_bot(Arc::new(TetraPlayoutBot::new().with_time_limit(Duration::from_secs(2)))),
+    ));
+
+    let shared_hex_registry = web::Data::new(Arc::new(
+        HexBotRegistry::new()
+            .with_bot(Arc::new(HexRandomBot))
+            .with_bot(Arc::new(HexDistanceBot))
+            .with_bot(Arc::new(HexPlayoutBot::new().with_time_limit(Duration::from_secs(2)))),
+    ));
 
     HttpServer::new(move || {
         App::new()
             .app_data(shared_games.clone())
-            .app_data(shared_tetra_game.clone())
+            .app_data(shared_tetra_games.clone())
+            .app_data(shared_hex_games.clone())
 
             .app_data(shared_registry.clone())
+            .app_data(shared_tetra_registry.clone())
+            .app_data(shared_hex_registry.clone())
             // Juego
             .route("/v1/game/start",  web::post().to(start_game))
             .route("/v1/game/move",   web::post().to(user_move))
             .route("/v1/game/end",    web::post().to(end_game))
+            .route("/v1/game/undo",   web::post().to(undo_move))
+            .route("/v1/game/redo",   web::post().to(redo_move))
             // Juego tetraedrico
             .route("/v1/tetra/start", web::post().to(start_tetra_game))
             .route("/v1/tetra/move", web::post().to(tetra_move))
+            .route("/v1/tetra/restore", web::post().to(restore_tetra_game))
+            .route("/v1/tetra/end", web::post().to(end_tetra_game))
             .route("/v1/tetra/bot/random_bot", web::post().to(tetra_bot_move_random))
             .route("/v1/tetra/bot/intermediate_bot", web::post().to(tetra_bot_move_intermediate))
             .route("/v1/tetra/bot/hard_bot", web::post().to(tetra_bot_move_hard))
+            // Hex
+            .route("/v1/hex/start", web::post().to(start_hex_game))
+            .route("/v1/hex/move", web::post().to(hex_move))
+            .route("/v1/hex/end", web::post().to(end_hex_game))
+            .route("/v1/hex/bot/{bot_id}", web::post().to(hex_bot_move))
             // Bots
             .route("/v1/ybot/choose/random_bot",       web::post().to(bot_move_random))
             .route("/v1/ybot/choose/intermediate_bot", web::post().to(bot_move_intermediate))
             .route("/v1/ybot/choose/hard_bot", web::post().to(bot_move_hard))
+            .route("/v1/ybot/choose/mcts_bot", web::post().to(bot_move_mcts))
+            .route("/v1/ybot/choose/search_bot", web::post().to(bot_move_search))
+            .route("/v1/ybot/analyze/{bot_id}", web::post().to(analyze_position))
             // API pública para competición entre bots
             .route("/play", web::get().to(play))
     })
@@ -4,6 +4,9 @@
 //! in a compact, portable way. Currently supported:
 //!
 //! - [`YEN`]: Y Exchange Notation - a JSON-based format inspired by chess FEN
+//! - [`TetraYEN`]: the YEN counterpart for the tetrahedral variant
 
+pub mod tetra_yen;
 pub mod yen;
+pub use tetra_yen::*;
 pub use yen::*;
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Tetrahedral Y Exchange Notation - the [`YEN`](crate::YEN) counterpart for
+/// positions of the tetrahedral variant.
+///
+/// # Format
+/// - `size`: The board size (number of cells along each edge of the tetrahedron)
+/// - `turn`: Which player's turn it is (0 or 1)
+/// - `players`: Character symbols for each player (e.g., ['B', 'R'])
+/// - `layout`: A compact string holding one triangular layer per value of the
+///   `a` coordinate, from the apex (`a = size - 1`) down to face A (`a = 0`).
+///   Layers are separated by [`TETRA_LAYER_SEPARATOR`] and written like a YEN
+///   layout: rows separated by '/', from the highest `b` down to `b = 0`, each
+///   row listing its cells by increasing `c`, with '.' for empty cells
+///
+/// # Example
+/// ```json
+/// {
+///   "size": 3,
+///   "turn": 0,
+///   "players": ["B", "R"],
+///   "layout": "B|./.R|./../..."
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TetraYEN {
+    /// The board size (number of cells along each edge).
+    size: u32,
+    /// The index of the player whose turn it is (0-indexed).
+    turn: u32,
+    /// Character symbols representing each player.
+    players: Vec<char>,
+    /// A compact string representation of the board, layer by layer.
+    layout: String,
+}
+
+/// Symbol separating the layers of a [`TetraYEN`] layout.
+pub const TETRA_LAYER_SEPARATOR: char = '|';
+
+impl TetraYEN {
+    /// Creates a new tetrahedral YEN representation.
+    ///
+    /// # Arguments
+    /// * `size` - The board size
+    /// * `turn` - Index of the player to move (0 or 1)
+    /// * `players` - Character symbols for each player
+    /// * `layout` - The board layout string
+    pub fn new(size: u32, turn: u32, players: Vec<char>, layout: String) -> Self {
+        TetraYEN {
+            size,
+            turn,
+            players,
+            layout,
+        }
+    }
+
+    /// Returns the board layout string.
+    pub fn layout(&self) -> &str {
+        &self.layout
+    }
+
+    /// Returns the board size.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the index of the player whose turn it is.
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    /// Returns the player symbols.
+    pub fn players(&self) -> &[char] {
+        &self.players
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let yen = TetraYEN::new(2, 0, vec!['B', 'R'], "B|./R.".to_string());
+        assert_eq!(yen.size(), 2);
+        assert_eq!(yen.turn(), 0);
+        assert_eq!(yen.layout(), "B|./R.");
+        assert_eq!(yen.players(), &['B', 'R']);
+    }
+
+    #[test]
+    fn test_roundtrip_serialization() {
+        let original = TetraYEN::new(3, 0, vec!['B', 'R'], ".|./B.|./../R..".to_string());
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains("\"layout\":\".|./B.|./../R..\""));
+        let restored: TetraYEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, restored);
+    }
+}
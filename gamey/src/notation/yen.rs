@@ -1,3 +1,4 @@
+use crate::{Coordinates, GameRules};
 use serde::{Deserialize, Serialize};
 
 /// Y Exchange Notation (YEN) - a compact format for representing Y game states.
@@ -8,9 +9,17 @@ use serde::{Deserialize, Serialize};
 /// # Format
 /// - `size`: The board size (length of one side of the triangle)
 /// - `turn`: Which player's turn it is (0 or 1)
-/// - `players`: Character symbols for each player (e.g., ['B', 'R'] for Blue/Red)
+/// - `players`: Character symbols for each player (e.g., ['B', 'R'] for Blue/Red,
+///   or ['B', 'R', 'G'] in a three-player game)
 /// - `layout`: A compact string where rows are separated by '/', and cells are
-///   represented by player symbols or '.' for empty cells
+///   represented by player symbols, '.' for empty cells or [`BLOCKED_SYMBOL`]
+///   for cells that are not part of the board
+/// - `swapped`: Whether the second player has used the swap rule (omitted when false)
+/// - `rules`: The [`GameRules`] of the game (omitted when they are the defaults)
+/// - `handicap`: The cells of the layout holding handicap stones, which were
+///   placed before the game started (omitted when empty)
+/// - `eliminated`: The players who are out of the game, because they resigned
+///   or lost a misère game, in the order they left (omitted when empty)
 ///
 /// # Example
 /// ```json
@@ -31,11 +40,29 @@ pub struct YEN {
     players: Vec<char>,
     /// A compact string representation of the board.
     ///
-    /// Rows are separated by '/', with cells represented by player symbols
-    /// or '.' for empty cells. Example: "B/..R/.B.R"
+    /// Rows are separated by '/', with cells represented by player symbols,
+    /// '.' for empty cells or '#' for blocked cells. Example: "B/..R/.B.R"
     layout: String,
+    /// Whether the swap rule has been used in this game.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    swapped: bool,
+    /// The rules the game is played by.
+    #[serde(default, skip_serializing_if = "GameRules::is_default")]
+    rules: GameRules,
+    /// The cells holding handicap stones, in the order they were placed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    handicap: Vec<Coordinates>,
+    /// The indices of the players who are out of the game, in the order they left.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    eliminated: Vec<u32>,
 }
 
+/// Symbols used for the players of a game, in player id order.
+pub const PLAYER_SYMBOLS: [char; GameRules::MAX_PLAYERS as usize] = ['B', 'R', 'G', 'Y', 'P', 'O'];
+
+/// Symbol used in the layout for cells that are blocked or removed from the board.
+pub const BLOCKED_SYMBOL: char = '#';
+
 impl YEN {
     /// Creates a new YEN representation.
     ///
@@ -50,9 +77,37 @@ impl YEN {
             turn,
             players,
             layout,
+            swapped: false,
+            rules: GameRules::default(),
+            handicap: Vec::new(),
+            eliminated: Vec::new(),
         }
     }
 
+    /// Sets whether the swap rule has been used and returns the updated YEN.
+    pub fn with_swapped(mut self, swapped: bool) -> Self {
+        self.swapped = swapped;
+        self
+    }
+
+    /// Sets the rules of the game and returns the updated YEN.
+    pub fn with_rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Sets the cells holding handicap stones and returns the updated YEN.
+    pub fn with_handicap(mut self, handicap: Vec<Coordinates>) -> Self {
+        self.handicap = handicap;
+        self
+    }
+
+    /// Sets the players who are out of the game and returns the updated YEN.
+    pub fn with_eliminated(mut self, eliminated: Vec<u32>) -> Self {
+        self.eliminated = eliminated;
+        self
+    }
+
     /// Returns the board layout string.
     pub fn layout(&self) -> &str {
         &self.layout
@@ -72,6 +127,26 @@ impl YEN {
     pub fn players(&self) -> &[char] {
         &self.players
     }
+
+    /// Returns true if the swap rule has been used.
+    pub fn swapped(&self) -> bool {
+        self.swapped
+    }
+
+    /// Returns the rules the game is played by.
+    pub fn rules(&self) -> GameRules {
+        self.rules
+    }
+
+    /// Returns the cells holding handicap stones.
+    pub fn handicap(&self) -> &[Coordinates] {
+        &self.handicap
+    }
+
+    /// Returns the indices of the players who are out of the game.
+    pub fn eliminated(&self) -> &[u32] {
+        &self.eliminated
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +186,75 @@ mod tests {
         assert_eq!(yen.players(), &['B', 'R']);
     }
 
+    #[test]
+    fn test_swapped_defaults_to_false_and_is_omitted() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+        assert!(!yen.swapped());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(!json.contains("swapped"));
+    }
+
+    #[test]
+    fn test_swapped_roundtrip() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "R/../...".to_string()).with_swapped(true);
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"swapped\":true"));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert!(restored.swapped());
+    }
+
+    #[test]
+    fn test_eliminated_omitted_when_empty_and_roundtrip() {
+        let yen = YEN::new(3, 0, vec!['B', 'R', 'G'], "./../...".to_string());
+        assert!(yen.eliminated().is_empty());
+        assert!(!serde_json::to_string(&yen).unwrap().contains("eliminated"));
+
+        let yen = yen.with_eliminated(vec![2]);
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"eliminated\":[2]"));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.eliminated(), &[2]);
+    }
+
+    #[test]
+    fn test_rules_default_and_are_omitted() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+        assert!(yen.rules().is_default());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(!json.contains("rules"));
+    }
+
+    #[test]
+    fn test_handicap_omitted_when_empty() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+        assert!(yen.handicap().is_empty());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(!json.contains("handicap"));
+    }
+
+    #[test]
+    fn test_handicap_roundtrip() {
+        let handicap = vec![Coordinates::new(1, 0, 1)];
+        let yen = YEN::new(3, 1, vec!['B', 'R'], "./B./...".to_string())
+            .with_handicap(handicap.clone());
+        let json = serde_json::to_string(&yen).unwrap();
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.handicap(), handicap.as_slice());
+    }
+
+    #[test]
+    fn test_rules_roundtrip() {
+        let rules = GameRules {
+            swap_allowed: false,
+            ..GameRules::default()
+        };
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string()).with_rules(rules);
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"swap_allowed\":false"));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.rules(), rules);
+    }
+
     #[test]
     fn test_clone() {
         let yen = YEN::new(5, 0, vec!['B', 'R'], "./.././.../.....".to_string());
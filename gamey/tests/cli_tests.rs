@@ -1,4 +1,4 @@
-use gamey::{Command, Mode, parse_command, parse_idx};
+use gamey::{Command, Mode, Variant, parse_command, parse_hex_command, parse_idx};
 
 // =============================================================================
 // parse_command Tests
@@ -40,6 +40,18 @@ fn test_parse_command_resign() {
     assert_eq!(command, Command::Resign);
 }
 
+#[test]
+fn test_parse_command_undo() {
+    let command = parse_command("undo", 10);
+    assert_eq!(command, Command::Undo);
+}
+
+#[test]
+fn test_parse_command_redo() {
+    let command = parse_command("redo", 10);
+    assert_eq!(command, Command::Redo);
+}
+
 #[test]
 fn test_parse_command_help() {
     let command = parse_command("help", 10);
@@ -234,6 +246,32 @@ fn test_parse_idx_large_valid_number() {
     assert_eq!(result, Ok(999));
 }
 
+// =============================================================================
+// parse_hex_command Tests
+// =============================================================================
+
+#[test]
+fn test_parse_hex_command_cell_notation() {
+    // c2 is row 1, column 2
+    assert_eq!(parse_hex_command("c2", 5), Command::Place { idx: 7 });
+    assert_eq!(parse_hex_command("  C2 ", 5), Command::Place { idx: 7 });
+}
+
+#[test]
+fn test_parse_hex_command_cell_off_the_board() {
+    assert!(matches!(parse_hex_command("f1", 5), Command::Error { .. }));
+    assert!(matches!(parse_hex_command("a6", 5), Command::Error { .. }));
+}
+
+#[test]
+fn test_parse_hex_command_index_and_other_commands() {
+    assert_eq!(parse_hex_command("24", 5), Command::Place { idx: 24 });
+    assert!(matches!(parse_hex_command("25", 5), Command::Error { .. }));
+    assert_eq!(parse_hex_command("help", 5), Command::Help);
+    assert_eq!(parse_hex_command("exit", 5), Command::Exit);
+    assert_eq!(parse_hex_command("", 5), Command::None);
+}
+
 // =============================================================================
 // Mode enum Tests
 // =============================================================================
@@ -270,7 +308,7 @@ fn test_mode_equality() {
 // =============================================================================
 
 use clap::Parser;
-use gamey::CliArgs;
+use gamey::{CliArgs, GameRules, PlayerId};
 
 #[test]
 fn test_cli_args_default_values() {
@@ -361,6 +399,76 @@ fn test_cli_args_combined_options() {
     assert_eq!(args.port, 5000);
 }
 
+#[test]
+fn test_cli_args_default_rules() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.rules(), GameRules::default());
+}
+
+#[test]
+fn test_cli_args_custom_rules() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "--no-swap",
+        "--first-player",
+        "1",
+        "--strict",
+        "--misere",
+        "--max-size",
+        "19",
+    ])
+    .unwrap();
+    let rules = args.rules();
+    assert!(!rules.swap_allowed);
+    assert_eq!(rules.starting_player, PlayerId::new(1));
+    assert!(rules.strict_game_over);
+    assert!(rules.misere);
+    assert_eq!(rules.max_board_size, 19);
+}
+
+#[test]
+fn test_cli_args_handicap() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.handicap, 0);
+    assert_eq!(args.handicap_player, 0);
+
+    let args =
+        CliArgs::try_parse_from(["gamey", "--handicap", "3", "--handicap-player", "1"]).unwrap();
+    assert_eq!(args.handicap, 3);
+    assert_eq!(args.handicap_player, 1);
+}
+
+#[test]
+fn test_cli_args_invalid_first_player() {
+    let result = CliArgs::try_parse_from(["gamey", "--first-player", "6"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_args_players() {
+    let args = CliArgs::try_parse_from(["gamey", "--players", "3", "--first-player", "2"]).unwrap();
+    assert_eq!(args.players, 3);
+    let rules = args.rules();
+    assert_eq!(rules.num_players, 3);
+    assert_eq!(rules.starting_player, PlayerId::new(2));
+}
+
+#[test]
+fn test_cli_args_invalid_players() {
+    assert!(CliArgs::try_parse_from(["gamey", "--players", "1"]).is_err());
+    assert!(CliArgs::try_parse_from(["gamey", "--players", "7"]).is_err());
+}
+
+#[test]
+fn test_cli_args_variant() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.variant, Variant::Y);
+    let args = CliArgs::try_parse_from(["gamey", "--variant", "hex"]).unwrap();
+    assert_eq!(args.variant, Variant::Hex);
+    assert_eq!(format!("{}", args.variant), "hex");
+    assert!(CliArgs::try_parse_from(["gamey", "--variant", "tetra"]).is_err());
+}
+
 #[test]
 fn test_cli_args_invalid_mode() {
     let result = CliArgs::try_parse_from(["gamey", "--mode", "invalid"]);
@@ -1,5 +1,7 @@
 use gamey::{
-    Coordinates, GameAction, GameStatus, GameY, GameYError, Movement, PlayerId, RenderOptions, YEN,
+    Coordinates, GameAction, GameRules, GameStatus, GameY, GameYError, Movement, PlayerId,
+    HexCell, HexGame, PolyCell, PolyYGame, RenderOptions, SwapMode, Symmetry, TetraCoordinates,
+    TetraGame, TetraYEN, YEN,
 };
 use std::fs;
 use tempfile::tempdir;
@@ -324,6 +326,49 @@ fn test_cannot_place_on_occupied_cell() {
     }
 }
 
+#[test]
+fn test_cannot_place_outside_the_board() {
+    let mut game = GameY::new(5);
+
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(7, 0, 0),
+    });
+
+    match result.unwrap_err() {
+        GameYError::CoordOutOfRange {
+            id_coord,
+            coord,
+            board_size,
+        } => {
+            assert_eq!(id_coord, 'x');
+            assert_eq!(coord, 7);
+            assert_eq!(board_size, 5);
+        }
+        other => panic!("Expected CoordOutOfRange error, got {:?}", other),
+    }
+    assert!(game.history().is_empty());
+    assert_eq!(game.available_cells().len(), 15);
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_cannot_place_with_wrong_coordinate_sum() {
+    let mut game = GameY::new(5);
+
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 1, 1),
+    });
+
+    assert!(matches!(
+        result,
+        Err(GameYError::CoordOutOfRange { id_coord: 'z', .. })
+    ));
+    assert!(game.history().is_empty());
+    assert_eq!(YEN::from(&game).layout(), YEN::from(&GameY::new(5)).layout());
+}
+
 #[test]
 fn test_check_player_turn_wrong_player() {
     let game = GameY::new(5);
@@ -357,6 +402,95 @@ fn test_check_player_turn_correct_player() {
     assert!(game.check_player_turn(&movement).is_ok());
 }
 
+// ============================================================================
+// Winning Group Tests
+// ============================================================================
+
+/// Plays a size 4 game where player 0 wins along side A with an extra stone
+/// hanging off the winning chain.
+fn game_won_with_extra_stone() -> GameY {
+    let mut game = GameY::new(4);
+    let moves = [
+        (0, Coordinates::new(1, 1, 1)),
+        (1, Coordinates::new(3, 0, 0)),
+        (0, Coordinates::new(0, 0, 3)),
+        (1, Coordinates::new(2, 1, 0)),
+        (0, Coordinates::new(0, 1, 2)),
+        (1, Coordinates::new(2, 0, 1)),
+        (0, Coordinates::new(0, 2, 1)),
+        (1, Coordinates::new(1, 2, 0)),
+        (0, Coordinates::new(0, 3, 0)),
+    ];
+    for (player, coords) in moves {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords,
+        })
+        .unwrap();
+    }
+    game
+}
+
+#[test]
+fn test_winning_group_is_none_while_ongoing() {
+    let game = GameY::new(4);
+    assert!(game.winning_group().is_none());
+    assert!(game.minimal_winning_path().is_none());
+}
+
+#[test]
+fn test_winning_group_is_none_after_resign() {
+    let mut game = GameY::new(4);
+    game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    assert!(game.winning_group().is_none());
+    assert!(game.minimal_winning_path().is_none());
+}
+
+#[test]
+fn test_winning_group_contains_whole_chain() {
+    let game = game_won_with_extra_stone();
+    let group = game.winning_group().unwrap();
+    assert_eq!(group.len(), 5);
+    assert!(group.contains(&Coordinates::new(1, 1, 1)));
+    assert!(!group.contains(&Coordinates::new(3, 0, 0)));
+}
+
+#[test]
+fn test_minimal_winning_path_drops_extra_stone() {
+    let game = game_won_with_extra_stone();
+    let path = game.minimal_winning_path().unwrap();
+    assert_eq!(
+        path,
+        vec![
+            Coordinates::new(0, 0, 3),
+            Coordinates::new(0, 1, 2),
+            Coordinates::new(0, 2, 1),
+            Coordinates::new(0, 3, 0),
+        ]
+    );
+    assert!(path.iter().any(|c| c.touches_side_a()));
+    assert!(path.iter().any(|c| c.touches_side_b()));
+    assert!(path.iter().any(|c| c.touches_side_c()));
+}
+
+#[test]
+fn test_minimal_winning_path_single_cell_board() {
+    let mut game = GameY::new(1);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(0, 0, 0),
+    })
+    .unwrap();
+    assert_eq!(
+        game.minimal_winning_path(),
+        Some(vec![Coordinates::new(0, 0, 0)])
+    );
+}
+
 // ============================================================================
 // Game Actions Tests (Resign, Swap)
 // ============================================================================
@@ -408,17 +542,17 @@ fn test_player_1_resign_makes_player_0_win() {
 }
 
 #[test]
-fn test_swap_changes_next_player() {
+fn test_swap_as_first_move_is_rejected() {
     let mut game = GameY::new(5);
 
-    game.add_move(Movement::Action {
+    let result = game.add_move(Movement::Action {
         player: PlayerId::new(0),
         action: GameAction::Swap,
-    })
-    .unwrap();
+    });
 
-    assert!(!game.check_game_over());
-    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert!(matches!(result, Err(GameYError::IllegalSwap { .. })));
+    assert!(game.history().is_empty());
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
 }
 
 #[test]
@@ -444,6 +578,1400 @@ fn test_swap_after_opening_move() {
     assert!(!game.check_game_over());
 }
 
+#[test]
+fn test_swap_transfers_opening_stone() {
+    let mut game = GameY::new(5);
+    let opening = Coordinates::new(2, 1, 1);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: opening,
+    })
+    .unwrap();
+
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+
+    assert_eq!(game.player_at(&opening), Some(PlayerId::new(1)));
+    assert!(game.swapped());
+    assert_eq!(game.history().len(), 2);
+    assert_eq!(game.available_cells().len(), 14);
+}
+
+#[test]
+fn test_swap_mirror_reflects_opening_stone() {
+    let mut game = GameY::new(5).with_swap_mode(SwapMode::Mirror);
+    let opening = Coordinates::new(1, 3, 0);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: opening,
+    })
+    .unwrap();
+
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+
+    let mirrored = Coordinates::new(1, 0, 3);
+    assert_eq!(game.player_at(&opening), None);
+    assert_eq!(game.player_at(&mirrored), Some(PlayerId::new(1)));
+    assert!(game.available_cells().contains(&opening.to_index(5)));
+    assert!(!game.available_cells().contains(&mirrored.to_index(5)));
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_swap_stone_only_after_opening_move() {
+    let mut game = GameY::new(5);
+    let opening = Coordinates::new(2, 1, 1);
+    assert_eq!(game.swap_stone(PlayerId::new(1)), None);
+
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: opening,
+    })
+    .unwrap();
+    assert_eq!(game.swap_stone(PlayerId::new(1)), Some(opening));
+    assert_eq!(game.swap_stone(PlayerId::new(0)), None);
+
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+    assert_eq!(game.swap_stone(PlayerId::new(0)), None);
+    assert_eq!(game.swap_stone(PlayerId::new(1)), None);
+}
+
+#[test]
+fn test_swap_stone_none_when_disabled() {
+    let rules = GameRules {
+        swap_allowed: false,
+        ..GameRules::default()
+    };
+    let mut game = GameY::with_rules(5, rules).unwrap();
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 1, 1),
+    })
+    .unwrap();
+    assert_eq!(game.swap_stone(PlayerId::new(1)), None);
+}
+
+#[test]
+fn test_swap_by_first_player_is_rejected() {
+    let mut game = GameY::new(5);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 1, 1),
+    })
+    .unwrap();
+
+    let result = game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Swap,
+    });
+    assert!(matches!(result, Err(GameYError::IllegalSwap { .. })));
+}
+
+#[test]
+fn test_swap_after_second_move_is_rejected() {
+    let mut game = GameY::new(5);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 1, 1),
+    })
+    .unwrap();
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(4, 0, 0),
+    })
+    .unwrap();
+
+    let result = game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Swap,
+    });
+    assert!(matches!(result, Err(GameYError::IllegalSwap { .. })));
+    assert_eq!(game.history().len(), 2);
+}
+
+#[test]
+fn test_undo_swap_restores_opening_stone() {
+    let mut game = GameY::new(5).with_swap_mode(SwapMode::Mirror);
+    let opening = Coordinates::new(1, 3, 0);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: opening,
+    })
+    .unwrap();
+    let available = game.available_cells().clone();
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+
+    game.undo_move().unwrap();
+
+    assert_eq!(game.player_at(&opening), Some(PlayerId::new(0)));
+    assert_eq!(game.available_cells(), &available);
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert!(!game.swapped());
+}
+
+#[test]
+fn test_swap_is_recorded_in_yen() {
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+
+    let yen: YEN = (&game).into();
+    assert!(yen.swapped());
+    assert_eq!(yen.layout(), "R/../...");
+    assert_eq!(yen.turn(), 0);
+
+    let loaded = GameY::try_from(yen).unwrap();
+    assert!(loaded.swapped());
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(0)));
+}
+
+// ============================================================================
+// Game Rules Tests
+// ============================================================================
+
+/// Plays a won game on a size-3 board for player 0, using `rules`.
+fn won_game_with_rules(rules: GameRules) -> GameY {
+    let mut game = GameY::with_rules(3, rules).unwrap();
+    for (player, coords) in [
+        (0, Coordinates::new(0, 2, 0)),
+        (1, Coordinates::new(2, 0, 0)),
+        (0, Coordinates::new(0, 1, 1)),
+        (1, Coordinates::new(1, 1, 0)),
+        (0, Coordinates::new(0, 0, 2)),
+    ] {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords,
+        })
+        .unwrap();
+    }
+    assert!(game.check_game_over());
+    game
+}
+
+#[test]
+fn test_new_game_uses_default_rules() {
+    let game = GameY::new(5);
+    assert_eq!(game.rules(), &GameRules::default());
+}
+
+#[test]
+fn test_board_larger_than_limit_is_rejected() {
+    let rules = GameRules {
+        max_board_size: 11,
+        ..GameRules::default()
+    };
+    assert!(GameY::with_rules(11, rules).is_ok());
+    assert!(matches!(
+        GameY::with_rules(12, rules),
+        Err(GameYError::BoardTooLarge { size: 12, max: 11 })
+    ));
+}
+
+#[test]
+fn test_starting_player_moves_first() {
+    let rules = GameRules {
+        starting_player: PlayerId::new(1),
+        ..GameRules::default()
+    };
+    let game = GameY::with_rules(5, rules).unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+}
+
+#[test]
+fn test_swap_rejected_when_disabled() {
+    let rules = GameRules {
+        swap_allowed: false,
+        ..GameRules::default()
+    };
+    let mut game = GameY::with_rules(5, rules).unwrap();
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 1, 1),
+    })
+    .unwrap();
+    let result = game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    });
+    assert!(matches!(result, Err(GameYError::SwapNotAllowed { .. })));
+    assert_eq!(game.history().len(), 1);
+}
+
+#[test]
+fn test_strict_rules_reject_moves_after_game_over() {
+    let rules = GameRules {
+        strict_game_over: true,
+        ..GameRules::default()
+    };
+    let mut game = won_game_with_rules(rules);
+
+    let placement = game.add_move(Movement::Placement {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(1, 0, 1),
+    });
+    assert!(matches!(placement, Err(GameYError::GameOver { .. })));
+    let resign = game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    });
+    assert!(matches!(resign, Err(GameYError::GameOver { .. })));
+    assert_eq!(game.history().len(), 5);
+}
+
+#[test]
+fn test_lenient_rules_accept_moves_after_game_over() {
+    let mut game = won_game_with_rules(GameRules::default());
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(1, 0, 1),
+    })
+    .unwrap();
+    assert_eq!(game.history().len(), 6);
+}
+
+#[test]
+fn test_rules_are_kept_in_yen() {
+    let rules = GameRules {
+        swap_allowed: false,
+        strict_game_over: true,
+        ..GameRules::default()
+    };
+    let game = won_game_with_rules(rules);
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.rules(), rules);
+
+    // Finished positions load even with strict rules
+    let loaded = GameY::try_from(yen).unwrap();
+    assert_eq!(loaded.rules(), &rules);
+    assert!(loaded.check_game_over());
+}
+
+#[test]
+fn test_yen_board_larger_than_limit_is_rejected() {
+    let rules = GameRules {
+        max_board_size: 2,
+        ..GameRules::default()
+    };
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string()).with_rules(rules);
+    assert!(matches!(
+        GameY::try_from(yen),
+        Err(GameYError::BoardTooLarge { .. })
+    ));
+}
+
+// ============================================================================
+// Multi-player Tests
+// ============================================================================
+
+fn three_player_game(size: u32) -> GameY {
+    let rules = GameRules {
+        num_players: 3,
+        ..GameRules::default()
+    };
+    GameY::with_rules(size, rules).unwrap()
+}
+
+#[test]
+fn test_three_player_turn_rotation() {
+    let mut game = three_player_game(5);
+    assert_eq!(game.num_players(), 3);
+    for (player, coords) in [
+        (0, Coordinates::new(4, 0, 0)),
+        (1, Coordinates::new(0, 4, 0)),
+        (2, Coordinates::new(0, 0, 4)),
+    ] {
+        assert_eq!(game.next_player(), Some(PlayerId::new(player)));
+        place(&mut game, player, coords);
+    }
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_opponents_in_turn_order() {
+    let game = three_player_game(5);
+    assert_eq!(
+        game.opponents(PlayerId::new(1)),
+        vec![PlayerId::new(2), PlayerId::new(0)]
+    );
+}
+
+#[test]
+fn test_third_player_wins_by_connecting_sides() {
+    let rules = GameRules {
+        num_players: 3,
+        starting_player: PlayerId::new(2),
+        ..GameRules::default()
+    };
+    let mut game = GameY::with_rules(4, rules).unwrap();
+    for (player, coords) in [
+        (2, Coordinates::new(1, 1, 1)),
+        (0, Coordinates::new(3, 0, 0)),
+        (1, Coordinates::new(2, 1, 0)),
+        (2, Coordinates::new(0, 2, 1)),
+        (0, Coordinates::new(0, 3, 0)),
+        (1, Coordinates::new(2, 0, 1)),
+        (2, Coordinates::new(1, 0, 2)),
+        (0, Coordinates::new(0, 0, 3)),
+        (1, Coordinates::new(0, 1, 2)),
+    ] {
+        place(&mut game, player, coords);
+    }
+    assert!(!game.check_game_over());
+    place(&mut game, 2, Coordinates::new(1, 2, 0));
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(2)
+        }
+    );
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.turn(), 0);
+}
+
+#[test]
+fn test_resigned_player_is_skipped() {
+    let mut game = three_player_game(5);
+    game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert_eq!(game.opponents(PlayerId::new(1)), vec![PlayerId::new(2)]);
+
+    place(&mut game, 1, Coordinates::new(2, 1, 1));
+    place(&mut game, 2, Coordinates::new(1, 2, 1));
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+}
+
+#[test]
+fn test_last_player_standing_wins() {
+    let mut game = three_player_game(5);
+    for player in [0, 1] {
+        game.add_move(Movement::Action {
+            player: PlayerId::new(player),
+            action: GameAction::Resign,
+        })
+        .unwrap();
+    }
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(2)
+        }
+    );
+}
+
+#[test]
+fn test_undo_resign_restores_player() {
+    let mut game = three_player_game(5);
+    game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    game.undo_move().unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(
+        game.opponents(PlayerId::new(0)),
+        vec![PlayerId::new(1), PlayerId::new(2)]
+    );
+}
+
+#[test]
+fn test_move_by_unknown_player_is_rejected() {
+    let mut game = three_player_game(5);
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(3),
+        coords: Coordinates::new(2, 1, 1),
+    });
+    assert!(matches!(
+        result,
+        Err(GameYError::InvalidPlayer { num_players: 3, .. })
+    ));
+}
+
+#[test]
+fn test_invalid_player_counts_are_rejected() {
+    for num_players in [0, 1, 7] {
+        let rules = GameRules {
+            num_players,
+            ..GameRules::default()
+        };
+        assert!(matches!(
+            GameY::with_rules(5, rules),
+            Err(GameYError::InvalidNumPlayers { .. })
+        ));
+    }
+    let rules = GameRules {
+        num_players: 3,
+        starting_player: PlayerId::new(3),
+        ..GameRules::default()
+    };
+    assert!(matches!(
+        GameY::with_rules(5, rules),
+        Err(GameYError::InvalidPlayer { .. })
+    ));
+}
+
+#[test]
+fn test_three_player_yen_roundtrip() {
+    let mut game = three_player_game(3);
+    place(&mut game, 0, Coordinates::new(2, 0, 0));
+    place(&mut game, 1, Coordinates::new(1, 1, 0));
+    place(&mut game, 2, Coordinates::new(1, 0, 1));
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.players(), &['B', 'R', 'G']);
+    assert_eq!(yen.layout(), "B/GR/...");
+    assert_eq!(yen.turn(), 0);
+
+    let loaded = GameY::try_from(yen).unwrap();
+    assert_eq!(loaded.num_players(), 3);
+    assert_eq!(loaded.player_at(&Coordinates::new(1, 0, 1)), Some(PlayerId::new(2)));
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_resigned_player_survives_yen_roundtrip() {
+    let mut game = three_player_game(4);
+    place(&mut game, 0, Coordinates::new(3, 0, 0));
+    place(&mut game, 1, Coordinates::new(0, 3, 0));
+    game.add_move(Movement::Action {
+        player: PlayerId::new(2),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    place(&mut game, 0, Coordinates::new(1, 1, 1));
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.eliminated(), &[2]);
+    let json = serde_json::to_string(&yen).unwrap();
+    let loaded = GameY::try_from(serde_json::from_str::<YEN>(&json).unwrap()).unwrap();
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(1)));
+    assert_eq!(loaded.opponents(PlayerId::new(1)), vec![PlayerId::new(0)]);
+
+    // After player 1 moves, the turn goes back to player 0, not player 2
+    let mut loaded = loaded;
+    place(&mut loaded, 1, Coordinates::new(2, 1, 0));
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_yen_turn_of_eliminated_player_is_rejected() {
+    let players = vec!['B', 'R', 'G'];
+    let rules = GameRules {
+        num_players: 3,
+        ..GameRules::default()
+    };
+    let yen = YEN::new(3, 2, players.clone(), "./../...".to_string())
+        .with_rules(rules)
+        .with_eliminated(vec![2]);
+    assert!(matches!(
+        GameY::try_from(yen),
+        Err(GameYError::EliminatedPlayerTurn { .. })
+    ));
+
+    let yen = YEN::new(3, 0, players, "./../...".to_string())
+        .with_rules(rules)
+        .with_eliminated(vec![3]);
+    assert!(matches!(
+        GameY::try_from(yen),
+        Err(GameYError::InvalidEliminatedPlayer {
+            player: 3,
+            num_players: 3
+        })
+    ));
+}
+
+#[test]
+fn test_yen_with_one_player_left_is_finished() {
+    let rules = GameRules {
+        num_players: 3,
+        ..GameRules::default()
+    };
+    let yen = YEN::new(3, 0, vec!['B', 'R', 'G'], "./../...".to_string())
+        .with_rules(rules)
+        .with_eliminated(vec![0, 1]);
+    let game = GameY::try_from(yen).unwrap();
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(2)
+        }
+    );
+}
+
+#[test]
+fn test_yen_players_must_match_rules() {
+    let yen = YEN::new(3, 0, vec!['B', 'R', 'G'], "./../...".to_string());
+    assert!(matches!(
+        GameY::try_from(yen),
+        Err(GameYError::InvalidNumPlayers {
+            num_players: 3,
+            expected: 2
+        })
+    ));
+}
+
+// ============================================================================
+// Misère Tests
+// ============================================================================
+
+fn misere_rules() -> GameRules {
+    GameRules {
+        misere: true,
+        ..GameRules::default()
+    }
+}
+
+#[test]
+fn test_misere_connecting_sides_loses() {
+    let game = won_game_with_rules(misere_rules());
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(1)
+        }
+    );
+    assert!(game.winning_group().is_none());
+}
+
+#[test]
+fn test_misere_undo_restores_losing_move() {
+    let mut game = won_game_with_rules(misere_rules());
+    game.undo_move().unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_misere_three_players_eliminates_connecting_player() {
+    let rules = GameRules {
+        num_players: 3,
+        starting_player: PlayerId::new(2),
+        misere: true,
+        ..GameRules::default()
+    };
+    let mut game = GameY::with_rules(4, rules).unwrap();
+    for (player, coords) in [
+        (2, Coordinates::new(1, 1, 1)),
+        (0, Coordinates::new(3, 0, 0)),
+        (1, Coordinates::new(2, 1, 0)),
+        (2, Coordinates::new(0, 2, 1)),
+        (0, Coordinates::new(0, 3, 0)),
+        (1, Coordinates::new(2, 0, 1)),
+        (2, Coordinates::new(1, 0, 2)),
+        (0, Coordinates::new(0, 0, 3)),
+        (1, Coordinates::new(0, 1, 2)),
+        (2, Coordinates::new(1, 2, 0)),
+    ] {
+        place(&mut game, player, coords);
+    }
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(game.opponents(PlayerId::new(0)), vec![PlayerId::new(1)]);
+}
+
+#[test]
+fn test_connects_all_sides() {
+    let mut game = GameY::new(3);
+    place(&mut game, 0, Coordinates::new(0, 2, 0));
+    place(&mut game, 1, Coordinates::new(2, 0, 0));
+    place(&mut game, 0, Coordinates::new(0, 0, 2));
+
+    let p0 = PlayerId::new(0);
+    assert!(game.connects_all_sides(p0, &Coordinates::new(0, 1, 1)));
+    assert!(!game.connects_all_sides(p0, &Coordinates::new(1, 0, 1)));
+    assert!(!game.connects_all_sides(PlayerId::new(1), &Coordinates::new(0, 1, 1)));
+    // Occupied and off-board cells never connect
+    assert!(!game.connects_all_sides(p0, &Coordinates::new(0, 2, 0)));
+    assert!(!game.connects_all_sides(p0, &Coordinates::new(0, 1, 3)));
+}
+
+#[test]
+fn test_misere_rules_are_kept_in_yen() {
+    let game = won_game_with_rules(misere_rules());
+    let yen: YEN = (&game).into();
+    assert!(yen.rules().misere);
+
+    let loaded = GameY::try_from(yen).unwrap();
+    assert_eq!(
+        *loaded.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(1)
+        }
+    );
+}
+
+// ============================================================================
+// Board Shape Tests
+// ============================================================================
+
+#[test]
+fn test_blocked_cells_are_not_available() {
+    let game = GameY::new(3)
+        .with_blocked_cells([Coordinates::new(1, 0, 1)])
+        .unwrap();
+    assert_eq!(game.available_cells(), &vec![0, 2, 3, 4, 5]);
+    assert!(game.is_blocked(&Coordinates::new(1, 0, 1)));
+    assert!(!game.is_blocked(&Coordinates::new(1, 1, 0)));
+    assert_eq!(game.blocked_cells(), vec![Coordinates::new(1, 0, 1)]);
+}
+
+#[test]
+fn test_placing_on_blocked_cell_is_rejected() {
+    let mut game = GameY::new(3)
+        .with_blocked_cells([Coordinates::new(1, 0, 1)])
+        .unwrap();
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 0, 1),
+    });
+    assert!(matches!(result, Err(GameYError::BlockedCell { .. })));
+}
+
+#[test]
+fn test_blocking_after_first_move_is_rejected() {
+    let mut game = GameY::new(3);
+    place(&mut game, 0, Coordinates::new(2, 0, 0));
+    assert!(matches!(
+        game.with_blocked_cells([Coordinates::new(1, 0, 1)]),
+        Err(GameYError::BoardNotEmpty)
+    ));
+}
+
+#[test]
+fn test_blocking_off_board_cell_is_rejected() {
+    assert!(matches!(
+        GameY::new(3).with_blocked_cells([Coordinates::new(3, 0, 0)]),
+        Err(GameYError::CoordOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_hole_on_side_extends_the_side() {
+    let game = GameY::new(5)
+        .with_blocked_cells([Coordinates::new(0, 2, 2)])
+        .unwrap();
+    assert!(game.touches_side_a(&Coordinates::new(1, 1, 2)));
+    assert!(game.touches_side_a(&Coordinates::new(1, 2, 1)));
+    assert!(!game.touches_side_a(&Coordinates::new(0, 2, 2)));
+    assert!(!game.touches_side_a(&Coordinates::new(2, 1, 1)));
+}
+
+#[test]
+fn test_interior_hole_touches_no_side() {
+    let game = GameY::new(7)
+        .with_blocked_cells([Coordinates::new(2, 2, 2)])
+        .unwrap();
+    let neighbor = Coordinates::new(3, 1, 2);
+    assert!(!game.touches_side_a(&neighbor));
+    assert!(!game.touches_side_b(&neighbor));
+    assert!(!game.touches_side_c(&neighbor));
+}
+
+#[test]
+fn test_truncated_corners() {
+    let game = GameY::new(7).with_truncated_corners(2).unwrap();
+    assert_eq!(game.blocked_cells().len(), 9);
+    assert_eq!(game.available_cells().len(), 28 - 9);
+    assert!(game.is_blocked(&Coordinates::new(5, 1, 0)));
+    // The cut edge next to the corner of sides B and C touches both
+    let cut = Coordinates::new(4, 1, 1);
+    assert!(game.touches_side_b(&cut));
+    assert!(game.touches_side_c(&cut));
+    assert!(!game.touches_side_a(&cut));
+}
+
+#[test]
+fn test_win_through_blocked_corner() {
+    let mut game = GameY::new(3)
+        .with_blocked_cells([Coordinates::new(0, 2, 0)])
+        .unwrap();
+    place(&mut game, 0, Coordinates::new(0, 1, 1));
+    place(&mut game, 1, Coordinates::new(2, 0, 0));
+    place(&mut game, 0, Coordinates::new(1, 0, 1));
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(0)
+        }
+    );
+    assert_eq!(
+        game.minimal_winning_path().unwrap(),
+        vec![Coordinates::new(1, 0, 1), Coordinates::new(0, 1, 1)]
+    );
+}
+
+#[test]
+fn test_blocked_cells_yen_roundtrip() {
+    let mut game = GameY::new(3)
+        .with_blocked_cells([Coordinates::new(1, 0, 1)])
+        .unwrap();
+    place(&mut game, 0, Coordinates::new(2, 0, 0));
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.layout(), "B/#./...");
+
+    let loaded = GameY::try_from(yen).unwrap();
+    assert_eq!(loaded.blocked_cells(), vec![Coordinates::new(1, 0, 1)]);
+    assert_eq!(loaded.available_cells(), game.available_cells());
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(1)));
+}
+
+#[test]
+fn test_render_shows_blocked_cells() {
+    let game = GameY::new(2)
+        .with_blocked_cells([Coordinates::new(1, 0, 0)])
+        .unwrap();
+    let output = game.render(&RenderOptions {
+        show_3d_coords: false,
+        show_idx: false,
+        show_colors: false,
+    });
+    assert!(output.contains('#'));
+}
+
+// ============================================================================
+// Handicap Tests
+// ============================================================================
+
+#[test]
+fn test_handicap_stones_are_setup_moves() {
+    let game = GameY::new(7).with_handicap(PlayerId::new(0), 3).unwrap();
+    assert_eq!(
+        game.handicap_stones(),
+        vec![
+            Coordinates::new(2, 2, 2),
+            Coordinates::new(4, 1, 1),
+            Coordinates::new(1, 4, 1),
+        ]
+    );
+    assert_eq!(game.history().len(), 3);
+    assert!(
+        game.history()
+            .iter()
+            .all(|movement| matches!(movement, Movement::Setup { .. }))
+    );
+    assert_eq!(game.available_cells().len(), 25);
+    // Setup stones are not turns
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(game.player_at(&Coordinates::new(2, 2, 2)), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_too_many_handicap_stones() {
+    assert!(GameY::new(9).with_handicap(PlayerId::new(0), 7).is_ok());
+    assert!(matches!(
+        GameY::new(9).with_handicap(PlayerId::new(0), 8),
+        Err(GameYError::TooManyHandicapStones { count: 8, max: 7 })
+    ));
+}
+
+#[test]
+fn test_custom_handicap_stones() {
+    let stones = [Coordinates::new(0, 2, 2), Coordinates::new(2, 0, 2)];
+    let game = GameY::new(5)
+        .with_handicap_stones(PlayerId::new(1), stones)
+        .unwrap();
+    assert_eq!(game.handicap_stones(), stones.to_vec());
+    assert_eq!(game.player_at(&stones[1]), Some(PlayerId::new(1)));
+}
+
+#[test]
+fn test_handicap_after_first_move_is_rejected() {
+    let mut game = GameY::new(5);
+    place(&mut game, 0, Coordinates::new(2, 1, 1));
+    assert!(matches!(
+        game.with_handicap(PlayerId::new(1), 1),
+        Err(GameYError::BoardNotEmpty)
+    ));
+}
+
+#[test]
+fn test_handicap_stones_cannot_be_undone() {
+    let mut game = GameY::new(7).with_handicap(PlayerId::new(0), 2).unwrap();
+    place(&mut game, 0, Coordinates::new(0, 3, 3));
+    game.undo_move().unwrap();
+    assert!(matches!(game.undo_move(), Err(GameYError::NothingToUndo)));
+    assert_eq!(game.handicap_stones().len(), 2);
+}
+
+#[test]
+fn test_swap_not_allowed_with_handicap() {
+    let mut game = GameY::new(7).with_handicap(PlayerId::new(0), 1).unwrap();
+    place(&mut game, 0, Coordinates::new(0, 3, 3));
+    let result = game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    });
+    assert!(matches!(result, Err(GameYError::IllegalSwap { .. })));
+}
+
+#[test]
+fn test_setup_move_ignores_turn() {
+    let game = GameY::new(5);
+    let setup = Movement::Setup {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(2, 1, 1),
+    };
+    assert!(game.check_player_turn(&setup).is_ok());
+}
+
+#[test]
+fn test_handicap_yen_roundtrip() {
+    let mut game = GameY::new(5).with_handicap(PlayerId::new(1), 1).unwrap();
+    place(&mut game, 0, Coordinates::new(4, 0, 0));
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.handicap(), &[Coordinates::new(1, 1, 2)]);
+
+    let loaded = GameY::try_from(yen).unwrap();
+    assert_eq!(loaded.handicap_stones(), vec![Coordinates::new(1, 1, 2)]);
+    assert!(matches!(loaded.history()[0], Movement::Setup { .. }));
+    assert_eq!(loaded.player_at(&Coordinates::new(1, 1, 2)), Some(PlayerId::new(1)));
+}
+
+#[test]
+fn test_yen_handicap_on_empty_cell_is_rejected() {
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string())
+        .with_handicap(vec![Coordinates::new(1, 0, 1)]);
+    assert!(matches!(
+        GameY::try_from(yen),
+        Err(GameYError::HandicapCellEmpty { .. })
+    ));
+}
+
+// ============================================================================
+// Poly-Y Tests
+// ============================================================================
+
+#[test]
+fn test_poly_board_size() {
+    let game = PolyYGame::new(7, 4).unwrap();
+    assert_eq!(game.sides(), 7);
+    assert_eq!(game.radius(), 4);
+    assert_eq!(game.total_cells(), 1 + 7 * 10);
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_poly_rejects_triangles_and_squares() {
+    assert!(matches!(
+        PolyYGame::new(3, 4),
+        Err(GameYError::InvalidPolygon { sides: 3, .. })
+    ));
+    assert!(PolyYGame::new(4, 4).is_err());
+}
+
+#[test]
+fn test_poly_corner_needs_a_third_side() {
+    let mut game = PolyYGame::new(5, 2).unwrap();
+    game.play(PlayerId::new(0), game.corner_cell(0)).unwrap();
+    game.play(PlayerId::new(1), PolyCell::new(0, 0)).unwrap();
+    game.play(PlayerId::new(0), game.corner_cell(1)).unwrap();
+    assert_eq!(game.corners_owned(PlayerId::new(0)), 0);
+    game.play(PlayerId::new(1), PolyCell::new(2, 5)).unwrap();
+    // The edge cell between them joins sides 4, 0 and 1
+    game.play(PlayerId::new(0), PolyCell::new(2, 1)).unwrap();
+    assert_eq!(game.corner_owner(0), Some(PlayerId::new(0)));
+    assert_eq!(game.corner_owner(1), Some(PlayerId::new(0)));
+    assert_eq!(game.corners_owned(PlayerId::new(1)), 0);
+}
+
+#[test]
+fn test_poly_single_corner_stones_own_nothing() {
+    let mut game = PolyYGame::new(5, 20).unwrap();
+    for corner in 0..5 {
+        let player = game.next_player().unwrap();
+        game.play(player, game.corner_cell(corner)).unwrap();
+    }
+    assert_eq!(game.corners_owned(PlayerId::new(0)), 0);
+    assert_eq!(game.corners_owned(PlayerId::new(1)), 0);
+    assert!(!game.check_game_over());
+}
+
+#[test]
+fn test_poly_majority_wins() {
+    let mut game = PolyYGame::new(5, 1).unwrap();
+    let moves = [(0, 0), (1, 3), (0, 1), (1, 4), (0, 2)];
+    for (i, (player, position)) in moves.iter().enumerate() {
+        assert!(!game.check_game_over(), "game ended after {} moves", i);
+        game.play(PlayerId::new(*player), PolyCell::new(1, *position))
+            .unwrap();
+    }
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(0)
+        }
+    );
+    assert_eq!(game.corners_owned(PlayerId::new(0)), 3);
+}
+
+// ============================================================================
+// Hex Tests
+// ============================================================================
+
+#[test]
+fn test_hex_new_game() {
+    let game = HexGame::new(11).unwrap();
+    assert_eq!(game.board_size(), 11);
+    assert_eq!(game.available_cells().len(), 121);
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert!(game.history().is_empty());
+}
+
+#[test]
+fn test_hex_winner_by_notation() {
+    let game = HexGame::from_moves(4, "a1 d1 a2 c2 a3 b3 b3").unwrap_err();
+    assert!(matches!(game, GameYError::HexCellOccupied { .. }));
+
+    let game = HexGame::from_moves(4, "a1 d1 a2 c2 a3 b3 a4").unwrap();
+    assert_eq!(
+        *game.status(),
+        GameStatus::Finished {
+            winner: PlayerId::new(0)
+        }
+    );
+    assert_eq!(game.moves_notation(), "a1 d1 a2 c2 a3 b3 a4");
+}
+
+#[test]
+fn test_hex_invalid_notation() {
+    assert!(matches!(
+        HexGame::from_moves(4, "a1 e9"),
+        Err(GameYError::InvalidHexCell { .. })
+    ));
+    assert!(matches!(
+        HexGame::from_moves(4, "a1 9e"),
+        Err(GameYError::InvalidHexNotation { .. })
+    ));
+}
+
+#[test]
+fn test_hex_render_shows_stones() {
+    let mut game = HexGame::new(3).unwrap();
+    game.play(PlayerId::new(0), HexCell::new(1, 1)).unwrap();
+    let options = RenderOptions {
+        show_3d_coords: true,
+        show_idx: true,
+        show_colors: false,
+    };
+    let rendered = game.render(&options);
+    assert!(rendered.contains("Hex (Size 3)"));
+    assert!(rendered.contains("0(b2)(4)"));
+    assert!(rendered.contains(".(c3)(8)"));
+}
+
+// ============================================================================
+// Tetrahedral Variant Tests
+// ============================================================================
+
+#[test]
+fn test_tetra_new_game() {
+    let game = TetraGame::new(4).unwrap();
+    assert_eq!(game.board_size(), 4);
+    assert_eq!(game.available_moves().len(), 20);
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(game.winner(), None);
+}
+
+#[test]
+fn test_tetra_turns_alternate() {
+    let mut game = TetraGame::new(3).unwrap();
+    game.place(TetraCoordinates::new(1, 1, 0, 0), PlayerId::new(0))
+        .unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert_eq!(
+        game.player_at(&TetraCoordinates::new(1, 1, 0, 0)),
+        Some(PlayerId::new(0))
+    );
+    assert_eq!(game.available_moves().len(), 9);
+}
+
+#[test]
+fn test_tetra_rejects_cells_off_the_board() {
+    let mut game = TetraGame::new(3).unwrap();
+    let result = game.place(TetraCoordinates::new(3, 0, 0, 0), PlayerId::new(0));
+    assert!(matches!(
+        result,
+        Err(GameYError::InvalidTetraCoordinates { .. })
+    ));
+}
+
+#[test]
+fn test_tetra_save_and_load_file() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("test_game.tyen");
+
+    let mut game = TetraGame::new(4).unwrap();
+    game.place(TetraCoordinates::new(1, 1, 1, 0), PlayerId::new(0))
+        .unwrap();
+    game.place(TetraCoordinates::new(0, 0, 0, 3), PlayerId::new(1))
+        .unwrap();
+    game.save_to_file(&file_path).unwrap();
+
+    let loaded = TetraGame::load_from_file(&file_path).unwrap();
+    assert_eq!(loaded.board_size(), 4);
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(
+        TetraYEN::from(&loaded).layout(),
+        TetraYEN::from(&game).layout()
+    );
+}
+
+#[test]
+fn test_tetra_load_nonexistent_file() {
+    let result = TetraGame::load_from_file("/nonexistent/path/game.tyen");
+    assert!(matches!(result, Err(GameYError::IoError { .. })));
+}
+
+#[test]
+fn test_tetra_yen_with_huge_size_is_rejected() {
+    let yen = TetraYEN::new(4_000_000_000, 0, vec!['B', 'R'], ".".to_string());
+    assert!(matches!(
+        TetraGame::try_from(yen),
+        Err(GameYError::InvalidTetraSize {
+            size: 4_000_000_000,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_tetra_yen_layer_count_must_match_size() {
+    let yen = TetraYEN::new(3, 0, vec!['B', 'R'], ".".to_string());
+    assert!(matches!(
+        TetraGame::try_from(yen),
+        Err(GameYError::InvalidTetraLayout {
+            expected: 3,
+            found: 1
+        })
+    ));
+}
+
+// ============================================================================
+// Undo / Redo Tests
+// ============================================================================
+
+#[test]
+fn test_undo_on_new_game_fails() {
+    let mut game = GameY::new(3);
+    assert!(matches!(game.undo_move(), Err(GameYError::NothingToUndo)));
+}
+
+#[test]
+fn test_redo_without_undo_fails() {
+    let mut game = GameY::new(3);
+    assert!(matches!(game.redo_move(), Err(GameYError::NothingToRedo)));
+}
+
+#[test]
+fn test_undo_restores_board_and_turn() {
+    let mut game = GameY::new(3);
+    let empty_layout = YEN::from(&game).layout().to_string();
+
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 1, 0),
+    })
+    .unwrap();
+
+    let undone = game.undo_move().unwrap();
+    assert!(matches!(undone, Movement::Placement { .. }));
+    assert_eq!(YEN::from(&game).layout(), empty_layout);
+    assert_eq!(game.available_cells(), &(0..6).collect::<Vec<u32>>());
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert!(game.history().is_empty());
+}
+
+#[test]
+fn test_redo_replays_undone_move() {
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 1, 0),
+    })
+    .unwrap();
+    let layout = YEN::from(&game).layout().to_string();
+
+    game.undo_move().unwrap();
+    game.redo_move().unwrap();
+
+    assert_eq!(YEN::from(&game).layout(), layout);
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert_eq!(game.history().len(), 1);
+}
+
+#[test]
+fn test_new_move_clears_redo() {
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 1, 0),
+    })
+    .unwrap();
+    game.undo_move().unwrap();
+
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+
+    assert!(matches!(game.redo_move(), Err(GameYError::NothingToRedo)));
+}
+
+#[test]
+fn test_undo_winning_move_reopens_game() {
+    let mut game = GameY::new(3);
+    let moves = [
+        (0, Coordinates::new(0, 2, 0)),
+        (1, Coordinates::new(2, 0, 0)),
+        (0, Coordinates::new(0, 1, 1)),
+        (1, Coordinates::new(1, 1, 0)),
+        (0, Coordinates::new(0, 0, 2)),
+    ];
+    for (player, coords) in moves {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords,
+        })
+        .unwrap();
+    }
+    assert!(game.check_game_over());
+
+    game.undo_move().unwrap();
+    assert!(!game.check_game_over());
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+    assert_eq!(game.available_cells().len(), 2);
+
+    game.redo_move().unwrap();
+    match game.status() {
+        GameStatus::Finished { winner } => assert_eq!(*winner, PlayerId::new(0)),
+        _ => panic!("Redoing the winning move should finish the game"),
+    }
+}
+
+#[test]
+fn test_undo_resign() {
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    assert!(game.check_game_over());
+
+    game.undo_move().unwrap();
+    assert!(!game.check_game_over());
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_undo_all_then_redo_all_reaches_same_position() {
+    let mut game = GameY::new(5);
+    for idx in 0..10 {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(idx % 2),
+            coords: Coordinates::from_index(idx * 2 % 15, 5),
+        })
+        .unwrap();
+    }
+    let layout = YEN::from(&game).layout().to_string();
+    let available = game.available_cells().clone();
+
+    while game.undo_move().is_ok() {}
+    assert_eq!(game.available_cells().len(), 15);
+    while game.redo_move().is_ok() {}
+
+    assert_eq!(YEN::from(&game).layout(), layout);
+    assert_eq!(game.available_cells(), &available);
+    assert_eq!(game.history().len(), 10);
+}
+
+// ============================================================================
+// Position Hash Tests
+// ============================================================================
+
+fn place(game: &mut GameY, player: u32, coords: Coordinates) {
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(player),
+        coords,
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_same_position_by_different_move_orders_has_same_hash() {
+    let a = Coordinates::new(2, 1, 1);
+    let b = Coordinates::new(1, 1, 2);
+    let c = Coordinates::new(0, 2, 2);
+    let d = Coordinates::new(4, 0, 0);
+
+    let mut first = GameY::new(5);
+    place(&mut first, 0, a);
+    place(&mut first, 1, b);
+    place(&mut first, 0, c);
+    place(&mut first, 1, d);
+
+    let mut second = GameY::new(5);
+    place(&mut second, 0, c);
+    place(&mut second, 1, d);
+    place(&mut second, 0, a);
+    place(&mut second, 1, b);
+
+    assert_eq!(first.position_hash(), second.position_hash());
+}
+
+#[test]
+fn test_position_hash_depends_on_stones() {
+    let mut first = GameY::new(5);
+    place(&mut first, 0, Coordinates::new(2, 1, 1));
+    let mut second = GameY::new(5);
+    place(&mut second, 0, Coordinates::new(1, 1, 2));
+
+    assert_ne!(first.position_hash(), second.position_hash());
+    assert_ne!(first.position_hash(), GameY::new(5).position_hash());
+}
+
+#[test]
+fn test_position_hash_includes_side_to_move() {
+    let a = Coordinates::new(2, 1, 1);
+    let b = Coordinates::new(1, 1, 2);
+
+    // Same stones, but the last stone placed decides who moves next
+    let mut blue_to_move = GameY::new(5);
+    place(&mut blue_to_move, 0, a);
+    place(&mut blue_to_move, 1, b);
+    let mut red_to_move = GameY::new(5);
+    place(&mut red_to_move, 1, b);
+    place(&mut red_to_move, 0, a);
+
+    assert_eq!(YEN::from(&blue_to_move).layout(), YEN::from(&red_to_move).layout());
+    assert_ne!(blue_to_move.next_player(), red_to_move.next_player());
+    assert_ne!(blue_to_move.position_hash(), red_to_move.position_hash());
+}
+
+#[test]
+fn test_undo_restores_position_hash() {
+    let mut game = GameY::new(5);
+    let mut hashes = vec![game.position_hash()];
+    for idx in 0..8 {
+        place(&mut game, idx % 2, Coordinates::from_index(idx * 2 % 15, 5));
+        hashes.push(game.position_hash());
+    }
+    while game.undo_move().is_ok() {
+        hashes.pop();
+        assert_eq!(game.position_hash(), *hashes.last().unwrap());
+    }
+    assert_eq!(game.position_hash(), GameY::new(5).position_hash());
+}
+
+#[test]
+fn test_swap_updates_position_hash() {
+    let mut game = GameY::new(5).with_swap_mode(SwapMode::Mirror);
+    place(&mut game, 0, Coordinates::new(1, 3, 0));
+    let before = game.position_hash();
+
+    game.add_move(Movement::Action {
+        player: PlayerId::new(1),
+        action: GameAction::Swap,
+    })
+    .unwrap();
+
+    // Red owns the mirrored stone and blue is to move
+    let mut same_position = GameY::new(5);
+    place(&mut same_position, 1, Coordinates::new(1, 0, 3));
+    assert_eq!(game.position_hash(), same_position.position_hash());
+
+    game.undo_move().unwrap();
+    assert_eq!(game.position_hash(), before);
+}
+
+// ============================================================================
+// Symmetry Tests
+// ============================================================================
+
+#[test]
+fn test_canonical_form_of_empty_board_uses_identity() {
+    let game = GameY::new(4);
+    let (yen, symmetry) = game.canonical_form();
+    assert_eq!(symmetry, Symmetry::Identity);
+    assert_eq!(yen.layout(), YEN::from(&game).layout());
+}
+
+#[test]
+fn test_symmetric_positions_share_canonical_form() {
+    let stones = [
+        (0, Coordinates::new(3, 1, 0)),
+        (1, Coordinates::new(1, 1, 2)),
+        (0, Coordinates::new(0, 4, 0)),
+    ];
+    let mut canonical = Vec::new();
+    for symmetry in Symmetry::ALL {
+        let mut game = GameY::new(5);
+        for (player, coords) in stones {
+            place(&mut game, player, coords.transform(symmetry, 5));
+        }
+        canonical.push(game.canonical_form().0);
+    }
+    for yen in &canonical {
+        assert_eq!(yen.layout(), canonical[0].layout());
+        assert_eq!(yen.turn(), 1);
+    }
+}
+
+#[test]
+fn test_canonical_form_is_smallest_layout() {
+    let mut game = GameY::new(4);
+    place(&mut game, 0, Coordinates::new(0, 0, 3));
+    let (yen, symmetry) = game.canonical_form();
+
+    // Empty cells sort first, so the stone is moved to the corner that comes
+    // last in the layout
+    assert_eq!(yen.layout(), "./../.../...B");
+    assert_eq!(Coordinates::new(0, 0, 3).transform(symmetry, 4), Coordinates::new(0, 3, 0));
+}
+
+#[test]
+fn test_canonical_form_is_stable() {
+    let mut game = GameY::new(5);
+    place(&mut game, 0, Coordinates::new(1, 3, 0));
+    place(&mut game, 1, Coordinates::new(2, 0, 2));
+    let (yen, _) = game.canonical_form();
+
+    let loaded = GameY::try_from(yen.clone()).unwrap();
+    let (again, symmetry) = loaded.canonical_form();
+    assert_eq!(again.layout(), yen.layout());
+    assert_eq!(symmetry, Symmetry::Identity);
+}
+
 // ============================================================================
 // YEN Serialization Tests
 // ============================================================================